@@ -1,14 +1,160 @@
 import Vue from 'https://cdn.jsdelivr.net/npm/vue@2.6.11/dist/vue.esm.browser.js';
+import DocSearch, { revealSymbol, symbolId } from './search.js';
+
+Vue.component('doc-node', {
+  name: 'doc-node',
+  props: {
+    docNode: Object,
+    // Names of enclosing namespaces.
+    parents: { type: Array, default: () => [] }
+  },
+  computed: {
+    path: function () {
+      return this.parents.concat([this.docNode.name]);
+    },
+    qualifiedName: function () {
+      return this.path.join(".");
+    },
+    id: function () {
+      return symbolId(this.qualifiedName);
+    }
+  },
+  data: function () {
+    return { copied: false };
+  },
+  methods: {
+    copyImport: async function () {
+      await navigator.clipboard.writeText(this.docNode.importStatement);
+      this.copied = true;
+      setTimeout(() => this.copied = false, 1500);
+    },
+    memberPath: function (member) {
+      return this.path.concat([member.name]);
+    },
+    memberId: function (member) {
+      return symbolId(this.memberPath(member).join("."));
+    },
+    paramRepr: function (param) {
+      let name = param.kind === "rest" ? "..." + param.name : param.name;
+      if (param.optional) {
+        name += "?";
+      }
+      return param.tsType ? name + ": " + param.tsType.repr : name;
+    }
+  },
+  template: '#doc-node-template'
+});
+
+// Path of a symbol as links to the module and each enclosing symbol.
+Vue.component('breadcrumbs', {
+  props: {
+    filename: String,
+    path: Array
+  },
+  computed: {
+    crumbs: function () {
+      return this.path.map((name, i) => ({
+        name,
+        id: symbolId(this.path.slice(0, i + 1).join("."))
+      }));
+    }
+  },
+  template: `
+    <nav class="breadcrumbs">
+      <a href="#">{{ filename }}</a>
+      <template v-for="crumb in crumbs">
+        › <a :href="'#' + crumb.id">{{ crumb.name }}</a>
+      </template>
+    </nav>
+  `
+});
+
+// Generic type parameters of a function, class, interface or type alias.
+Vue.component('type-params', {
+  props: {
+    typeParams: Array
+  },
+  template: `
+    <div v-if="typeParams.length">
+      <b>Type params:</b><br>
+      <ul>
+        <li v-for="typeParam in typeParams">
+          <span>{{ typeParam.name }}</span>
+          <span v-if="typeParam.constraint"> extends <i>{{ typeParam.constraint.repr }}</i></span>
+          <span v-if="typeParam.default"> = <i>{{ typeParam.default.repr }}</i></span>
+        </li>
+      </ul>
+    </div>
+  `
+});
+
+Vue.component('doc-search', DocSearch);
 
 var app = new Vue({
 el: '#doc',
 data: {
-    docs: null
+    docs: null,
+    moduleDoc: null,
+    docsUrl: null,
+    workspace: null
 },
 created: async function () {
     console.log("loading json data");
-    const res = await fetch("./docs.json");
-    this.docs = await res.json();
-    console.log(this.docs);
+    this.docsUrl = new URLSearchParams(location.search).get("docs") || "./docs.json";
+    const res = await fetch(this.docsUrl);
+    const json = await res.json();
+    // Workspace indexes list packages instead of symbols.
+    if (Array.isArray(json)) {
+      // The description of the module comes first, if it has one.
+      const moduleDoc = json.find(docNode => docNode.kind === "moduleDoc");
+      this.moduleDoc = moduleDoc ? moduleDoc.jsDoc : null;
+      this.docs = json.filter(docNode => docNode.kind !== "moduleDoc");
+    } else {
+      this.workspace = json;
+    }
+    console.log(json);
+    // Symbols linked to only exist once the docs are rendered.
+    await this.$nextTick();
+    const element = location.hash && revealSymbol(location.hash.slice(1));
+    if (element) {
+      element.scrollIntoView();
+    }
+  },
+computed: {
+    // Symbols grouped by their `@category`, uncategorized ones first.
+    groups: function () {
+      const groups = [];
+      for (const docNode of this.docs || []) {
+        const category = docNode.category || null;
+        let group = groups.find(group => group.category === category);
+        if (!group) {
+          group = { category, docs: [] };
+          groups.push(group);
+        }
+        group.docs.push(docNode);
+      }
+      return groups.sort((a, b) =>
+        a.category === b.category ? 0 :
+        a.category === null ? -1 :
+        b.category === null ? 1 :
+        a.category.localeCompare(b.category));
+    }
+  },
+methods: {
+    // Link to the documentation of a package of the workspace, optionally
+    // to one of its symbols.
+    packageUrl: function (packageName, symbol) {
+      const pkg = this.workspace.packages.find(pkg => pkg.name === packageName);
+      const docs = new URL(pkg.docs, new URL(this.docsUrl, location.href));
+      const url = "?docs=" + encodeURIComponent(docs.pathname);
+      return symbol ? url + "#" + symbolId(symbol) : url;
+    },
+    toggleTheme: function () {
+      const current = document.documentElement.dataset.theme ||
+        (matchMedia("(prefers-color-scheme: dark)").matches ? "dark" : "light");
+      const theme = current === "dark" ? "light" : "dark";
+      document.documentElement.dataset.theme = theme;
+      localStorage.setItem("theme", theme);
+    }
   }
 });
\ No newline at end of file
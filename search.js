@@ -0,0 +1,119 @@
+// Quick-open search over all documented symbols, including namespace
+// elements. Press `s` (or `/`) anywhere on the page to focus it.
+
+function collectSymbols(docNodes, prefix, symbols) {
+  for (const docNode of docNodes) {
+    const name = prefix + docNode.name;
+    symbols.push({ name, kind: docNode.kind, id: symbolId(name) });
+    if (docNode.namespaceDef) {
+      collectSymbols(docNode.namespaceDef.elements, name + ".", symbols);
+    }
+  }
+  return symbols;
+}
+
+// Expand collapsed sections containing the symbol, so it can be jumped to.
+export function revealSymbol(id) {
+  const element = document.getElementById(id);
+  for (let parent = element; parent; parent = parent.parentElement) {
+    if (parent.tagName === "DETAILS") {
+      parent.open = true;
+    }
+  }
+  return element;
+}
+
+export function symbolId(name) {
+  return "symbol-" + name;
+}
+
+export default {
+  name: 'doc-search',
+  props: ['docs'],
+  data: function () {
+    return {
+      query: "",
+      selected: 0
+    };
+  },
+  computed: {
+    symbols: function () {
+      return collectSymbols(this.docs || [], "", []);
+    },
+    results: function () {
+      const query = this.query.trim().toLowerCase();
+      if (!query) {
+        return [];
+      }
+      const matches = this.symbols.filter(symbol => symbol.name.toLowerCase().includes(query));
+      // Symbols whose own name starts with the query come first.
+      const rank = symbol => {
+        const name = symbol.name.toLowerCase();
+        const ownName = name.slice(name.lastIndexOf(".") + 1);
+        return ownName.startsWith(query) ? 0 : 1;
+      };
+      matches.sort((a, b) => rank(a) - rank(b) || a.name.localeCompare(b.name));
+      return matches.slice(0, 20);
+    }
+  },
+  watch: {
+    query: function () {
+      this.selected = 0;
+    }
+  },
+  mounted: function () {
+    this.onKeyDown = event => {
+      const target = event.target;
+      const typing = target.tagName === "INPUT" || target.tagName === "TEXTAREA";
+      if (!typing && (event.key === "s" || event.key === "/")) {
+        event.preventDefault();
+        this.$refs.input.focus();
+      }
+    };
+    document.addEventListener("keydown", this.onKeyDown);
+  },
+  beforeDestroy: function () {
+    document.removeEventListener("keydown", this.onKeyDown);
+  },
+  methods: {
+    move: function (delta) {
+      const count = this.results.length;
+      if (count) {
+        this.selected = (this.selected + delta + count) % count;
+      }
+    },
+    open: function (symbol) {
+      if (!symbol) {
+        return;
+      }
+      revealSymbol(symbol.id);
+      window.location.hash = "#" + symbol.id;
+      this.query = "";
+      this.$refs.input.blur();
+    },
+    close: function () {
+      this.query = "";
+      this.$refs.input.blur();
+    }
+  },
+  template: `
+    <div class="doc-search">
+      <input
+        ref="input"
+        v-model="query"
+        placeholder="Search (press s)"
+        @keydown.down.prevent="move(1)"
+        @keydown.up.prevent="move(-1)"
+        @keydown.enter.prevent="open(results[selected])"
+        @keydown.esc="close">
+      <ul v-if="results.length">
+        <li
+          v-for="(symbol, i) in results"
+          :class="{ selected: i === selected }"
+          @mousedown.prevent="open(symbol)">
+          <i>{{ symbol.kind }}</i> {{ symbol.name }}
+        </li>
+      </ul>
+    </div>
+  `
+};
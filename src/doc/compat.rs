@@ -0,0 +1,38 @@
+//! String helpers which are only in the standard library of Rust versions
+//! newer than the one CI builds with.
+
+/// Like `str::split_once`.
+pub fn split_once<'a>(
+  s: &'a str,
+  delimiter: &str,
+) -> Option<(&'a str, &'a str)> {
+  let index = s.find(delimiter)?;
+  Some((&s[..index], &s[index + delimiter.len()..]))
+}
+
+/// Like `str::rsplit_once`.
+pub fn rsplit_once<'a>(
+  s: &'a str,
+  delimiter: &str,
+) -> Option<(&'a str, &'a str)> {
+  let index = s.rfind(delimiter)?;
+  Some((&s[..index], &s[index + delimiter.len()..]))
+}
+
+/// Like `str::strip_prefix`.
+pub fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+  if s.starts_with(prefix) {
+    s.get(prefix.len()..)
+  } else {
+    None
+  }
+}
+
+/// Like `str::strip_suffix`.
+pub fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+  if s.ends_with(suffix) {
+    s.get(..s.len() - suffix.len())
+  } else {
+    None
+  }
+}
@@ -0,0 +1,115 @@
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+
+struct Tag<'a> {
+  name: String,
+  /// Namespace containing the symbol.
+  scope: Option<String>,
+  node: &'a DocNode,
+}
+
+fn collect_tags<'a>(
+  doc_nodes: &'a [DocNode],
+  scope: Option<&str>,
+  tags: &mut Vec<Tag<'a>>,
+) {
+  for node in doc_nodes {
+    tags.push(Tag {
+      name: node.name.to_string(),
+      scope: scope.map(|scope| scope.to_string()),
+      node,
+    });
+    if let Some(namespace_def) = &node.namespace_def {
+      let scope = match scope {
+        Some(scope) => format!("{}.{}", scope, node.name),
+        None => node.name.to_string(),
+      };
+      collect_tags(&namespace_def.elements, Some(&scope), tags);
+    }
+  }
+}
+
+fn kind_letter(kind: &DocNodeKind) -> &'static str {
+  match kind {
+    DocNodeKind::Function => "f",
+    DocNodeKind::Variable => "v",
+    DocNodeKind::Class => "c",
+    DocNodeKind::Enum => "e",
+    DocNodeKind::Interface => "i",
+    DocNodeKind::TypeAlias => "t",
+    DocNodeKind::Namespace => "n",
+  }
+}
+
+/// Tags file in the extended ctags format, as read by vim, pointing at the
+/// line declaring each symbol.
+pub fn print_ctags(doc_nodes: &[DocNode]) -> String {
+  let mut tags = vec![];
+  collect_tags(doc_nodes, None, &mut tags);
+  // Sorted by bytes so editors can binary search the file.
+  tags.sort_by(|a, b| {
+    a.name
+      .cmp(&b.name)
+      .then_with(|| a.node.location.filename.cmp(&b.node.location.filename))
+      .then_with(|| a.node.location.line.cmp(&b.node.location.line))
+  });
+
+  let mut out = String::new();
+  out.push_str("!_TAG_FILE_FORMAT\t2\t/extended format/\n");
+  out.push_str("!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/\n");
+  out.push_str("!_TAG_PROGRAM_NAME\tdeno_doc\t//\n");
+  for tag in &tags {
+    out.push_str(&format!(
+      "{}\t{}\t{};\"\t{}",
+      tag.name,
+      tag.node.location.filename,
+      tag.node.location.line,
+      kind_letter(&tag.node.kind)
+    ));
+    if let Some(scope) = &tag.scope {
+      out.push_str(&format!("\tnamespace:{}", scope));
+    }
+    out.push('\n');
+  }
+  out
+}
+
+/// Tags file in the etags format, as read by emacs. Each tag has the start of
+/// its declaration for emacs to search for around the line.
+pub fn print_etags(doc_nodes: &[DocNode]) -> String {
+  let mut tags = vec![];
+  collect_tags(doc_nodes, None, &mut tags);
+
+  let mut files: Vec<(&str, Vec<&Tag>)> = vec![];
+  for tag in &tags {
+    let filename = tag.node.location.filename.as_str();
+    match files.iter_mut().find(|(other, _)| *other == filename) {
+      Some((_, file_tags)) => file_tags.push(tag),
+      None => files.push((filename, vec![tag])),
+    }
+  }
+
+  let mut out = String::new();
+  for (filename, file_tags) in files {
+    let mut section = String::new();
+    for tag in file_tags {
+      let text = tag.node.snippet.lines().next().unwrap_or("");
+      // The definition text has to end with the tag name when it's known.
+      let text = match text.find(&tag.name) {
+        Some(index) => &text[..index + tag.name.len()],
+        None => text,
+      };
+      section.push_str(&format!(
+        "{}\u{7f}{}\u{1}{},\n",
+        text, tag.name, tag.node.location.line
+      ));
+    }
+    out.push_str(&format!(
+      "\u{c}\n{},{}\n{}",
+      filename,
+      section.len(),
+      section
+    ));
+  }
+  out
+}
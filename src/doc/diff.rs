@@ -0,0 +1,169 @@
+use serde::Serialize;
+
+use crate::doc::graph::kind_name;
+use crate::doc::printer::format_call_signature;
+use crate::doc::printer::format_index_signature;
+use crate::doc::printer::format_interface_method;
+use crate::doc::printer::format_interface_property;
+use crate::doc::printer::format_signature;
+use crate::doc::printer::PrinterOptions;
+use crate::doc::DocNode;
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+  Added,
+  Removed,
+  Changed,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+  pub kind: ChangeKind,
+  /// Qualified name of the symbol, eg. `Ns.Class.method`.
+  pub path: String,
+  /// Kind of the symbol, a `DocNodeKind` or a kind of member.
+  pub symbol_kind: &'static str,
+  pub before: Option<String>,
+  pub after: Option<String>,
+}
+
+struct Symbol {
+  path: String,
+  kind: &'static str,
+  signature: String,
+}
+
+/// Every symbol and member of `doc_nodes` along with its signature.
+fn collect_symbols(
+  doc_nodes: &[DocNode],
+  namespace: &str,
+  symbols: &mut Vec<Symbol>,
+) {
+  let options = PrinterOptions::default();
+  for node in doc_nodes {
+    let path = format!("{}{}", namespace, node.name);
+    symbols.push(Symbol {
+      path: path.to_string(),
+      kind: kind_name(node),
+      signature: format_signature(node, &options),
+    });
+
+    let mut push_member = |name: &str, kind, signature: String| {
+      symbols.push(Symbol {
+        path: format!("{}.{}", path, name),
+        kind,
+        signature,
+      })
+    };
+
+    if let Some(class_def) = &node.class_def {
+      for ctor in &class_def.constructors {
+        push_member(&ctor.name, "constructor", ctor.snippet.to_string());
+      }
+      for prop in &class_def.properties {
+        push_member(&prop.name, "property", prop.snippet.to_string());
+      }
+      for method in &class_def.methods {
+        push_member(&method.name, "method", method.snippet.to_string());
+      }
+    }
+
+    if let Some(enum_def) = &node.enum_def {
+      for member in &enum_def.members {
+        let signature = match (&member.value, &member.init) {
+          (Some(value), _) => format!("{} = {}", member.name, value),
+          (None, Some(init)) => format!("{} = {}", member.name, init),
+          (None, None) => member.name.to_string(),
+        };
+        push_member(&member.name, "enumMember", signature);
+      }
+    }
+
+    if let Some(interface_def) = &node.interface_def {
+      for prop in &interface_def.properties {
+        push_member(&prop.name, "property", format_interface_property(prop));
+      }
+      for method in &interface_def.methods {
+        push_member(&method.name, "method", format_interface_method(method));
+      }
+      // Signatures without a name are told apart by their position.
+      for (i, call) in interface_def.call_signatures.iter().enumerate() {
+        let name = format!("({})", i);
+        push_member(&name, "callSignature", format_call_signature(call, false));
+      }
+      for (i, construct) in
+        interface_def.construct_signatures.iter().enumerate()
+      {
+        let name = format!("new({})", i);
+        let signature = format_call_signature(construct, true);
+        push_member(&name, "constructSignature", signature);
+      }
+      for (i, index) in interface_def.index_signatures.iter().enumerate() {
+        let name = format!("[{}]", i);
+        let signature = format_index_signature(index);
+        push_member(&name, "indexSignature", signature);
+      }
+    }
+
+    if let Some(namespace_def) = &node.namespace_def {
+      collect_symbols(&namespace_def.elements, &format!("{}.", path), symbols);
+    }
+  }
+}
+
+/// Changes to the public API between `old` and `new`, a symbol changes when
+/// its signature does. Removed and changed symbols come in the order of `old`,
+/// followed by added ones in the order of `new`.
+pub fn diff(old: &[DocNode], new: &[DocNode]) -> Vec<Change> {
+  let mut old_symbols = vec![];
+  collect_symbols(old, "", &mut old_symbols);
+  let mut new_symbols = vec![];
+  collect_symbols(new, "", &mut new_symbols);
+
+  let find = |symbols: &[Symbol], symbol: &Symbol| {
+    symbols
+      .iter()
+      .position(|other| other.path == symbol.path && other.kind == symbol.kind)
+  };
+
+  let mut changes = vec![];
+  for old_symbol in &old_symbols {
+    match find(&new_symbols, old_symbol) {
+      None => changes.push(Change {
+        kind: ChangeKind::Removed,
+        path: old_symbol.path.to_string(),
+        symbol_kind: old_symbol.kind,
+        before: Some(old_symbol.signature.to_string()),
+        after: None,
+      }),
+      Some(index) => {
+        let new_symbol = &new_symbols[index];
+        if new_symbol.signature != old_symbol.signature {
+          changes.push(Change {
+            kind: ChangeKind::Changed,
+            path: old_symbol.path.to_string(),
+            symbol_kind: old_symbol.kind,
+            before: Some(old_symbol.signature.to_string()),
+            after: Some(new_symbol.signature.to_string()),
+          });
+        }
+      }
+    }
+  }
+
+  for new_symbol in &new_symbols {
+    if find(&old_symbols, new_symbol).is_none() {
+      changes.push(Change {
+        kind: ChangeKind::Added,
+        path: new_symbol.path.to_string(),
+        symbol_kind: new_symbol.kind,
+        before: None,
+        after: Some(new_symbol.signature.to_string()),
+      });
+    }
+  }
+
+  changes
+}
@@ -0,0 +1,124 @@
+use crate::doc::graph::GraphNode;
+use crate::doc::graph::RelationshipKind;
+use crate::doc::graph::SymbolGraph;
+
+fn is_member(node: &GraphNode) -> bool {
+  matches!(
+    node.kind,
+    "constructor" | "property" | "method" | "enumMember"
+  )
+}
+
+fn quote(id: &str) -> String {
+  format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Id of the symbol owning `id`: members are drawn as part of their class,
+/// interface or enum.
+fn owner<'a>(graph: &'a SymbolGraph, id: &'a str) -> &'a str {
+  let mut id = id;
+  while let Some(node) = graph.nodes.iter().find(|node| node.id == id) {
+    if !is_member(node) {
+      break;
+    }
+    let parent = graph
+      .relationships
+      .iter()
+      .find(|r| r.kind == RelationshipKind::MemberOf && r.source == node.id);
+    match parent {
+      Some(parent) => id = &parent.target,
+      None => break,
+    }
+  }
+  id
+}
+
+fn print_cluster(
+  graph: &SymbolGraph,
+  namespace: Option<&str>,
+  indent: usize,
+  out: &mut String,
+) {
+  let padding = "  ".repeat(indent);
+  for node in &graph.nodes {
+    if is_member(node) {
+      continue;
+    }
+    let parent = graph
+      .relationships
+      .iter()
+      .find(|r| r.kind == RelationshipKind::MemberOf && r.source == node.id)
+      .map(|r| r.target.as_str());
+    if parent != namespace {
+      continue;
+    }
+
+    if node.kind == "namespace" {
+      out.push_str(&format!(
+        "{}subgraph {} {{\n",
+        padding,
+        quote(&format!("cluster_{}", node.id))
+      ));
+      out.push_str(&format!(
+        "{}  label={};\n",
+        padding,
+        quote(&format!("namespace {}", node.name))
+      ));
+      print_cluster(graph, Some(&node.id), indent + 1, out);
+      out.push_str(&format!("{}}}\n", padding));
+    } else {
+      let shape = match node.kind {
+        "class" | "interface" | "enum" => "box",
+        _ => "ellipse",
+      };
+      out.push_str(&format!(
+        "{}{} [label={}, shape={}];\n",
+        padding,
+        quote(&node.id),
+        quote(&format!("{} {}", node.kind, node.name)),
+        shape
+      ));
+    }
+  }
+}
+
+/// Graphviz DOT graph of the type references between the documented symbols,
+/// with namespaces drawn as clusters. References from members are drawn from
+/// the symbol owning them, and references to undocumented symbols are left
+/// out.
+pub fn print_dot(graph: &SymbolGraph) -> String {
+  let mut out = String::new();
+  out.push_str("digraph {\n");
+  out.push_str("  rankdir=LR;\n");
+  print_cluster(graph, None, 1, &mut out);
+
+  let mut edges: Vec<(&str, &str, &RelationshipKind)> = vec![];
+  for relationship in &graph.relationships {
+    if relationship.external || relationship.kind == RelationshipKind::MemberOf
+    {
+      continue;
+    }
+    let source = owner(graph, &relationship.source);
+    let target = owner(graph, &relationship.target);
+    let edge = (source, target, &relationship.kind);
+    if source != target && !edges.contains(&edge) {
+      edges.push(edge);
+    }
+  }
+  for (source, target, kind) in edges {
+    let style = match kind {
+      RelationshipKind::Extends => "arrowhead=empty",
+      RelationshipKind::Implements => "arrowhead=empty, style=dashed",
+      _ => "style=dashed",
+    };
+    out.push_str(&format!(
+      "  {} -> {} [{}];\n",
+      quote(source),
+      quote(target),
+      style
+    ));
+  }
+
+  out.push_str("}\n");
+  out
+}
@@ -0,0 +1,340 @@
+use crate::doc::js_doc::JsDoc;
+use crate::doc::printer::object_members;
+use crate::doc::printer::render_params;
+use crate::doc::printer::render_ts_type;
+use crate::doc::printer::render_type_params;
+use crate::doc::ts_type::TsTypeDefKind;
+use crate::doc::Augmentation;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+use crate::doc::FunctionDef;
+use crate::doc::ParamDef;
+
+/// Declaration file describing the public API in `doc_nodes`, with
+/// re-exported symbols declared in place.
+pub fn print_dts(doc_nodes: &[DocNode]) -> String {
+  let mut out = String::new();
+  print_declarations(&mut out, doc_nodes, 0);
+  out
+}
+
+fn push_line(out: &mut String, indent: usize, line: &str) {
+  if !line.is_empty() {
+    for _ in 0..indent {
+      out.push_str("  ");
+    }
+  }
+  out.push_str(line);
+  out.push('\n');
+}
+
+fn print_declarations(out: &mut String, doc_nodes: &[DocNode], indent: usize) {
+  // Inside of a namespace declarations are already ambient.
+  let declare = if indent == 0 { "declare " } else { "" };
+
+  for (i, node) in doc_nodes.iter().enumerate() {
+    if i > 0 {
+      out.push('\n');
+    }
+    print_js_doc(out, &node.js_doc, indent);
+
+    // `default` isn't a valid name, so default exports are declared like tsc
+    // does, under a local name which is exported as the default.
+    let is_default = node.is_default
+      && matches!(
+        node.kind,
+        DocNodeKind::Function | DocNodeKind::Variable | DocNodeKind::Class
+      );
+    let (export, name) = if is_default {
+      ("", "_default")
+    } else {
+      ("export ", node.name.as_str())
+    };
+
+    match node.kind {
+      DocNodeKind::Function => {
+        let function_def = node.function_def.as_ref().unwrap();
+        let line = function_line(export, name, function_def, declare);
+        push_line(out, indent, &line);
+        for overload in &function_def.overloads {
+          print_js_doc(out, &overload.js_doc, indent);
+          let line =
+            function_line(export, name, &overload.function_def, declare);
+          push_line(out, indent, &line);
+        }
+      }
+      DocNodeKind::Variable => {
+        use swc_ecma_ast::VarDeclKind;
+
+        let variable_def = node.variable_def.as_ref().unwrap();
+        let kind = match variable_def.kind {
+          VarDeclKind::Const => "const",
+          VarDeclKind::Let => "let",
+          VarDeclKind::Var => "var",
+        };
+        // Literal initializers are allowed in ambient const declarations.
+        let value = variable_def
+          .init
+          .as_ref()
+          .filter(|_| variable_def.is_literal && kind == "const");
+        let ts_type = match (&variable_def.ts_type, value) {
+          (Some(ts_type), Some(value))
+            if ts_type.kind == Some(TsTypeDefKind::Literal) =>
+          {
+            format!(" = {}", value)
+          }
+          (Some(ts_type), _) => format!(": {}", render_ts_type(ts_type)),
+          (None, Some(value)) => format!(" = {}", value),
+          (None, None) => ": unknown".to_string(),
+        };
+        // Object literals are declared by their members.
+        let ts_type = match object_members(node) {
+          Some(members) => format!(": {}", members),
+          None => ts_type,
+        };
+        let line =
+          format!("{}{}{} {}{};", export, declare, kind, name, ts_type);
+        push_line(out, indent, &line);
+      }
+      DocNodeKind::Class => {
+        print_class(out, node, export, name, declare, indent)
+      }
+      DocNodeKind::Enum => {
+        let enum_def = node.enum_def.as_ref().unwrap();
+        let modifier = if enum_def.is_const { "const " } else { "" };
+        push_line(
+          out,
+          indent,
+          &format!("export {}{}enum {} {{", declare, modifier, node.name),
+        );
+        for member in &enum_def.members {
+          let line = match (&member.value, &member.init) {
+            (Some(value), _) => format!("{} = {},", member.name, value),
+            (None, Some(init)) => format!("{} = {},", member.name, init),
+            (None, None) => format!("{},", member.name),
+          };
+          push_line(out, indent + 1, &line);
+        }
+        push_line(out, indent, "}");
+      }
+      DocNodeKind::Interface => {
+        // Interfaces are declarations already, so their source is valid.
+        let snippet = node.snippet.trim_start_matches("export ");
+        let mut lines = snippet.lines();
+        if let Some(first) = lines.next() {
+          push_line(out, indent, &format!("export {}", first));
+        }
+        for line in lines {
+          push_line(out, 0, line);
+        }
+      }
+      DocNodeKind::TypeAlias => {
+        let type_alias_def = node.type_alias_def.as_ref().unwrap();
+        let line = format!(
+          "export type {}{} = {};",
+          node.name,
+          render_type_params(&type_alias_def.type_params),
+          render_ts_type(&type_alias_def.ts_type)
+        );
+        push_line(out, indent, &line);
+      }
+      DocNodeKind::Namespace => print_namespace(out, node, declare, indent),
+    }
+    if is_default {
+      push_line(out, indent, "export default _default;");
+    }
+    // Namespace merged into another declaration.
+    if node.kind != DocNodeKind::Namespace && node.namespace_def.is_some() {
+      print_namespace(out, node, declare, indent);
+    }
+  }
+}
+
+fn print_namespace(
+  out: &mut String,
+  node: &DocNode,
+  declare: &str,
+  indent: usize,
+) {
+  let namespace_def = node.namespace_def.as_ref().unwrap();
+  let line = match namespace_def.augmentation {
+    Some(Augmentation::Global) => "declare global {".to_string(),
+    Some(Augmentation::Module) => format!("declare module {:?} {{", node.name),
+    None => format!("export {}namespace {} {{", declare, node.name),
+  };
+  push_line(out, indent, &line);
+  print_declarations(out, &namespace_def.elements, indent + 1);
+  push_line(out, indent, "}");
+}
+
+fn print_js_doc(out: &mut String, js_doc: &Option<String>, indent: usize) {
+  let js_doc = match js_doc {
+    Some(js_doc) => JsDoc::parse(js_doc),
+    None => return,
+  };
+
+  let mut text = js_doc.description.to_string();
+  for tag in &js_doc.tags {
+    if !text.is_empty() {
+      text.push('\n');
+    }
+    text.push_str(&tag.text());
+  }
+
+  push_line(out, indent, "/**");
+  for line in text.lines() {
+    push_line(out, indent, format!(" * {}", line).trim_end());
+  }
+  push_line(out, indent, " */");
+}
+
+/// Declarations can't have default values, parameters with one are optional
+/// instead.
+fn render_declaration_params(params: &[ParamDef]) -> String {
+  let params: Vec<ParamDef> = params
+    .iter()
+    .map(|param| ParamDef {
+      optional: param.optional || param.default.is_some(),
+      default: None,
+      ..param.clone()
+    })
+    .collect();
+  render_params(&params)
+}
+
+/// Type parameters, parameters and return type of a function. `async` and
+/// `*` aren't allowed in declarations, the return type is enough. Functions
+/// without one are declared as returning nothing, which is what tsc infers
+/// for a body without `return` statements.
+fn signature(function_def: &FunctionDef) -> String {
+  let return_type = match &function_def.return_type {
+    Some(return_type) => format!(": {}", render_ts_type(return_type)),
+    None if function_def.is_generator => "".to_string(),
+    None if function_def.is_async => ": Promise<void>".to_string(),
+    None => ": void".to_string(),
+  };
+  format!(
+    "{}({}){}",
+    render_type_params(&function_def.type_params),
+    render_declaration_params(&function_def.params),
+    return_type
+  )
+}
+
+fn function_line(
+  export: &str,
+  name: &str,
+  function_def: &FunctionDef,
+  declare: &str,
+) -> String {
+  format!(
+    "{}{}function {}{};",
+    export,
+    declare,
+    name,
+    signature(function_def)
+  )
+}
+
+fn accessibility_modifier(
+  accessibility: Option<swc_ecma_ast::Accessibility>,
+) -> &'static str {
+  use swc_ecma_ast::Accessibility;
+
+  match accessibility {
+    Some(Accessibility::Private) => "private ",
+    Some(Accessibility::Protected) => "protected ",
+    _ => "",
+  }
+}
+
+fn print_class(
+  out: &mut String,
+  node: &DocNode,
+  export: &str,
+  name: &str,
+  declare: &str,
+  indent: usize,
+) {
+  let class_def = node.class_def.as_ref().unwrap();
+  let mut line = format!("{}{}", export, declare);
+  if class_def.is_abstract {
+    line.push_str("abstract ");
+  }
+  line.push_str(&format!(
+    "class {}{}",
+    name,
+    render_type_params(&class_def.type_params)
+  ));
+  if let Some(extends) = &class_def.extends {
+    line.push_str(&format!(" extends {}", extends));
+    if !class_def.super_type_params.is_empty() {
+      let type_params: Vec<String> = class_def
+        .super_type_params
+        .iter()
+        .map(render_ts_type)
+        .collect();
+      line.push_str(&format!("<{}>", type_params.join(", ")));
+    }
+  }
+  if !class_def.implements.is_empty() {
+    line.push_str(&format!(" implements {}", class_def.implements.join(", ")));
+  }
+  line.push_str(" {");
+  push_line(out, indent, &line);
+
+  // Properties declared by constructor parameters are printed along with
+  // the other properties.
+  for ctor in &class_def.constructors {
+    print_js_doc(out, &ctor.js_doc, indent + 1);
+    let line = format!(
+      "{}constructor({});",
+      accessibility_modifier(ctor.accessibility),
+      render_declaration_params(&ctor.params)
+    );
+    push_line(out, indent + 1, &line);
+  }
+
+  for prop in &class_def.properties {
+    print_js_doc(out, &prop.js_doc, indent + 1);
+    let mut line = accessibility_modifier(prop.accessibility).to_string();
+    if prop.is_static {
+      line.push_str("static ");
+    }
+    if prop.is_abstract {
+      line.push_str("abstract ");
+    }
+    if prop.readonly {
+      line.push_str("readonly ");
+    }
+    line.push_str(&prop.name);
+    if prop.optional {
+      line.push('?');
+    }
+    if let Some(ts_type) = &prop.ts_type {
+      line.push_str(&format!(": {}", render_ts_type(ts_type)));
+    }
+    line.push(';');
+    push_line(out, indent + 1, &line);
+  }
+
+  for method in &class_def.methods {
+    print_js_doc(out, &method.js_doc, indent + 1);
+    let mut line = accessibility_modifier(method.accessibility).to_string();
+    if method.is_static {
+      line.push_str("static ");
+    }
+    if method.is_abstract {
+      line.push_str("abstract ");
+    }
+    line.push_str(&method.name);
+    if method.optional {
+      line.push('?');
+    }
+    line.push_str(&signature(&method.function_def));
+    line.push(';');
+    push_line(out, indent + 1, &line);
+  }
+
+  push_line(out, indent, "}");
+}
@@ -0,0 +1,355 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+use crate::doc::import::default_import_name;
+use crate::doc::js_doc::JsDoc;
+use crate::doc::DocNode;
+use crate::doc::Location;
+
+#[derive(Debug)]
+pub struct Example {
+  pub symbol: String,
+  pub location: Location,
+  pub code: String,
+}
+
+/// Collect code of all `@example` tags, including ones on class members and
+/// namespace elements.
+pub fn collect_examples(doc_nodes: &[DocNode]) -> Vec<Example> {
+  let mut examples = vec![];
+  collect_examples_(doc_nodes, "", &mut examples);
+  examples
+}
+
+fn collect_examples_(
+  doc_nodes: &[DocNode],
+  namespace: &str,
+  examples: &mut Vec<Example>,
+) {
+  for node in doc_nodes {
+    let name = format!("{}{}", namespace, node.name);
+    push_examples(&node.js_doc, &name, &node.location, examples);
+
+    if let Some(class_def) = &node.class_def {
+      for ctor in &class_def.constructors {
+        let ctor_name = format!("{}.constructor", name);
+        push_examples(&ctor.js_doc, &ctor_name, &node.location, examples);
+      }
+      for prop in &class_def.properties {
+        let prop_name = format!("{}.{}", name, prop.name);
+        push_examples(&prop.js_doc, &prop_name, &node.location, examples);
+      }
+      for method in &class_def.methods {
+        let method_name = format!("{}.{}", name, method.name);
+        push_examples(&method.js_doc, &method_name, &node.location, examples);
+      }
+    }
+    if let Some(namespace_def) = &node.namespace_def {
+      let namespace = format!("{}.", name);
+      collect_examples_(&namespace_def.elements, &namespace, examples);
+    }
+  }
+}
+
+fn push_examples(
+  js_doc: &Option<String>,
+  symbol: &str,
+  location: &Location,
+  examples: &mut Vec<Example>,
+) {
+  let js_doc = match js_doc {
+    Some(js_doc) => JsDoc::parse(js_doc),
+    None => return,
+  };
+
+  for tag in js_doc.tags_named("example") {
+    for code in example_code_blocks(&tag.value) {
+      examples.push(Example {
+        symbol: symbol.to_string(),
+        location: location.clone(),
+        code,
+      });
+    }
+  }
+}
+
+/// Examples are usually wrapped in Markdown code fences; if there are none,
+/// the whole tag is treated as code.
+fn example_code_blocks(example: &str) -> Vec<String> {
+  let mut blocks = vec![];
+  let mut current: Option<String> = None;
+
+  for line in example.lines() {
+    if line.trim_start().starts_with("```") {
+      match current.take() {
+        Some(block) => blocks.push(block),
+        None => current = Some(String::new()),
+      }
+      continue;
+    }
+
+    if let Some(block) = current.as_mut() {
+      block.push_str(line);
+      block.push('\n');
+    }
+  }
+
+  if blocks.is_empty() && !example.contains("```") {
+    blocks.push(format!("{}\n", example));
+  }
+
+  blocks
+    .into_iter()
+    .filter(|block| !block.trim().is_empty())
+    .collect()
+}
+
+/// Split `code` into its import declarations and the rest of the code.
+fn split_imports(code: &str) -> (String, String) {
+  let mut imports = String::new();
+  let mut body = String::new();
+  let mut in_import = false;
+
+  for line in code.lines() {
+    let trimmed = line.trim();
+    if in_import || trimmed.starts_with("import ") {
+      imports.push_str(line);
+      imports.push('\n');
+      in_import = !(trimmed.ends_with(';')
+        || trimmed.contains(" from \"")
+        || trimmed.contains(" from '"));
+    } else {
+      body.push_str(line);
+      body.push('\n');
+    }
+  }
+
+  (imports, body)
+}
+
+/// Identifiers `code` refers to, leaving out property names like `close` in
+/// `server.close()`.
+fn referenced_names(code: &str) -> Vec<&str> {
+  let is_ident_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+  let mut names = vec![];
+  let mut start = None;
+  let mut after_dot = false;
+  for (i, c) in code
+    .char_indices()
+    .chain(std::iter::once((code.len(), ' ')))
+  {
+    if is_ident_char(c) {
+      if start.is_none() {
+        start = Some(i);
+      }
+      continue;
+    }
+    if let Some(start) = start.take() {
+      let name = &code[start..i];
+      if !after_dot && !names.contains(&name) {
+        names.push(name);
+      }
+    }
+    if !c.is_whitespace() {
+      after_dot = c == '.';
+    }
+  }
+  names
+}
+
+/// Name declared by a default exported class or function, eg. `Server` in
+/// `export default class Server {}`.
+fn default_export_name(snippet: &str) -> Option<&str> {
+  let mut words = snippet
+    .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+    .filter(|word| !word.is_empty())
+    .skip_while(|word| {
+      ["export", "default", "declare", "abstract", "async"].contains(word)
+    });
+  match words.next() {
+    Some("class") | Some("function") => {}
+    _ => return None,
+  }
+  words
+    .next()
+    .filter(|name| *name != "extends" && *name != "implements")
+}
+
+/// Import declarations for `example`; the documented symbols it refers to are
+/// imported from `specifier` unless the example brings its own imports.
+fn example_imports(
+  example: &Example,
+  specifier: &str,
+  doc_nodes: &[DocNode],
+) -> (String, String) {
+  let (imports, body) = split_imports(&example.code);
+  if !imports.is_empty() {
+    return (imports, body);
+  }
+
+  let referenced = referenced_names(&body);
+  let mut default_name = None;
+  let mut names: Vec<&str> = vec![];
+  let mut type_names: Vec<&str> = vec![];
+  for node in doc_nodes {
+    // Augmentations aren't imported by name, and declarations of declaration
+    // files can be documented without being exported.
+    let is_augmentation = matches!(
+      &node.namespace_def,
+      Some(namespace_def) if namespace_def.augmentation.is_some()
+    );
+    if is_augmentation
+      || !node.is_default && !node.snippet.starts_with("export")
+    {
+      continue;
+    }
+    if node.is_default {
+      let name = default_export_name(&node.snippet)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| default_import_name(specifier));
+      if referenced.contains(&name.as_str()) {
+        default_name = Some(name);
+      }
+      continue;
+    }
+    let name = node.name.as_str();
+    // Merged declarations, like a function and a namespace, share a name.
+    if !referenced.contains(&name) || names.contains(&name) {
+      continue;
+    }
+    if node.is_type_only {
+      if !type_names.contains(&name) {
+        type_names.push(name);
+      }
+    } else {
+      names.push(name);
+    }
+  }
+  type_names.retain(|name| !names.contains(name));
+
+  let specifier = serde_json::to_string(specifier).unwrap();
+  let mut imports = String::new();
+  let mut bindings = vec![];
+  if let Some(default_name) = default_name {
+    bindings.push(default_name);
+  }
+  if !names.is_empty() {
+    bindings.push(format!("{{ {} }}", names.join(", ")));
+  }
+  if !bindings.is_empty() {
+    imports.push_str(&format!(
+      "import {} from {};\n",
+      bindings.join(", "),
+      specifier
+    ));
+  }
+  // Types are imported on their own, as they don't exist at runtime.
+  if !type_names.is_empty() {
+    imports.push_str(&format!(
+      "import type {{ {} }} from {};\n",
+      type_names.join(", "),
+      specifier
+    ));
+  }
+  (imports, body)
+}
+
+/// Source of a standalone module running `example` at the top level.
+pub fn example_module_source(
+  example: &Example,
+  specifier: &str,
+  doc_nodes: &[DocNode],
+) -> String {
+  let (imports, body) = example_imports(example, specifier, doc_nodes);
+  format!("{}\n{}", imports, body)
+}
+
+/// Source of a module registering `example` as a single `Deno.test` case.
+pub fn example_test_source(
+  example: &Example,
+  specifier: &str,
+  doc_nodes: &[DocNode],
+) -> String {
+  let (imports, body) = example_imports(example, specifier, doc_nodes);
+  let body: Vec<String> =
+    body.lines().map(|line| format!("  {}", line)).collect();
+  format!(
+    "{}\nDeno.test({:?}, async () => {{\n{}\n}});\n",
+    imports,
+    format!("{} example", example.symbol),
+    body.join("\n").trim_end()
+  )
+}
+
+pub fn file_specifier(file_name: &str) -> String {
+  let path = Path::new(file_name);
+  let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+  format!("file://{}", path.to_string_lossy().replace('\\', "/"))
+}
+
+/// Write every module source to its own file in a fresh temporary directory.
+pub fn write_example_modules(
+  sources: &[String],
+) -> std::io::Result<(PathBuf, Vec<PathBuf>)> {
+  let dir = std::env::temp_dir()
+    .join(format!("deno_doc_examples_{}", std::process::id()));
+  std::fs::create_dir_all(&dir)?;
+
+  let mut paths = vec![];
+  for (i, source) in sources.iter().enumerate() {
+    let path = dir.join(format!("example_{}_test.ts", i));
+    std::fs::write(&path, source)?;
+    paths.push(path);
+  }
+
+  Ok((dir, paths))
+}
+
+/// Run `deno <subcommand>` on a single module.
+pub fn run_deno(subcommand: &str, path: &Path) -> std::io::Result<Output> {
+  let deno = std::env::var("DENO").unwrap_or_else(|_| "deno".to_string());
+  Command::new(deno)
+    .arg(subcommand)
+    .arg("--quiet")
+    .arg(path)
+    .env("NO_COLOR", "1")
+    .output()
+}
+
+#[derive(Debug)]
+pub struct ExampleTestResult {
+  pub example: Example,
+  pub passed: bool,
+  pub output: String,
+}
+
+/// Run every `@example` code block under `deno test`.
+pub fn test_examples(
+  file_name: &str,
+  doc_nodes: &[DocNode],
+) -> std::io::Result<Vec<ExampleTestResult>> {
+  let examples = collect_examples(doc_nodes);
+  let specifier = file_specifier(file_name);
+  let sources: Vec<String> = examples
+    .iter()
+    .map(|example| example_test_source(example, &specifier, doc_nodes))
+    .collect();
+  let (dir, paths) = write_example_modules(&sources)?;
+
+  let mut results = vec![];
+  for (example, path) in examples.into_iter().zip(paths.iter()) {
+    let output = run_deno("test", path)?;
+    let mut output_text = String::from_utf8_lossy(&output.stdout).to_string();
+    output_text.push_str(&String::from_utf8_lossy(&output.stderr));
+    results.push(ExampleTestResult {
+      example,
+      passed: output.status.success(),
+      output: output_text.trim_end().to_string(),
+    });
+  }
+
+  let _ = std::fs::remove_dir_all(dir);
+  Ok(results)
+}
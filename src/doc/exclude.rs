@@ -0,0 +1,77 @@
+use swc_ecma_ast::Accessibility;
+
+use crate::doc::DocNode;
+use crate::glob;
+
+/// Leave out symbols declared in files matching one of `patterns`.
+pub fn exclude_files(
+  doc_nodes: Vec<DocNode>,
+  patterns: &[&str],
+) -> Vec<DocNode> {
+  doc_nodes
+    .into_iter()
+    .filter(|node| {
+      !patterns
+        .iter()
+        .any(|pattern| glob::is_match(pattern, &node.location.filename))
+    })
+    .map(|mut node| {
+      if let Some(namespace_def) = node.namespace_def.as_mut() {
+        let elements = std::mem::take(&mut namespace_def.elements);
+        namespace_def.elements = exclude_files(elements, patterns);
+      }
+      node
+    })
+    .collect()
+}
+
+/// Leave out symbols, including namespace elements, whose name matches one of
+/// `patterns`.
+pub fn exclude_symbols(
+  doc_nodes: Vec<DocNode>,
+  patterns: &[&str],
+) -> Vec<DocNode> {
+  doc_nodes
+    .into_iter()
+    .filter(|node| {
+      !patterns
+        .iter()
+        .any(|pattern| glob::is_match(pattern, &node.name))
+    })
+    .map(|mut node| {
+      if let Some(namespace_def) = node.namespace_def.as_mut() {
+        let elements = std::mem::take(&mut namespace_def.elements);
+        namespace_def.elements = exclude_symbols(elements, patterns);
+      }
+      node
+    })
+    .collect()
+}
+
+fn is_public(accessibility: Option<Accessibility>) -> bool {
+  !matches!(
+    accessibility,
+    Some(Accessibility::Private) | Some(Accessibility::Protected)
+  )
+}
+
+/// Leave out `private` and `protected` members of classes, including classes
+/// in namespaces.
+pub fn exclude_non_public_members(doc_nodes: &mut [DocNode]) {
+  for node in doc_nodes {
+    if let Some(class_def) = node.class_def.as_mut() {
+      class_def
+        .constructors
+        .retain(|ctor| is_public(ctor.accessibility));
+      class_def
+        .properties
+        .retain(|prop| is_public(prop.accessibility));
+      class_def
+        .methods
+        .retain(|method| is_public(method.accessibility));
+    }
+    if let Some(namespace_def) = node.namespace_def.as_mut() {
+      exclude_non_public_members(&mut namespace_def.elements);
+    }
+  }
+}
@@ -0,0 +1,102 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::doc::compat::split_once;
+
+pub fn is_remote(specifier: &str) -> bool {
+  specifier.starts_with("https://") || specifier.starts_with("http://")
+}
+
+/// Directory remote modules are cached in, `$DENO_DOC_CACHE` or
+/// `~/.cache/deno_doc`.
+pub fn cache_dir() -> PathBuf {
+  if let Ok(dir) = std::env::var("DENO_DOC_CACHE") {
+    return PathBuf::from(dir);
+  }
+  let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+  Path::new(&home).join(".cache").join("deno_doc")
+}
+
+/// Path of the cached copy of `url`, eg. `https/deno.land/std/mod.ts` for
+/// `https://deno.land/std/mod.ts`.
+pub fn cache_path(cache_dir: &Path, url: &str) -> PathBuf {
+  let (scheme, rest) = split_once(url, "://").unwrap_or(("", url));
+  let mut path = cache_dir.join(scheme);
+  for segment in rest.split('/').filter(|segment| !segment.is_empty()) {
+    let segment: String = segment
+      .chars()
+      .map(|c| match c {
+        '?' | '#' | ':' | '*' | '"' | '<' | '>' | '|' | '\\' => '_',
+        c => c,
+      })
+      .collect();
+    path.push(segment);
+  }
+  path
+}
+
+/// Source of the module at `url`, downloaded with `curl` unless it's in
+/// `cache_dir` already.
+pub fn fetch(url: &str, cache_dir: &Path) -> Result<String, String> {
+  let path = cache_path(cache_dir, url);
+  if let Ok(source_code) = std::fs::read_to_string(&path) {
+    return Ok(source_code);
+  }
+
+  let output = Command::new("curl")
+    .arg("--silent")
+    .arg("--show-error")
+    .arg("--fail")
+    .arg("--location")
+    .arg(url)
+    .output()
+    .map_err(|err| format!("Failed to run curl: {}", err))?;
+  if !output.status.success() {
+    return Err(format!(
+      "Failed to fetch {}: {}",
+      url,
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+  let source_code = String::from_utf8_lossy(&output.stdout).to_string();
+
+  let write = std::fs::create_dir_all(path.parent().unwrap())
+    .and_then(|_| std::fs::write(&path, &source_code));
+  if let Err(err) = write {
+    eprintln!("Failed to cache {}: {}", url, err);
+  }
+  Ok(source_code)
+}
+
+/// Source of the module `file_name`, which is a path or a remote URL.
+pub fn read_module(file_name: &str) -> Result<String, String> {
+  if is_remote(file_name) {
+    return fetch(file_name, &cache_dir());
+  }
+  std::fs::read_to_string(file_name)
+    .map_err(|err| format!("Failed to read {}: {}", file_name, err))
+}
+
+/// The relative `specifier` resolved against the URL `base`, eg.
+/// `../fmt/colors.ts` from `https://deno.land/std/log/mod.ts`.
+pub fn join_url(base: &str, specifier: &str) -> String {
+  let (scheme, rest) = split_once(base, "://").unwrap_or(("", base));
+  let mut segments: Vec<&str> = rest.split('/').collect();
+  // Drop the file name, but never the host.
+  if segments.len() > 1 {
+    segments.pop();
+  }
+  for segment in specifier.split('/') {
+    match segment {
+      "." => {}
+      ".." => {
+        if segments.len() > 1 {
+          segments.pop();
+        }
+      }
+      segment => segments.push(segment),
+    }
+  }
+  format!("{}://{}", scheme, segments.join("/"))
+}
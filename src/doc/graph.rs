@@ -0,0 +1,396 @@
+use serde::Serialize;
+
+use crate::doc::ts_type::TsTypeDef;
+use crate::doc::ts_type::TsTypeParamDef;
+use crate::doc::DocNode;
+use crate::doc::Location;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+  /// Qualified name, eg. `Ns.Class.member`.
+  pub id: String,
+  pub name: String,
+  /// Kind of the symbol, a `DocNodeKind` or a kind of member.
+  pub kind: &'static str,
+  pub location: Location,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RelationshipKind {
+  /// Source is a member of a class, interface or enum, or an element of a
+  /// namespace.
+  MemberOf,
+  /// Source is a class or interface extending target.
+  Extends,
+  /// Source is a class implementing target.
+  Implements,
+  /// Source refers to target in its signature.
+  References,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Relationship {
+  pub kind: RelationshipKind,
+  pub source: String,
+  /// Id of a node of the graph, or the name of a symbol which isn't
+  /// documented.
+  pub target: String,
+  /// Whether target is a symbol which isn't documented, eg. a global type.
+  pub external: bool,
+}
+
+/// Symbols of a module along with the relationships between them.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolGraph {
+  pub nodes: Vec<GraphNode>,
+  pub relationships: Vec<Relationship>,
+}
+
+pub fn kind_name(node: &DocNode) -> &'static str {
+  use crate::doc::DocNodeKind;
+
+  match node.kind {
+    DocNodeKind::Function => "function",
+    DocNodeKind::Variable => "variable",
+    DocNodeKind::Class => "class",
+    DocNodeKind::Enum => "enum",
+    DocNodeKind::Interface => "interface",
+    DocNodeKind::TypeAlias => "typeAlias",
+    DocNodeKind::Namespace => "namespace",
+  }
+}
+
+/// Types mentioned in the signature of `node`, not including its members.
+pub fn signature_types(node: &DocNode) -> Vec<&TsTypeDef> {
+  let mut types = vec![];
+  let mut type_params: &[TsTypeParamDef] = &[];
+  if let Some(function_def) = &node.function_def {
+    types.extend(
+      function_def
+        .params
+        .iter()
+        .filter_map(|p| p.ts_type.as_ref()),
+    );
+    types.extend(function_def.return_type.as_ref());
+    for overload in &function_def.overloads {
+      let overload = &overload.function_def;
+      types.extend(overload.params.iter().filter_map(|p| p.ts_type.as_ref()));
+      types.extend(overload.return_type.as_ref());
+    }
+    type_params = &function_def.type_params;
+  }
+  if let Some(variable_def) = &node.variable_def {
+    types.extend(variable_def.ts_type.as_ref());
+  }
+  if let Some(class_def) = &node.class_def {
+    types.extend(class_def.super_type_params.iter());
+    type_params = &class_def.type_params;
+  }
+  if let Some(interface_def) = &node.interface_def {
+    type_params = &interface_def.type_params;
+  }
+  if let Some(type_alias_def) = &node.type_alias_def {
+    types.push(&type_alias_def.ts_type);
+    type_params = &type_alias_def.type_params;
+  }
+  for type_param in type_params {
+    types.extend(type_param.constraint.as_ref());
+    types.extend(type_param.default.as_ref());
+  }
+  types
+}
+
+/// Find the symbol `name` refers to from within `namespace`, looking in the
+/// namespace and its parents before the top level.
+fn resolve<'a>(
+  ids: &'a [String],
+  namespace: &str,
+  name: &str,
+) -> Option<&'a str> {
+  let mut scope = namespace;
+  loop {
+    let candidate = format!("{}{}", scope, name);
+    if let Some(id) = ids.iter().find(|id| **id == candidate) {
+      return Some(id);
+    }
+    if scope.is_empty() {
+      return None;
+    }
+    let trimmed = scope.trim_end_matches('.');
+    scope = match trimmed.rfind('.') {
+      Some(index) => &scope[..=index],
+      None => "",
+    };
+  }
+}
+
+struct Builder<'a> {
+  graph: SymbolGraph,
+  /// Types referenced by each node, resolved once all nodes are known.
+  references: Vec<(String, String, Vec<&'a TsTypeDef>)>,
+  /// Classes and interfaces extended or implemented by each node.
+  heritage: Vec<(RelationshipKind, String, String, &'a str)>,
+}
+
+impl<'a> Builder<'a> {
+  fn push_node(
+    &mut self,
+    id: &str,
+    name: &str,
+    kind: &'static str,
+    location: &Location,
+  ) {
+    self.graph.nodes.push(GraphNode {
+      id: id.to_string(),
+      name: name.to_string(),
+      kind,
+      location: location.clone(),
+    });
+  }
+
+  fn push_member(
+    &mut self,
+    parent: &str,
+    namespace: &str,
+    name: &str,
+    kind: &'static str,
+    location: &Location,
+    types: Vec<&'a TsTypeDef>,
+  ) {
+    let id = format!("{}.{}", parent, name);
+    self.push_node(&id, name, kind, location);
+    self.graph.relationships.push(Relationship {
+      kind: RelationshipKind::MemberOf,
+      source: id.to_string(),
+      target: parent.to_string(),
+      external: false,
+    });
+    self.references.push((id, namespace.to_string(), types));
+  }
+
+  /// `name` can have type arguments, which are ignored.
+  fn push_heritage(
+    &mut self,
+    kind: RelationshipKind,
+    id: &str,
+    namespace: &str,
+    name: &'a str,
+  ) {
+    let name = name.split('<').next().unwrap().trim();
+    self
+      .heritage
+      .push((kind, id.to_string(), namespace.to_string(), name));
+  }
+
+  fn add_nodes(&mut self, doc_nodes: &'a [DocNode], namespace: &str) {
+    for node in doc_nodes {
+      let id = format!("{}{}", namespace, node.name);
+      self.push_node(&id, &node.name, kind_name(node), &node.location);
+      if !namespace.is_empty() {
+        self.graph.relationships.push(Relationship {
+          kind: RelationshipKind::MemberOf,
+          source: id.to_string(),
+          target: namespace.trim_end_matches('.').to_string(),
+          external: false,
+        });
+      }
+      self.references.push((
+        id.to_string(),
+        namespace.to_string(),
+        signature_types(node),
+      ));
+
+      if let Some(class_def) = &node.class_def {
+        if let Some(extends) = &class_def.extends {
+          self.push_heritage(
+            RelationshipKind::Extends,
+            &id,
+            namespace,
+            extends,
+          );
+        }
+        for implements in &class_def.implements {
+          self.push_heritage(
+            RelationshipKind::Implements,
+            &id,
+            namespace,
+            implements,
+          );
+        }
+        for ctor in &class_def.constructors {
+          self.push_member(
+            &id,
+            namespace,
+            &ctor.name,
+            "constructor",
+            &node.location,
+            vec![],
+          );
+        }
+        for prop in &class_def.properties {
+          let types = prop.ts_type.iter().collect();
+          self.push_member(
+            &id,
+            namespace,
+            &prop.name,
+            "property",
+            &node.location,
+            types,
+          );
+        }
+        for method in &class_def.methods {
+          self.push_member(
+            &id,
+            namespace,
+            &method.name,
+            "method",
+            &node.location,
+            vec![],
+          );
+        }
+      }
+      if let Some(interface_def) = &node.interface_def {
+        for extends in &interface_def.extends {
+          self.push_heritage(
+            RelationshipKind::Extends,
+            &id,
+            namespace,
+            extends,
+          );
+        }
+        for prop in &interface_def.properties {
+          let types = prop.ts_type.iter().collect();
+          self.push_member(
+            &id,
+            namespace,
+            &prop.name,
+            "property",
+            &node.location,
+            types,
+          );
+        }
+        // Call, construct and index signatures have no name, so their types
+        // are referenced by the interface itself.
+        let signatures = interface_def
+          .call_signatures
+          .iter()
+          .chain(&interface_def.construct_signatures);
+        for signature in signatures {
+          let types = signature
+            .params
+            .iter()
+            .filter_map(|param| param.ts_type.as_ref())
+            .chain(signature.ts_type.as_ref())
+            .collect();
+          self
+            .references
+            .push((id.to_string(), namespace.to_string(), types));
+        }
+        for index in &interface_def.index_signatures {
+          let types = index
+            .params
+            .iter()
+            .filter_map(|param| param.ts_type.as_ref())
+            .chain(index.ts_type.as_ref())
+            .collect();
+          self
+            .references
+            .push((id.to_string(), namespace.to_string(), types));
+        }
+        for method in &interface_def.methods {
+          let types = method
+            .params
+            .iter()
+            .filter_map(|param| param.ts_type.as_ref())
+            .chain(method.return_type.as_ref())
+            .collect();
+          self.push_member(
+            &id,
+            namespace,
+            &method.name,
+            "method",
+            &node.location,
+            types,
+          );
+        }
+      }
+      if let Some(enum_def) = &node.enum_def {
+        for member in &enum_def.members {
+          self.push_member(
+            &id,
+            namespace,
+            &member.name,
+            "enumMember",
+            &node.location,
+            vec![],
+          );
+        }
+      }
+      if let Some(namespace_def) = &node.namespace_def {
+        self.add_nodes(&namespace_def.elements, &format!("{}.", id));
+      }
+    }
+  }
+
+  fn resolve_edges(mut self) -> SymbolGraph {
+    let ids: Vec<String> = self
+      .graph
+      .nodes
+      .iter()
+      .map(|node| node.id.to_string())
+      .collect();
+    let mut relationships = vec![];
+
+    let mut push = |kind, source: &str, namespace: &str, name: &str| {
+      let (target, external) = match resolve(&ids, namespace, name) {
+        Some(id) => (id.to_string(), false),
+        None => (name.to_string(), true),
+      };
+      relationships.push(Relationship {
+        kind,
+        source: source.to_string(),
+        target,
+        external,
+      });
+    };
+
+    for (kind, source, namespace, name) in self.heritage.drain(..) {
+      push(kind, &source, &namespace, name);
+    }
+    for (source, namespace, types) in &self.references {
+      let mut names: Vec<&str> = vec![];
+      for ts_type in types {
+        for name in ts_type.type_ref_names() {
+          if !names.contains(&name) {
+            names.push(name);
+          }
+        }
+      }
+      for name in names {
+        push(RelationshipKind::References, source, namespace, name);
+      }
+    }
+
+    self.graph.relationships.extend(relationships);
+    self.graph
+  }
+}
+
+impl SymbolGraph {
+  pub fn new(doc_nodes: &[DocNode]) -> SymbolGraph {
+    let mut builder = Builder {
+      graph: SymbolGraph {
+        nodes: vec![],
+        relationships: vec![],
+      },
+      references: vec![],
+      heritage: vec![],
+    };
+    builder.add_nodes(doc_nodes, "");
+    builder.resolve_edges()
+  }
+}
@@ -0,0 +1,73 @@
+use crate::doc::DocNode;
+
+pub fn import_statement(name: &str, specifier: &str) -> String {
+  format!(
+    "import {{ {} }} from {};",
+    name,
+    serde_json::to_string(specifier).unwrap()
+  )
+}
+
+/// Name of the module's file as an identifier, eg. `server` for
+/// `./server.ts`.
+pub fn default_import_name(specifier: &str) -> String {
+  let file_name = specifier.rsplit('/').next().unwrap_or(specifier);
+  let stem = file_name.split('.').next().unwrap_or(file_name);
+  let mut name: String = stem
+    .chars()
+    .map(|c| if c.is_alphanumeric() { c } else { '_' })
+    .collect();
+  if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+    name.insert(0, '_');
+  }
+  name
+}
+
+/// The default export is imported under the name of the module's file, eg.
+/// `import server from "./server.ts";`.
+pub fn default_import_statement(specifier: &str) -> String {
+  format!(
+    "import {} from {};",
+    default_import_name(specifier),
+    serde_json::to_string(specifier).unwrap()
+  )
+}
+
+/// Specifier importing the module at `file_name`, eg. `./mod.ts` for
+/// `mod.ts` or `file:///src/mod.ts` for `/src/mod.ts`. URLs and paths
+/// starting with `./` or `../` are kept as they are.
+pub fn module_specifier(file_name: &str) -> String {
+  let file_name = file_name.replace('\\', "/");
+  if file_name.contains("://")
+    || file_name.starts_with("./")
+    || file_name.starts_with("../")
+  {
+    file_name
+  } else if file_name.starts_with('/') {
+    format!("file://{}", file_name)
+  } else {
+    format!("./{}", file_name)
+  }
+}
+
+/// Set the statement importing each of `doc_nodes` from the module
+/// `specifier`, which may be a file name. Namespace elements are reached
+/// through their namespace, so they are left without one.
+pub fn set_import_statements(doc_nodes: &mut [DocNode], specifier: &str) {
+  let specifier = &module_specifier(specifier);
+  for node in doc_nodes {
+    // Augmentations aren't imported by name.
+    let is_augmentation = matches!(
+      &node.namespace_def,
+      Some(namespace_def) if namespace_def.augmentation.is_some()
+    );
+    if is_augmentation {
+      continue;
+    }
+    node.import_statement = Some(if node.is_default {
+      default_import_statement(specifier)
+    } else {
+      import_statement(&node.name, specifier)
+    });
+  }
+}
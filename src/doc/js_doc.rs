@@ -0,0 +1,171 @@
+use crate::doc::compat::split_once;
+use crate::doc::compat::strip_prefix;
+
+#[derive(Debug, PartialEq)]
+pub struct JsDocTag {
+  pub name: String,
+  pub value: String,
+  /// Whether the value starts on the line after the tag, like the code block
+  /// of an `@example`.
+  pub value_on_next_line: bool,
+}
+
+impl JsDocTag {
+  /// The tag as written in a JSDoc comment, keeping the value on the next
+  /// line when it started there.
+  pub fn text(&self) -> String {
+    if self.value.is_empty() {
+      format!("@{}", self.name)
+    } else if self.value_on_next_line {
+      format!("@{}\n{}", self.name, self.value)
+    } else {
+      format!("@{} {}", self.name, self.value)
+    }
+  }
+
+  /// Split `{type} rest` into the type and the rest of the value.
+  fn split_type(&self) -> Option<(Option<&str>, &str)> {
+    let value = self.value.trim_start();
+    if !value.starts_with('{') {
+      return Some((None, value));
+    }
+
+    let mut depth = 0;
+    let end = value.char_indices().find(|(_, c)| {
+      match c {
+        '{' => depth += 1,
+        '}' => depth -= 1,
+        _ => {}
+      };
+      depth == 0
+    })?;
+    Some((
+      Some(value[1..end.0].trim()),
+      value[end.0 + 1..].trim_start(),
+    ))
+  }
+
+  /// First word after the type of a `@param` tag, eg. `[name=default]`.
+  pub fn param_token(&self) -> Option<&str> {
+    let (_, value) = self.split_type()?;
+    value.split_whitespace().next()
+  }
+
+  /// Name documented by a `@param` tag, which can be written as
+  /// `{type} name`, `[name=default]` or `name.property`.
+  pub fn param_name(&self) -> Option<&str> {
+    let name = self.param_path()?;
+    let name = name.split('.').next()?;
+
+    if name.is_empty() {
+      None
+    } else {
+      Some(name)
+    }
+  }
+
+  /// Like `param_name`, but keeps the property path, eg. `options.port`.
+  pub fn param_path(&self) -> Option<&str> {
+    let name = self.param_token()?;
+    let name = name.trim_start_matches('[').trim_end_matches(']');
+    name.split('=').next()
+  }
+
+  /// Type of a `@param {type} name` tag.
+  pub fn param_type(&self) -> Option<&str> {
+    self.split_type()?.0
+  }
+
+  /// Default value of a `@param [name=default]` tag.
+  pub fn param_default(&self) -> Option<&str> {
+    let token = self.param_token()?;
+    if !token.starts_with('[') {
+      return None;
+    }
+    let token = token.trim_start_matches('[').trim_end_matches(']');
+    split_once(token, "=").map(|(_, default)| default)
+  }
+
+  /// Text following the name of a `@param` tag, without a leading `-`.
+  pub fn param_description(&self) -> &str {
+    let value = match self.split_type() {
+      Some((_, value)) => value,
+      None => return "",
+    };
+    let description = match value.find(char::is_whitespace) {
+      Some(index) => value[index..].trim(),
+      None => "",
+    };
+    description.trim_start_matches("- ")
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct JsDoc {
+  pub description: String,
+  pub tags: Vec<JsDocTag>,
+}
+
+impl JsDoc {
+  /// Parse "raw" JSDoc, as returned by `DocParser::js_doc_for_span`.
+  pub fn parse(js_doc: &str) -> Self {
+    let text = js_doc.trim();
+    let text = text.trim_start_matches("/**").trim_end_matches("*/");
+
+    let mut description = String::new();
+    let mut tags: Vec<JsDocTag> = vec![];
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+      let line = line.trim_start();
+      let line = strip_prefix(line, "*").unwrap_or(line);
+      let line = strip_prefix(line, " ").unwrap_or(line).trim_end();
+
+      if line.trim_start().starts_with("```") {
+        in_code_block = !in_code_block;
+      }
+
+      if !in_code_block && line.starts_with('@') {
+        let mut parts = line[1..].splitn(2, char::is_whitespace);
+        let name = parts.next().unwrap_or("").to_string();
+        let value = parts.next().unwrap_or("").trim().to_string();
+        tags.push(JsDocTag {
+          name,
+          value,
+          value_on_next_line: false,
+        });
+        continue;
+      }
+
+      let text = match tags.last_mut() {
+        Some(tag) => {
+          if tag.value.is_empty() && !line.is_empty() {
+            tag.value_on_next_line = true;
+          }
+          &mut tag.value
+        }
+        None => &mut description,
+      };
+      if !text.is_empty() {
+        text.push('\n');
+      }
+      text.push_str(line);
+    }
+
+    for tag in tags.iter_mut() {
+      tag.value = tag.value.trim_end().to_string();
+    }
+
+    JsDoc {
+      description: description.trim().to_string(),
+      tags,
+    }
+  }
+
+  pub fn tags_named<'a>(
+    &'a self,
+    name: &'a str,
+  ) -> impl Iterator<Item = &'a JsDocTag> + 'a {
+    self.tags.iter().filter(move |tag| tag.name == name)
+  }
+}
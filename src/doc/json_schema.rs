@@ -0,0 +1,334 @@
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::doc::js_doc::JsDoc;
+use crate::doc::ts_type::LiteralDefKind;
+use crate::doc::ts_type::TsTypeDef;
+use crate::doc::ts_type::TsTypeDefKind;
+use crate::doc::ts_type::TsTypeLiteralDef;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+use crate::doc::ParamDef;
+
+/// JSON Schema with a definition for each interface and type alias in
+/// `doc_nodes`. Types which can't be expressed in JSON Schema accept any
+/// value.
+pub fn json_schema(doc_nodes: &[DocNode]) -> Value {
+  let mut names = vec![];
+  collect_names(doc_nodes, "", &mut names);
+
+  let mut definitions = Map::new();
+  collect_definitions(doc_nodes, "", &names, &mut definitions);
+
+  json!({
+    "$schema": "http://json-schema.org/draft-07/schema#",
+    "definitions": definitions,
+  })
+}
+
+fn is_type(node: &DocNode) -> bool {
+  node.kind == DocNodeKind::Interface || node.kind == DocNodeKind::TypeAlias
+}
+
+fn collect_names(
+  doc_nodes: &[DocNode],
+  namespace: &str,
+  names: &mut Vec<String>,
+) {
+  for node in doc_nodes {
+    let name = format!("{}{}", namespace, node.name);
+    if is_type(node) {
+      names.push(name.to_string());
+    }
+    if let Some(namespace_def) = &node.namespace_def {
+      collect_names(&namespace_def.elements, &format!("{}.", name), names);
+    }
+  }
+}
+
+fn collect_definitions(
+  doc_nodes: &[DocNode],
+  namespace: &str,
+  names: &[String],
+  definitions: &mut Map<String, Value>,
+) {
+  for node in doc_nodes {
+    let name = format!("{}{}", namespace, node.name);
+    if is_type(node) {
+      let mut schema = match node.kind {
+        DocNodeKind::Interface => interface_schema(node, names),
+        _ => {
+          let type_alias_def = node.type_alias_def.as_ref().unwrap();
+          ts_type_schema(&type_alias_def.ts_type, names)
+        }
+      };
+      add_description(&mut schema, &node.js_doc);
+      definitions.insert(name.to_string(), schema);
+    }
+    if let Some(namespace_def) = &node.namespace_def {
+      let namespace = format!("{}.", name);
+      collect_definitions(
+        &namespace_def.elements,
+        &namespace,
+        names,
+        definitions,
+      );
+    }
+  }
+}
+
+fn add_description(schema: &mut Value, js_doc: &Option<String>) {
+  let description = match js_doc {
+    Some(js_doc) => JsDoc::parse(js_doc).description,
+    None => return,
+  };
+  if let (Value::Object(schema), false) = (schema, description.is_empty()) {
+    schema.insert("description".to_string(), Value::String(description));
+  }
+}
+
+fn interface_schema(node: &DocNode, names: &[String]) -> Value {
+  let interface_def = node.interface_def.as_ref().unwrap();
+  let mut properties = Map::new();
+  let mut required = vec![];
+
+  for prop in &interface_def.properties {
+    let mut schema = match &prop.ts_type {
+      Some(ts_type) => ts_type_schema(ts_type, names),
+      None => json!({}),
+    };
+    add_description(&mut schema, &prop.js_doc);
+    if prop.readonly {
+      schema["readOnly"] = Value::Bool(true);
+    }
+    properties.insert(prop.name.to_string(), schema);
+    if !prop.optional {
+      required.push(Value::String(prop.name.to_string()));
+    }
+  }
+
+  let mut schema = json!({
+    "type": "object",
+    "properties": properties,
+  });
+  if !required.is_empty() {
+    schema["required"] = Value::Array(required);
+  }
+  let index_signature =
+    interface_def.index_signatures.iter().find_map(|index| {
+      index_signature_schema(&index.params, &index.ts_type, names)
+    });
+  if let Some(additional_properties) = index_signature {
+    schema["additionalProperties"] = additional_properties;
+  }
+
+  // Members of known base interfaces are checked through their definitions,
+  // other bases accept any value.
+  let mut all_of: Vec<Value> = interface_def
+    .extends
+    .iter()
+    .map(|extends| extends.split('<').next().unwrap().trim())
+    .filter(|name| names.iter().any(|n| n == name))
+    .map(|name| json!({ "$ref": format!("#/definitions/{}", name) }))
+    .collect();
+  if all_of.is_empty() {
+    return schema;
+  }
+  all_of.push(schema);
+  json!({ "allOf": all_of })
+}
+
+fn type_literal_schema(
+  type_literal: &TsTypeLiteralDef,
+  names: &[String],
+) -> Value {
+  let mut properties = Map::new();
+  let mut required = vec![];
+
+  for prop in &type_literal.properties {
+    let mut schema = match &prop.ts_type {
+      Some(ts_type) => ts_type_schema(ts_type, names),
+      None => json!({}),
+    };
+    if prop.readonly {
+      schema["readOnly"] = Value::Bool(true);
+    }
+    properties.insert(prop.name.to_string(), schema);
+    if !prop.optional {
+      required.push(Value::String(prop.name.to_string()));
+    }
+  }
+
+  let mut schema = json!({
+    "type": "object",
+    "properties": properties,
+  });
+  if !required.is_empty() {
+    schema["required"] = Value::Array(required);
+  }
+  let index_signature =
+    type_literal.index_signatures.iter().find_map(|index| {
+      index_signature_schema(&index.params, &index.ts_type, names)
+    });
+  if let Some(additional_properties) = index_signature {
+    schema["additionalProperties"] = additional_properties;
+  }
+  schema
+}
+
+/// Schema of the values of a `[key: string]: T` index signature; keys of
+/// other types don't map to JSON object keys.
+fn index_signature_schema(
+  params: &[ParamDef],
+  ts_type: &Option<TsTypeDef>,
+  names: &[String],
+) -> Option<Value> {
+  let key_type = match params {
+    [param] => param.ts_type.as_ref()?,
+    _ => return None,
+  };
+  if key_type.keyword.as_deref() != Some("string") {
+    return None;
+  }
+  Some(match ts_type {
+    Some(ts_type) => ts_type_schema(ts_type, names),
+    None => json!({}),
+  })
+}
+
+fn literal_value(ts_type: &TsTypeDef) -> Option<Value> {
+  if ts_type.kind != Some(TsTypeDefKind::Literal) {
+    return None;
+  }
+
+  let literal = ts_type.literal.as_ref().unwrap();
+  match literal.kind {
+    LiteralDefKind::Number => {
+      Some(json!(literal.number.unwrap())).filter(|number| !number.is_null())
+    }
+    LiteralDefKind::String => Some(json!(literal.string.as_ref().unwrap())),
+    LiteralDefKind::Boolean => Some(json!(literal.boolean.unwrap())),
+    // JSON has no bigints.
+    LiteralDefKind::BigInt => None,
+  }
+}
+
+fn ts_type_schema(ts_type: &TsTypeDef, names: &[String]) -> Value {
+  let kind = match &ts_type.kind {
+    Some(kind) => kind,
+    None => return json!({}),
+  };
+
+  match kind {
+    TsTypeDefKind::Keyword => {
+      match ts_type.keyword.as_ref().unwrap().as_str() {
+        keyword @ "string"
+        | keyword @ "number"
+        | keyword @ "boolean"
+        | keyword @ "null"
+        | keyword @ "object" => json!({ "type": keyword }),
+        "never" => json!({ "not": {} }),
+        _ => json!({}),
+      }
+    }
+    TsTypeDefKind::Literal => match literal_value(ts_type) {
+      Some(value) => json!({ "const": value }),
+      None => json!({}),
+    },
+    TsTypeDefKind::Union => {
+      let union = ts_type.union.as_ref().unwrap();
+      let literals: Vec<Value> =
+        union.iter().filter_map(literal_value).collect();
+      if literals.len() == union.len() {
+        json!({ "enum": literals })
+      } else {
+        let schemas: Vec<Value> = union
+          .iter()
+          .map(|ts_type| ts_type_schema(ts_type, names))
+          .collect();
+        json!({ "anyOf": schemas })
+      }
+    }
+    TsTypeDefKind::Intersection => {
+      let schemas: Vec<Value> = ts_type
+        .intersection
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|ts_type| ts_type_schema(ts_type, names))
+        .collect();
+      json!({ "allOf": schemas })
+    }
+    TsTypeDefKind::Array => json!({
+      "type": "array",
+      "items": ts_type_schema(ts_type.array.as_ref().unwrap(), names),
+    }),
+    TsTypeDefKind::Tuple => {
+      tuple_schema(ts_type.tuple.as_ref().unwrap(), names)
+    }
+    TsTypeDefKind::Parenthesized => {
+      ts_type_schema(ts_type.parenthesized.as_ref().unwrap(), names)
+    }
+    TsTypeDefKind::TypeLiteral => {
+      type_literal_schema(ts_type.type_literal.as_ref().unwrap(), names)
+    }
+    TsTypeDefKind::TypeRef => {
+      let type_ref = ts_type.type_ref.as_ref().unwrap();
+      let type_params = type_ref.type_params.as_deref().unwrap_or(&[]);
+      match (type_ref.type_name.as_str(), type_params) {
+        (name, _) if names.iter().any(|n| n == name) => {
+          json!({ "$ref": format!("#/definitions/{}", name) })
+        }
+        ("Array", [item]) | ("ReadonlyArray", [item]) => json!({
+          "type": "array",
+          "items": ts_type_schema(item, names),
+        }),
+        ("Record", [_, value]) => json!({
+          "type": "object",
+          "additionalProperties": ts_type_schema(value, names),
+        }),
+        ("Date", _) => json!({ "type": "string", "format": "date-time" }),
+        _ => json!({}),
+      }
+    }
+    _ => json!({}),
+  }
+}
+
+fn tuple_schema(elements: &[TsTypeDef], names: &[String]) -> Value {
+  let mut items = vec![];
+  let mut min_items = 0;
+  let mut rest = None;
+
+  for element in elements {
+    match &element.kind {
+      Some(TsTypeDefKind::Optional) => {
+        items.push(ts_type_schema(element.optional.as_ref().unwrap(), names))
+      }
+      Some(TsTypeDefKind::Rest) => rest = element.rest.as_ref(),
+      _ => {
+        items.push(ts_type_schema(element, names));
+        min_items = items.len();
+      }
+    }
+  }
+
+  let mut schema = json!({
+    "type": "array",
+    "items": items,
+    "minItems": min_items,
+  });
+  match rest {
+    Some(rest) => {
+      // The rest element is an array type, its items follow the fixed ones.
+      let rest_schema = ts_type_schema(rest, names);
+      schema["additionalItems"] = rest_schema
+        .get("items")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    }
+    None => schema["maxItems"] = json!(elements.len()),
+  }
+  schema
+}
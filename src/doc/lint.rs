@@ -0,0 +1,241 @@
+use crate::doc::examples;
+use crate::doc::js_doc::JsDoc;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+use crate::doc::Location;
+
+#[derive(Debug)]
+pub struct LintDiagnostic {
+  pub rule: &'static str,
+  pub message: String,
+  pub location: Location,
+}
+
+/// Every rule along with a short description.
+pub const RULES: &[(&str, &str)] = &[
+  (
+    "require-returns",
+    "Exported functions with a non-void return type document it with @returns",
+  ),
+  (
+    "param-names",
+    "@param tags match one of the function's parameters",
+  ),
+  ("check-examples", "@example code blocks type check"),
+];
+
+impl LintDiagnostic {
+  /// GitHub Actions workflow command, shown as an annotation of the line in
+  /// pull requests.
+  pub fn to_github_annotation(&self) -> String {
+    fn escape_data(data: &str) -> String {
+      data
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+    }
+
+    fn escape_property(property: &str) -> String {
+      escape_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+    }
+
+    format!(
+      "::error file={},line={},col={},title={}::{}",
+      escape_property(&self.location.filename),
+      self.location.line,
+      self.location.col + 1,
+      escape_property(self.rule),
+      escape_data(&self.message)
+    )
+  }
+}
+
+/// SARIF log of `diagnostics`, for code scanning tools.
+pub fn to_sarif(diagnostics: &[LintDiagnostic]) -> serde_json::Value {
+  use serde_json::json;
+
+  let rules: Vec<serde_json::Value> = RULES
+    .iter()
+    .map(|(id, description)| {
+      json!({
+        "id": id,
+        "shortDescription": { "text": description },
+      })
+    })
+    .collect();
+
+  let results: Vec<serde_json::Value> = diagnostics
+    .iter()
+    .map(|diagnostic| {
+      json!({
+        "ruleId": diagnostic.rule,
+        "level": "error",
+        "message": { "text": diagnostic.message },
+        "locations": [{
+          "physicalLocation": {
+            "artifactLocation": {
+              "uri": diagnostic.location.filename.trim_start_matches("./"),
+            },
+            "region": {
+              "startLine": diagnostic.location.line,
+              "startColumn": diagnostic.location.col + 1,
+            },
+          },
+        }],
+      })
+    })
+    .collect();
+
+  json!({
+    "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+    "version": "2.1.0",
+    "runs": [{
+      "tool": {
+        "driver": {
+          "name": "deno_doc",
+          "version": env!("CARGO_PKG_VERSION"),
+          "rules": rules,
+        },
+      },
+      "results": results,
+    }],
+  })
+}
+
+pub fn lint(doc_nodes: &[DocNode]) -> Vec<LintDiagnostic> {
+  let mut diagnostics = vec![];
+  lint_(doc_nodes, "", &mut diagnostics);
+  diagnostics
+}
+
+fn lint_(
+  doc_nodes: &[DocNode],
+  namespace: &str,
+  diagnostics: &mut Vec<LintDiagnostic>,
+) {
+  for node in doc_nodes {
+    let name = format!("{}{}", namespace, node.name);
+
+    if node.kind == DocNodeKind::Function {
+      require_returns(node, &name, diagnostics);
+      param_names(node, &name, diagnostics);
+    }
+    if let Some(namespace_def) = &node.namespace_def {
+      lint_(&namespace_def.elements, &format!("{}.", name), diagnostics);
+    }
+  }
+}
+
+fn is_void_type(repr: &str) -> bool {
+  matches!(repr, "void" | "never" | "Promise<void>")
+}
+
+/// Functions with a non-void return type must describe it with `@returns`.
+fn require_returns(
+  node: &DocNode,
+  name: &str,
+  diagnostics: &mut Vec<LintDiagnostic>,
+) {
+  let function_def = node.function_def.as_ref().unwrap();
+  let return_type = match &function_def.return_type {
+    Some(return_type) if !is_void_type(&return_type.repr) => return_type,
+    _ => return,
+  };
+
+  let has_returns = match &node.js_doc {
+    Some(js_doc) => {
+      let js_doc = JsDoc::parse(js_doc);
+      let mut returns_tags = js_doc
+        .tags_named("returns")
+        .chain(js_doc.tags_named("return"));
+      returns_tags.any(|tag| !tag.value.is_empty())
+    }
+    None => false,
+  };
+
+  if !has_returns {
+    diagnostics.push(LintDiagnostic {
+      rule: "require-returns",
+      message: format!(
+        "Function `{}` returns `{}` but has no @returns documentation",
+        name, return_type.repr
+      ),
+      location: node.location.clone(),
+    });
+  }
+}
+
+/// `@param` tags must refer to one of the function's parameters.
+fn param_names(
+  node: &DocNode,
+  name: &str,
+  diagnostics: &mut Vec<LintDiagnostic>,
+) {
+  let js_doc = match &node.js_doc {
+    Some(js_doc) => JsDoc::parse(js_doc),
+    None => return,
+  };
+
+  let params = &node.function_def.as_ref().unwrap().params;
+  // Names of destructured parameters are arbitrary in JSDoc.
+  if params.iter().any(|param| param.name == "<TODO>") {
+    return;
+  }
+
+  for tag in js_doc.tags_named("param") {
+    let param_name = match tag.param_name() {
+      Some(param_name) => param_name,
+      None => continue,
+    };
+
+    if !params.iter().any(|param| param.name == param_name) {
+      diagnostics.push(LintDiagnostic {
+        rule: "param-names",
+        message: format!(
+          "@param `{}` does not match any parameter of `{}`",
+          param_name, name
+        ),
+        location: node.location.clone(),
+      });
+    }
+  }
+}
+
+/// Type check `@example` code blocks by running each of them through
+/// `deno check` as a standalone module importing the documented file.
+pub fn check_examples(
+  file_name: &str,
+  doc_nodes: &[DocNode],
+) -> std::io::Result<Vec<LintDiagnostic>> {
+  let examples = examples::collect_examples(doc_nodes);
+  let specifier = examples::file_specifier(file_name);
+  let sources: Vec<String> = examples
+    .iter()
+    .map(|example| {
+      examples::example_module_source(example, &specifier, doc_nodes)
+    })
+    .collect();
+  let (dir, paths) = examples::write_example_modules(&sources)?;
+
+  let mut diagnostics = vec![];
+  for (example, path) in examples.iter().zip(paths.iter()) {
+    let output = examples::run_deno("check", path)?;
+
+    if !output.status.success() {
+      diagnostics.push(LintDiagnostic {
+        rule: "check-examples",
+        message: format!(
+          "Example for `{}` does not type check:\n{}",
+          example.symbol,
+          String::from_utf8_lossy(&output.stderr).trim_end()
+        ),
+        location: example.location.clone(),
+      });
+    }
+  }
+
+  let _ = std::fs::remove_dir_all(dir);
+  Ok(diagnostics)
+}
@@ -0,0 +1,189 @@
+use serde_json::json;
+use serde_json::Value;
+
+use crate::doc::examples::file_specifier;
+use crate::doc::js_doc::JsDoc;
+use crate::doc::printer::format_signature;
+use crate::doc::printer::PrinterOptions;
+use crate::doc::DocNode;
+
+/// Vertices and edges of an LSIF dump, with consecutive ids.
+struct Dump {
+  elements: Vec<Value>,
+}
+
+impl Dump {
+  fn vertex(&mut self, label: &str, mut data: Value) -> usize {
+    let id = self.elements.len() + 1;
+    data["id"] = json!(id);
+    data["type"] = json!("vertex");
+    data["label"] = json!(label);
+    self.elements.push(data);
+    id
+  }
+
+  fn edge(&mut self, label: &str, mut data: Value) -> usize {
+    let id = self.elements.len() + 1;
+    data["id"] = json!(id);
+    data["type"] = json!("edge");
+    data["label"] = json!(label);
+    self.elements.push(data);
+    id
+  }
+}
+
+struct Document {
+  id: usize,
+  filename: String,
+  ranges: Vec<usize>,
+}
+
+/// Range of the name of `node`, found on the first line of its declaration.
+fn name_range(node: &DocNode) -> Value {
+  let first_line = node.snippet.lines().next().unwrap_or("");
+  let offset = first_line.find(&node.name).unwrap_or(0);
+  let character = node.location.col + first_line[..offset].chars().count();
+  let line = node.location.line - 1;
+  json!({
+    "start": { "line": line, "character": character },
+    "end": {
+      "line": line,
+      "character": character + node.name.chars().count(),
+    },
+  })
+}
+
+fn hover_contents(node: &DocNode) -> Value {
+  let signature = format_signature(node, &PrinterOptions::default());
+  let mut contents =
+    vec![json!({ "language": "typescript", "value": signature })];
+  if let Some(js_doc) = &node.js_doc {
+    let description = JsDoc::parse(js_doc).description;
+    if !description.is_empty() {
+      contents.push(json!(description));
+    }
+  }
+  json!(contents)
+}
+
+fn add_symbols(
+  dump: &mut Dump,
+  documents: &mut Vec<Document>,
+  doc_nodes: &[DocNode],
+  specifier: &str,
+  namespace: &str,
+) {
+  for node in doc_nodes {
+    let name = format!("{}{}", namespace, node.name);
+
+    let filename = &node.location.filename;
+    let index = match documents.iter().position(|d| &d.filename == filename) {
+      Some(index) => index,
+      None => {
+        let id = dump.vertex(
+          "document",
+          json!({
+            "uri": file_specifier(filename),
+            "languageId": "typescript",
+          }),
+        );
+        documents.push(Document {
+          id,
+          filename: filename.to_string(),
+          ranges: vec![],
+        });
+        documents.len() - 1
+      }
+    };
+
+    let result_set = dump.vertex("resultSet", json!({}));
+    let range = dump.vertex("range", name_range(node));
+    dump.edge("next", json!({ "outV": range, "inV": result_set }));
+
+    let hover = dump.vertex(
+      "hoverResult",
+      json!({ "result": { "contents": hover_contents(node) } }),
+    );
+    dump.edge(
+      "textDocument/hover",
+      json!({ "outV": result_set, "inV": hover }),
+    );
+
+    let definition = dump.vertex("definitionResult", json!({}));
+    dump.edge(
+      "textDocument/definition",
+      json!({ "outV": result_set, "inV": definition }),
+    );
+    dump.edge(
+      "item",
+      json!({
+        "outV": definition,
+        "inVs": [range],
+        "document": documents[index].id,
+      }),
+    );
+
+    let moniker = dump.vertex(
+      "moniker",
+      json!({
+        "kind": "export",
+        "scheme": "deno_doc",
+        "identifier": format!("{}#{}", specifier, name),
+      }),
+    );
+    dump.edge("moniker", json!({ "outV": result_set, "inV": moniker }));
+
+    documents[index].ranges.push(range);
+
+    if let Some(namespace_def) = &node.namespace_def {
+      let namespace = format!("{}.", name);
+      add_symbols(
+        dump,
+        documents,
+        &namespace_def.elements,
+        specifier,
+        &namespace,
+      );
+    }
+  }
+}
+
+/// LSIF dump, one JSON element per line, with hover documentation and
+/// definitions of the symbols exported by the module at `specifier`.
+pub fn print_lsif(doc_nodes: &[DocNode], specifier: &str) -> String {
+  let mut dump = Dump { elements: vec![] };
+  let project_root = std::env::current_dir()
+    .map(|dir| file_specifier(&dir.to_string_lossy()))
+    .unwrap_or_default();
+  dump.vertex(
+    "metaData",
+    json!({
+      "version": "0.4.3",
+      "projectRoot": project_root,
+      "positionEncoding": "utf-16",
+      "toolInfo": { "name": "deno_doc", "version": env!("CARGO_PKG_VERSION") },
+    }),
+  );
+  let project = dump.vertex("project", json!({ "kind": "typescript" }));
+
+  let mut documents = vec![];
+  add_symbols(&mut dump, &mut documents, doc_nodes, specifier, "");
+
+  for document in &documents {
+    dump.edge(
+      "contains",
+      json!({ "outV": document.id, "inVs": document.ranges }),
+    );
+  }
+  if !documents.is_empty() {
+    let document_ids: Vec<usize> = documents.iter().map(|d| d.id).collect();
+    dump.edge("contains", json!({ "outV": project, "inVs": document_ids }));
+  }
+
+  let mut out = String::new();
+  for element in &dump.elements {
+    out.push_str(&serde_json::to_string(element).unwrap());
+    out.push('\n');
+  }
+  out
+}
@@ -0,0 +1,248 @@
+use crate::doc::js_doc::JsDoc;
+use crate::doc::js_doc::JsDocTag;
+use crate::doc::printer::find_path;
+use crate::doc::printer::format_signature;
+use crate::doc::printer::iteration_notes;
+use crate::doc::printer::render_ts_type;
+use crate::doc::printer::PrinterOptions;
+use crate::doc::tags::group_by_category;
+use crate::doc::ts_type::TsTypeDefKind;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+use crate::doc::ParamDef;
+use crate::doc::ParamKind;
+
+/// Markdown document with a section for each of `doc_nodes`; functions get a
+/// table of their parameters.
+pub fn print_markdown(doc_nodes: &[DocNode]) -> String {
+  let mut out = String::new();
+  print_sections(&mut out, doc_nodes, doc_nodes, 2, "");
+  out
+}
+
+fn print_sections(
+  out: &mut String,
+  doc_nodes: &[DocNode],
+  all_nodes: &[DocNode],
+  level: usize,
+  namespace: &str,
+) {
+  let nodes: Vec<&DocNode> = doc_nodes.iter().collect();
+  for (category, nodes) in group_by_category(&nodes) {
+    match category {
+      Some(category) => {
+        out.push_str(&format!("{} {}\n\n", "#".repeat(level), category));
+        let level = (level + 1).min(6);
+        for node in nodes {
+          print_section(out, node, all_nodes, level, namespace);
+        }
+      }
+      None => {
+        for node in nodes {
+          print_section(out, node, all_nodes, level, namespace);
+        }
+      }
+    }
+  }
+}
+
+fn print_section(
+  out: &mut String,
+  node: &DocNode,
+  all_nodes: &[DocNode],
+  level: usize,
+  namespace: &str,
+) {
+  let name = format!("{}{}", namespace, node.name);
+  out.push_str(&format!("{} {}\n\n", "#".repeat(level), name));
+  out.push_str(&format!(
+    "```ts\n{}\n```\n\n",
+    format_signature(node, &PrinterOptions::default())
+  ));
+
+  let js_doc = node.js_doc.as_ref().map(|js_doc| JsDoc::parse(js_doc));
+  if let Some(js_doc) = &js_doc {
+    if !js_doc.description.is_empty() {
+      out.push_str(&format!("{}\n\n", js_doc.description));
+    }
+  }
+
+  if let Some(stability) = node.stability {
+    out.push_str(&format!("**Stability**: {}\n\n", stability.as_str()));
+  }
+
+  for tag in &node.custom_tags {
+    out.push_str(&format!("**{}**: {}\n\n", tag.label, tag.value));
+  }
+
+  if let Some(class_def) = &node.class_def {
+    for note in iteration_notes(class_def) {
+      out.push_str(&format!("{}\n\n", note));
+    }
+  }
+
+  if let Some(function_def) = &node.function_def {
+    let tags: Vec<&JsDocTag> = match &js_doc {
+      Some(js_doc) => js_doc.tags_named("param").collect(),
+      None => vec![],
+    };
+    print_param_table(out, &function_def.params, &tags, all_nodes);
+  }
+
+  if let Some(namespace_def) = &node.namespace_def {
+    let namespace = format!("{}.", name);
+    let level = (level + 1).min(6);
+    print_sections(out, &namespace_def.elements, all_nodes, level, &namespace);
+  }
+}
+
+fn escape_cell(text: &str) -> String {
+  text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn code_cell(text: &str) -> String {
+  if text.is_empty() {
+    "".to_string()
+  } else {
+    format!("`{}`", escape_cell(text))
+  }
+}
+
+struct ParamRow {
+  name: String,
+  ts_type: String,
+  default: String,
+  description: String,
+}
+
+/// Rows for `params`, followed by the members of options objects: properties
+/// of a parameter typed as an interface of this module and `@param` tags
+/// documenting `param.property`.
+fn param_rows(
+  params: &[ParamDef],
+  tags: &[&JsDocTag],
+  all_nodes: &[DocNode],
+) -> Vec<ParamRow> {
+  let find_tag =
+    |path: &str| tags.iter().find(|tag| tag.param_path() == Some(path));
+  let mut rows = vec![];
+
+  for param in params {
+    if param.kind == ParamKind::This {
+      continue;
+    }
+    let tag = find_tag(&param.name);
+    let default = param.default.as_deref();
+    rows.push(ParamRow {
+      name: if param.optional {
+        format!("{}?", param.name)
+      } else {
+        param.name.to_string()
+      },
+      ts_type: param
+        .ts_type
+        .as_ref()
+        .map(render_ts_type)
+        .unwrap_or_default(),
+      default: tag
+        .and_then(|tag| tag.param_default())
+        .or(default)
+        .unwrap_or("")
+        .to_string(),
+      description: tag
+        .map(|tag| tag.param_description())
+        .unwrap_or("")
+        .to_string(),
+    });
+
+    let type_ref =
+      param
+        .ts_type
+        .as_ref()
+        .and_then(|ts_type| match ts_type.kind {
+          Some(TsTypeDefKind::TypeRef) => ts_type.type_ref.as_ref(),
+          _ => None,
+        });
+    // Qualified names like `ns.Options` refer to namespace elements.
+    let interface_def = type_ref.and_then(|type_ref| {
+      find_path(all_nodes, &type_ref.type_name)
+        .filter(|node| node.kind == DocNodeKind::Interface)
+        .and_then(|node| node.interface_def.as_ref())
+    });
+    if let Some(interface_def) = interface_def {
+      for prop in &interface_def.properties {
+        let path = format!("{}.{}", param.name, prop.name);
+        let tag = find_tag(&path);
+        let description = match (tag, &prop.js_doc) {
+          (Some(tag), _) => tag.param_description().to_string(),
+          (None, Some(js_doc)) => JsDoc::parse(js_doc).description,
+          (None, None) => "".to_string(),
+        };
+        rows.push(ParamRow {
+          name: if prop.optional {
+            format!("{}?", path)
+          } else {
+            path
+          },
+          ts_type: prop
+            .ts_type
+            .as_ref()
+            .map(render_ts_type)
+            .unwrap_or_default(),
+          default: tag
+            .and_then(|tag| tag.param_default())
+            .unwrap_or("")
+            .to_string(),
+          description,
+        });
+      }
+    }
+
+    let prefix = format!("{}.", param.name);
+    for tag in tags {
+      let path = match tag.param_path() {
+        Some(path) if path.starts_with(&prefix) => path,
+        _ => continue,
+      };
+      if rows
+        .iter()
+        .any(|row| row.name.trim_end_matches('?') == path)
+      {
+        continue;
+      }
+      rows.push(ParamRow {
+        name: path.to_string(),
+        ts_type: tag.param_type().unwrap_or("").to_string(),
+        default: tag.param_default().unwrap_or("").to_string(),
+        description: tag.param_description().to_string(),
+      });
+    }
+  }
+
+  rows
+}
+
+fn print_param_table(
+  out: &mut String,
+  params: &[ParamDef],
+  tags: &[&JsDocTag],
+  all_nodes: &[DocNode],
+) {
+  let rows = param_rows(params, tags, all_nodes);
+  if rows.is_empty() {
+    return;
+  }
+
+  out.push_str("| Name | Type | Default | Description |\n");
+  out.push_str("| --- | --- | --- | --- |\n");
+  for row in rows {
+    out.push_str(&format!(
+      "| {} | {} | {} | {} |\n",
+      code_cell(&row.name),
+      code_cell(&row.ts_type),
+      code_cell(&row.default),
+      escape_cell(&row.description)
+    ));
+  }
+  out.push('\n');
+}
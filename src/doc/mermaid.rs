@@ -0,0 +1,146 @@
+use swc_ecma_ast::Accessibility;
+
+use crate::doc::graph::RelationshipKind;
+use crate::doc::graph::SymbolGraph;
+use crate::doc::printer::render_ts_type;
+use crate::doc::ts_type::TsTypeDef;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+
+/// Mermaid class names can't contain dots, so namespaced ids are joined with
+/// underscores and the qualified name is kept as a label.
+fn class_name(id: &str) -> String {
+  id.replace('.', "_")
+}
+
+fn visibility(accessibility: Option<Accessibility>) -> &'static str {
+  match accessibility {
+    Some(Accessibility::Private) => "-",
+    Some(Accessibility::Protected) => "#",
+    _ => "+",
+  }
+}
+
+/// Generic arguments are written with tildes, and types with braces would end
+/// the class body, so those are left out.
+fn member_type(ts_type: &Option<TsTypeDef>) -> String {
+  let rendered = match ts_type {
+    Some(ts_type) => render_ts_type(ts_type),
+    None => return "".to_string(),
+  };
+  if rendered.contains('{') || rendered.contains('}') {
+    return "".to_string();
+  }
+  // Mermaid writes type parameters as `Map~string, number~`.
+  let rendered: String = rendered
+    .chars()
+    .map(|c| if c == '<' || c == '>' { '~' } else { c })
+    .collect();
+  format!(" {}", rendered)
+}
+
+fn modifiers(is_abstract: bool, is_static: bool) -> &'static str {
+  if is_abstract {
+    "*"
+  } else if is_static {
+    "$"
+  } else {
+    ""
+  }
+}
+
+fn print_classes(doc_nodes: &[DocNode], namespace: &str, out: &mut String) {
+  for node in doc_nodes {
+    let id = format!("{}{}", namespace, node.name);
+    let mut members = vec![];
+    let stereotype;
+
+    if let Some(class_def) = &node.class_def {
+      stereotype = if class_def.is_abstract {
+        Some("abstract")
+      } else {
+        None
+      };
+      for prop in &class_def.properties {
+        members.push(format!(
+          "{}{}{}{}",
+          visibility(prop.accessibility),
+          prop.name,
+          member_type(&prop.ts_type).replacen(' ', " : ", 1),
+          modifiers(prop.is_abstract, prop.is_static)
+        ));
+      }
+      for method in &class_def.methods {
+        members.push(format!(
+          "{}{}(){}",
+          visibility(method.accessibility),
+          method.name,
+          modifiers(method.is_abstract, method.is_static)
+        ));
+      }
+    } else if node.kind == DocNodeKind::Interface {
+      let interface_def = node.interface_def.as_ref().unwrap();
+      stereotype = Some("interface");
+      for construct in &interface_def.construct_signatures {
+        members.push(format!("+new(){}", member_type(&construct.ts_type)));
+      }
+      for prop in &interface_def.properties {
+        members.push(format!(
+          "+{}{}",
+          prop.name,
+          member_type(&prop.ts_type).replacen(' ', " : ", 1)
+        ));
+      }
+      for method in &interface_def.methods {
+        members.push(format!(
+          "+{}(){}",
+          method.name,
+          member_type(&method.return_type)
+        ));
+      }
+    } else {
+      if let Some(namespace_def) = &node.namespace_def {
+        print_classes(&namespace_def.elements, &format!("{}.", id), out);
+      }
+      continue;
+    }
+
+    out.push_str(&format!("  class {}", class_name(&id)));
+    if !namespace.is_empty() {
+      out.push_str(&format!("[\"{}\"]", id));
+    }
+    out.push_str(" {\n");
+    if let Some(stereotype) = stereotype {
+      out.push_str(&format!("    <<{}>>\n", stereotype));
+    }
+    for member in members {
+      out.push_str(&format!("    {}\n", member));
+    }
+    out.push_str("  }\n");
+  }
+}
+
+/// Mermaid `classDiagram` of the documented classes and interfaces, with
+/// their properties and methods, and the classes and interfaces they extend
+/// or implement.
+pub fn print_mermaid(doc_nodes: &[DocNode]) -> String {
+  let mut out = String::new();
+  out.push_str("classDiagram\n");
+  print_classes(doc_nodes, "", &mut out);
+
+  let graph = SymbolGraph::new(doc_nodes);
+  for relationship in &graph.relationships {
+    let arrow = match relationship.kind {
+      RelationshipKind::Extends => "<|--",
+      RelationshipKind::Implements => "<|..",
+      _ => continue,
+    };
+    out.push_str(&format!(
+      "  {} {} {}\n",
+      class_name(&relationship.target),
+      arrow,
+      class_name(&relationship.source)
+    ));
+  }
+  out
+}
@@ -1,13 +1,40 @@
+use serde::Deserialize;
 use serde::Serialize;
 use swc_common;
 use swc_ecma_ast;
 
+pub mod compat;
+pub mod ctags;
+pub mod diff;
+pub mod dot;
+pub mod dts;
+pub mod examples;
+pub mod exclude;
+pub mod fetch;
+pub mod graph;
+pub mod import;
+pub mod js_doc;
+pub mod json_schema;
+pub mod lint;
+pub mod lsif;
+pub mod markdown;
+pub mod mermaid;
+pub mod module_graph;
+pub mod newer_syntax;
 pub mod parser;
+pub mod printer;
+pub mod stats;
+pub mod tags;
+pub mod transform;
+pub mod tree;
 pub mod ts_type;
+pub mod usage;
+pub mod workspace;
 
 use ts_type::TsTypeDef;
+use ts_type::TsTypeParamDef;
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum DocNodeKind {
   Function,
@@ -19,116 +46,301 @@ pub enum DocNodeKind {
   Namespace,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParamKind {
+  Identifier,
+  /// Rest parameter like `...args`.
+  Rest,
+  /// Destructured object like `{ a, b }`, named after the pattern.
+  Object,
+  /// Destructured array like `[x, y]`, named after the pattern.
+  Array,
+  /// `this: Type` declaring the type of `this` inside the function; it isn't
+  /// an argument callers pass.
+  This,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ParamDef {
+  pub kind: ParamKind,
   pub name: String,
   pub ts_type: Option<TsTypeDef>,
+  /// Whether the parameter is marked optional with `?`.
+  pub optional: bool,
+  /// Source of the default value, eg. `42` for `a = 42`.
+  pub default: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FunctionDef {
   pub params: Vec<ParamDef>,
   pub return_type: Option<TsTypeDef>,
   pub is_async: bool,
   pub is_generator: bool,
-  // TODO: type_params, decorators
+  pub type_params: Vec<TsTypeParamDef>,
+  /// Further overload signatures, the signature of the implementation isn't
+  /// documented.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub overloads: Vec<FunctionOverloadDef>,
+  // TODO: decorators
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionOverloadDef {
+  pub js_doc: Option<String>,
+  pub snippet: String,
+  #[serde(flatten)]
+  pub function_def: FunctionDef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct VariableDef {
-  type_: TsTypeDef,
-  kind: swc_ecma_ast::VarDeclKind,
+  pub ts_type: Option<TsTypeDef>,
+  pub kind: swc_ecma_ast::VarDeclKind,
+  /// Source of the initializer, eg. `new Map()`.
+  #[serde(default)]
+  pub init: Option<String>,
+  /// Whether the initializer is a literal like `5` or `"a"`, which ambient
+  /// constants can be declared with.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub is_literal: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumMemberDef {
   pub name: String,
+  /// Source of the initializer expression.
+  pub init: Option<String>,
+  /// Constant-folded value of the member, if it can be computed statically.
+  pub value: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EnumDef {
   pub members: Vec<EnumMemberDef>,
+  /// Whether it's a `const enum`, whose members are inlined at compile time.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub is_const: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClassConstructorDef {
   pub js_doc: Option<String>,
+  #[serde(default)]
+  pub location: Location,
   pub snippet: String,
   pub accessibility: Option<swc_ecma_ast::Accessibility>,
   pub name: String,
+  /// Parameters, including the ones declaring properties, without their
+  /// modifiers.
+  #[serde(default)]
+  pub params: Vec<ParamDef>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClassPropertyDef {
   pub js_doc: Option<String>,
+  #[serde(default)]
+  pub location: Location,
   pub snippet: String,
   pub ts_type: Option<TsTypeDef>,
+  pub init: Option<String>,
   pub readonly: bool,
+  pub optional: bool,
   pub accessibility: Option<swc_ecma_ast::Accessibility>,
   pub is_abstract: bool,
   pub is_static: bool,
   pub name: String,
+  /// Set for properties declared with `get` and `set` accessors.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub accessor: Option<AccessorKind>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub decorators: Vec<DecoratorDef>,
 }
 
-#[derive(Debug, Serialize)]
+/// Decorator like `@Inject("db")` applied to a class or its members.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecoratorDef {
+  pub name: String,
+  /// Source of each argument, `None` if the decorator isn't called.
+  pub args: Option<Vec<String>>,
+}
+
+/// Accessors declaring a property, a property with only a getter is
+/// `readonly`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AccessorKind {
+  Getter,
+  Setter,
+  GetterSetter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClassMethodDef {
   pub js_doc: Option<String>,
+  #[serde(default)]
+  pub location: Location,
   pub snippet: String,
   //   pub ts_type: Option<TsTypeDef>,
   //   pub readonly: bool,
   pub accessibility: Option<swc_ecma_ast::Accessibility>,
   pub is_abstract: bool,
   pub is_static: bool,
+  pub optional: bool,
   pub name: String,
   pub kind: swc_ecma_ast::MethodKind,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub decorators: Vec<DecoratorDef>,
+  #[serde(default)]
+  pub function_def: FunctionDef,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClassDef {
-  // TODO: decorators, super_class, implements,
-  // type_params, super_type_params
   pub is_abstract: bool,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub decorators: Vec<DecoratorDef>,
+  pub type_params: Vec<TsTypeParamDef>,
+  pub extends: Option<String>,
+  pub super_type_params: Vec<TsTypeDef>,
+  pub implements: Vec<String>,
   pub constructors: Vec<ClassConstructorDef>,
   pub properties: Vec<ClassPropertyDef>,
   pub methods: Vec<ClassMethodDef>,
+  /// Whether instances work with `for...of`, having a `[Symbol.iterator]()`
+  /// method.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub is_iterable: bool,
+  /// Whether instances work with `for await...of`, having a
+  /// `[Symbol.asyncIterator]()` method.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub is_async_iterable: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TypeAliasDef {
-  // pub ts_type: TsTypeDef,
-// TODO: type_params
+  pub ts_type: TsTypeDef,
+  pub type_params: Vec<TsTypeParamDef>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NamespaceDef {
   pub elements: Vec<DocNode>,
+  /// Set for blocks adding declarations to a scope outside of the module.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub augmentation: Option<Augmentation>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Augmentation {
+  /// `declare global { ... }`, named `global`.
+  Global,
+  /// `declare module "specifier" { ... }`, named after the specifier.
+  Module,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterfacePropertyDef {
+  pub name: String,
+  pub js_doc: Option<String>,
+  #[serde(default)]
+  pub location: Location,
+  pub ts_type: Option<TsTypeDef>,
+  pub readonly: bool,
+  pub optional: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterfaceMethodDef {
+  pub name: String,
+  pub js_doc: Option<String>,
+  #[serde(default)]
+  pub location: Location,
+  pub params: Vec<ParamDef>,
+  pub return_type: Option<TsTypeDef>,
+  pub optional: bool,
+}
+
+/// Index signature like `[key: string]: T`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterfaceIndexSignatureDef {
+  pub js_doc: Option<String>,
+  #[serde(default)]
+  pub location: Location,
+  pub readonly: bool,
+  pub params: Vec<ParamDef>,
+  pub ts_type: Option<TsTypeDef>,
+}
+
+/// Call signature like `(x: T): U`, or construct signature like
+/// `new (x: T): U`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterfaceCallSignatureDef {
+  pub js_doc: Option<String>,
+  #[serde(default)]
+  pub location: Location,
+  pub params: Vec<ParamDef>,
+  pub ts_type: Option<TsTypeDef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InterfaceDef {
-  // TODO: extends, type params
-// TODO: elements https://docs.rs/swc_ecma_ast/0.18.1/swc_ecma_ast/enum.TsTypeElement.html
+  pub type_params: Vec<TsTypeParamDef>,
+  pub extends: Vec<String>,
+  pub properties: Vec<InterfacePropertyDef>,
+  pub methods: Vec<InterfaceMethodDef>,
+  pub index_signatures: Vec<InterfaceIndexSignatureDef>,
+  pub call_signatures: Vec<InterfaceCallSignatureDef>,
+  pub construct_signatures: Vec<InterfaceCallSignatureDef>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Location {
-  filename: String,
-  line: usize,
-  col: usize,
+  pub filename: String,
+  pub line: usize,
+  pub col: usize,
+  /// Line and column right after the end of the declaration.
+  #[serde(default)]
+  pub end_line: usize,
+  #[serde(default)]
+  pub end_col: usize,
+  /// Byte range of the declaration in the file, to slice its source with.
+  #[serde(default)]
+  pub byte_start: usize,
+  #[serde(default)]
+  pub byte_end: usize,
 }
 
-impl Into<Location> for swc_common::Loc {
-  fn into(self) -> Location {
+impl Location {
+  pub fn from_span(
+    source_map: &swc_common::SourceMap,
+    span: swc_common::Span,
+  ) -> Self {
     use swc_common::FileName::*;
 
-    let filename = match &self.file.name {
+    let start = source_map.lookup_char_pos(span.lo());
+    let end = source_map.lookup_char_pos(span.hi());
+    let filename = match &start.file.name {
       Real(path_buf) => path_buf.to_string_lossy().to_string(),
       Custom(str_) => str_.to_string(),
       _ => panic!("invalid filename"),
@@ -136,21 +348,107 @@ impl Into<Location> for swc_common::Loc {
 
     Location {
       filename,
-      line: self.line,
-      col: self.col_display,
+      line: start.line,
+      col: start.col_display,
+      end_line: end.line,
+      end_col: end.col_display,
+      byte_start: source_map.lookup_byte_offset(span.lo()).pos.0 as usize,
+      byte_end: source_map.lookup_byte_offset(span.hi()).pos.0 as usize,
     }
   }
 }
 
-#[derive(Debug, Serialize)]
+/// Release stage of a symbol, from `@experimental`, `@alpha`, `@beta`,
+/// `@public` or `@internal` tags.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Stability {
+  Internal,
+  Experimental,
+  Alpha,
+  Beta,
+  Public,
+}
+
+impl Stability {
+  pub fn from_tag(tag: &str) -> Option<Stability> {
+    match tag {
+      "internal" => Some(Stability::Internal),
+      "experimental" => Some(Stability::Experimental),
+      "alpha" => Some(Stability::Alpha),
+      "beta" => Some(Stability::Beta),
+      "public" => Some(Stability::Public),
+      _ => None,
+    }
+  }
+
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Stability::Internal => "internal",
+      Stability::Experimental => "experimental",
+      Stability::Alpha => "alpha",
+      Stability::Beta => "beta",
+      Stability::Public => "public",
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomTagDef {
+  pub name: String,
+  pub label: String,
+  pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DocNode {
   pub kind: DocNodeKind,
+  /// Name of the symbol, `default` for the default export of a module.
   pub name: String,
   pub snippet: String,
   pub location: Location,
   pub js_doc: Option<String>,
 
+  /// Value of the `@category` (or `@group`) tag.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub category: Option<String>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub stability: Option<Stability>,
+
+  /// Tags configured with `--tag`, in the order they are documented.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub custom_tags: Vec<CustomTagDef>,
+
+  /// Whether the symbol is the default export of the module.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub is_default: bool,
+
+  /// Whether the symbol is declared with `declare` or in a declaration file,
+  /// so it only describes types and may not exist at runtime.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub is_ambient: bool,
+
+  /// Whether the symbol only exists in type space, like interfaces, type
+  /// aliases and symbols exported with `export type { name }`.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub is_type_only: bool,
+
+  /// Position of the declaration among the symbols of its module or
+  /// namespace, so they can be listed in the order they're written in.
+  #[serde(default)]
+  pub index: usize,
+
+  /// Specifier of the module a re-exported symbol is declared in, as written
+  /// in `export { name } from "./module.ts";`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub reexported_from: Option<String>,
+
+  /// Statement importing the symbol, only set on top-level symbols.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub import_statement: Option<String>,
+
   #[serde(skip_serializing_if = "Option::is_none")]
   pub function_def: Option<FunctionDef>,
 
@@ -172,3 +470,16 @@ pub struct DocNode {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub interface_def: Option<InterfaceDef>,
 }
+
+/// Node of the JSON output of a module, which starts with the description
+/// of the module if it has one.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ModuleJsonNode<'a> {
+  #[serde(rename_all = "camelCase")]
+  ModuleDoc {
+    kind: &'static str,
+    js_doc: &'a str,
+  },
+  Symbol(&'a DocNode),
+}
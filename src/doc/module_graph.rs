@@ -0,0 +1,31 @@
+use crate::doc::DocNode;
+
+/// Symbol a module imports from a local module, eg.
+/// `import { Options } from "./types.ts";`.
+#[derive(Debug)]
+pub struct ImportedSymbol {
+  /// Name the symbol is imported as.
+  pub local_name: String,
+  pub specifier: String,
+  pub doc_node: DocNode,
+}
+
+/// Symbols a module imports from local modules, which the signatures of its
+/// symbols can refer to.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+  pub imports: Vec<ImportedSymbol>,
+  /// Symbols imported with `import type { Options } from "./types.ts";`,
+  /// which are erased at runtime.
+  pub type_imports: Vec<ImportedSymbol>,
+}
+
+impl ModuleGraph {
+  pub fn find(&self, local_name: &str) -> Option<&ImportedSymbol> {
+    self
+      .imports
+      .iter()
+      .chain(&self.type_imports)
+      .find(|import| import.local_name == local_name)
+  }
+}
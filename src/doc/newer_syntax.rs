@@ -0,0 +1,337 @@
+//! Syntax newer than the parser, rewritten into older syntax of the same
+//! length before parsing. Spans then still point into the original source,
+//! which is where the rewritten parts are read back from.
+
+fn is_ident_byte(byte: u8) -> bool {
+  byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || byte >= 0x80
+}
+
+/// Index after the string, template literal or comment starting at `start`,
+/// if there is one. Strings end at the end of the line at the latest, so an
+/// unbalanced quote can't hide the rest of the module.
+fn skip_non_code(bytes: &[u8], start: usize) -> Option<usize> {
+  let end_of = |from: usize, terminator: &[u8]| {
+    let mut i = from;
+    while i < bytes.len() && !bytes[i..].starts_with(terminator) {
+      i += 1;
+    }
+    (i + terminator.len()).min(bytes.len())
+  };
+
+  match (bytes[start], bytes.get(start + 1)) {
+    (b'/', Some(b'/')) => Some(end_of(start + 2, b"\n")),
+    (b'/', Some(b'*')) => Some(end_of(start + 2, b"*/")),
+    (quote @ b'"', _) | (quote @ b'\'', _) | (quote @ b'`', _) => {
+      let mut i = start + 1;
+      while i < bytes.len() && bytes[i] != quote {
+        if bytes[i] == b'\n' && quote != b'`' {
+          break;
+        }
+        i += if bytes[i] == b'\\' { 2 } else { 1 };
+      }
+      Some((i + 1).min(bytes.len()))
+    }
+    _ => None,
+  }
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+  while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+    i += 1;
+  }
+  i
+}
+
+/// Range of the label of the tuple element starting at `start`, eg. `age?:`
+/// in `age?: number` or `rest:` in `...rest: number[]`.
+fn tuple_label(bytes: &[u8], start: usize) -> Option<(usize, usize)> {
+  let mut i = skip_whitespace(bytes, start);
+  if bytes[i..].starts_with(b"...") {
+    i = skip_whitespace(bytes, i + 3);
+  }
+  let label_start = i;
+  while i < bytes.len() && is_ident_byte(bytes[i]) {
+    i += 1;
+  }
+  if i == label_start || bytes[label_start].is_ascii_digit() {
+    return None;
+  }
+  i = skip_whitespace(bytes, i);
+  if bytes.get(i) == Some(&b'?') {
+    i = skip_whitespace(bytes, i + 1);
+  }
+  match (bytes.get(i), bytes.get(i + 1)) {
+    (Some(b':'), Some(b':')) => None,
+    (Some(b':'), _) => Some((label_start, i + 1)),
+    _ => None,
+  }
+}
+
+/// Index after the regular expression literal starting at `start`. Regular
+/// expressions end at the end of the line at the latest.
+fn skip_regex(bytes: &[u8], start: usize) -> usize {
+  let mut in_class = false;
+  let mut i = start + 1;
+  while i < bytes.len() && bytes[i] != b'\n' {
+    match bytes[i] {
+      b'\\' => i += 1,
+      b'[' => in_class = true,
+      b']' => in_class = false,
+      b'/' if !in_class => return i + 1,
+      _ => {}
+    }
+    i += 1;
+  }
+  i.min(bytes.len())
+}
+
+/// Index after the `{ ... }` block starting at `start`, if it's closed.
+fn skip_braces(bytes: &[u8], start: usize) -> Option<usize> {
+  let mut depth = 0;
+  let mut i = start;
+  while i < bytes.len() {
+    if let Some(end) = skip_non_code(bytes, i) {
+      i = end;
+      continue;
+    }
+    match bytes[i] {
+      b'{' => depth += 1,
+      b'}' => {
+        depth -= 1;
+        if depth == 0 {
+          return Some(i + 1);
+        }
+      }
+      _ => {}
+    }
+    i += 1;
+  }
+  None
+}
+
+/// Index after the JSX element or fragment starting at `start`, if it is
+/// one. Generic arrow functions like `<T,>(x: T) => x` aren't.
+fn skip_jsx(bytes: &[u8], start: usize) -> Option<usize> {
+  let is_name_byte = |byte: u8| {
+    is_ident_byte(byte) || byte == b'.' || byte == b':' || byte == b'-'
+  };
+
+  let mut i = skip_whitespace(bytes, start + 1);
+  let name_start = i;
+  while i < bytes.len() && is_name_byte(bytes[i]) {
+    i += 1;
+  }
+  if i == name_start && bytes.get(i) != Some(&b'>') {
+    return None;
+  }
+
+  // Attributes.
+  loop {
+    i = skip_whitespace(bytes, i);
+    match *bytes.get(i)? {
+      b'/' if bytes.get(i + 1) == Some(&b'>') => return Some(i + 2),
+      b'>' => break,
+      b'{' => i = skip_braces(bytes, i)?,
+      b'"' | b'\'' => i = skip_non_code(bytes, i)?,
+      b'=' => i += 1,
+      byte if is_name_byte(byte) => {
+        while i < bytes.len() && is_name_byte(bytes[i]) {
+          i += 1;
+        }
+      }
+      _ => return None,
+    }
+  }
+
+  // Children, up to the closing tag.
+  i += 1;
+  loop {
+    match *bytes.get(i)? {
+      b'{' => i = skip_braces(bytes, i)?,
+      b'<' if bytes.get(i + 1) == Some(&b'/') => {
+        let end = i + bytes[i..].iter().position(|byte| *byte == b'>')?;
+        return Some(end + 1);
+      }
+      b'<' => i = skip_jsx(bytes, i)?,
+      _ => i += 1,
+    }
+  }
+}
+
+/// Identifier or keyword ending at `end`.
+fn word_ending_at(bytes: &[u8], end: usize) -> &[u8] {
+  let mut start = end + 1;
+  while start > 0 && is_ident_byte(bytes[start - 1]) {
+    start -= 1;
+  }
+  &bytes[start..=end]
+}
+
+/// Whether an expression can start after the token ending at `prev`, so a
+/// `/` starts a regular expression and a `<` may start a JSX element.
+fn starts_expression(bytes: &[u8], prev: Option<usize>) -> bool {
+  let prev = match prev {
+    Some(prev) => prev,
+    None => return true,
+  };
+  match bytes[prev] {
+    byte if is_ident_byte(byte) => {
+      let keywords: &[&[u8]] = &[
+        b"return",
+        b"typeof",
+        b"case",
+        b"do",
+        b"else",
+        b"in",
+        b"of",
+        b"instanceof",
+        b"new",
+        b"delete",
+        b"void",
+        b"throw",
+        b"yield",
+        b"await",
+      ];
+      keywords.contains(&word_ending_at(bytes, prev))
+    }
+    b')' | b']' | b'}' | b'"' | b'\'' | b'`' => false,
+    _ => true,
+  }
+}
+
+/// Whether a `[` after the token ending at `prev` starts a member of the
+/// innermost open bracket, like an index signature `[key: string]: T` in a
+/// type literal or interface, rather than a tuple type.
+fn starts_member(
+  bytes: &[u8],
+  innermost: Option<u8>,
+  prev: Option<usize>,
+  line_break: bool,
+) -> bool {
+  let prev = match (innermost, prev) {
+    (Some(b'{'), Some(prev)) => prev,
+    _ => return false,
+  };
+  match bytes[prev] {
+    b'{' | b';' | b',' => true,
+    byte if is_ident_byte(byte) => {
+      let modifiers: &[&[u8]] = &[
+        b"readonly",
+        b"static",
+        b"declare",
+        b"public",
+        b"private",
+        b"protected",
+      ];
+      line_break || modifiers.contains(&word_ending_at(bytes, prev))
+    }
+    // Members without a separator end their line, after a type.
+    b')' | b']' | b'}' | b'"' | b'\'' | b'`' => line_break,
+    b'>' => line_break && bytes[..prev].last() != Some(&b'='),
+    _ => false,
+  }
+}
+
+type Labels = Vec<(usize, usize)>;
+
+/// Source of the module as the parser understands it:
+/// - BigInt literal types like `10n` become number literal types, the `n` is
+///   replaced by a `.`, or a `0` after a `0x`, `0o` or `0b` prefix.
+/// - Labels of tuple elements like `[name: string, age?: number]` are
+///   replaced by spaces, leaving `[      string,      number]`.
+///
+/// Strings, comments, regular expressions and, if `jsx` is set, JSX
+/// elements are left as they are.
+pub fn rewrite(source_code: &str, jsx: bool) -> String {
+  let bytes = source_code.as_bytes();
+  let mut out = bytes.to_vec();
+  // Open brackets, with the ranges of labels found in the ones which may be
+  // tuples.
+  let mut brackets: Vec<(u8, Option<Labels>)> = vec![];
+  // Last byte of the previous token, and whether a line break follows it.
+  let mut prev: Option<usize> = None;
+  let mut line_break = false;
+
+  let mut i = 0;
+  while i < bytes.len() {
+    let byte = bytes[i];
+    if byte.is_ascii_whitespace() {
+      line_break |= byte == b'\n';
+      i += 1;
+      continue;
+    }
+    if let Some(end) = skip_non_code(bytes, i) {
+      if byte == b'/' {
+        line_break |= bytes[i..end].contains(&b'\n');
+      } else {
+        prev = Some(end - 1);
+        line_break = false;
+      }
+      i = end;
+      continue;
+    }
+    if starts_expression(bytes, prev) {
+      let end = match byte {
+        b'/' => Some(skip_regex(bytes, i)),
+        b'<' if jsx => skip_jsx(bytes, i),
+        _ => None,
+      };
+      if let Some(end) = end {
+        prev = Some(end - 1);
+        line_break = false;
+        i = end;
+        continue;
+      }
+    }
+
+    match byte {
+      b'[' => {
+        let innermost = brackets.last().map(|(bracket, _)| *bracket);
+        let labels = if starts_member(bytes, innermost, prev, line_break) {
+          None
+        } else {
+          Some(vec![])
+        };
+        brackets.push((byte, labels));
+      }
+      b'{' | b'(' => brackets.push((byte, None)),
+      b']' | b'}' | b')' => {
+        if let Some((b'[', Some(labels))) = brackets.pop() {
+          for (start, end) in labels {
+            for byte in &mut out[start..end] {
+              *byte = b' ';
+            }
+          }
+        }
+      }
+      _ => {}
+    }
+    if byte == b'[' || byte == b',' {
+      if let Some((b'[', Some(labels))) = brackets.last_mut() {
+        labels.extend(tuple_label(bytes, i + 1));
+      }
+    }
+    if byte.is_ascii_digit() && (i == 0 || !is_ident_byte(bytes[i - 1])) {
+      let mut end = i;
+      while end < bytes.len() && is_ident_byte(bytes[end]) {
+        end += 1;
+      }
+      // The parser only accepts BigInt literals as values, which can't be
+      // told apart from types here, so they are all rewritten.
+      if bytes[end - 1] == b'n' {
+        let is_decimal = end - i < 2 || !bytes[i + 1].is_ascii_alphabetic();
+        out[end - 1] = if is_decimal { b'.' } else { b'0' };
+      }
+      prev = Some(end - 1);
+      line_break = false;
+      i = end;
+      continue;
+    }
+    prev = Some(i);
+    line_break = false;
+    i += 1;
+  }
+
+  // Only ASCII bytes are replaced, so the source is still valid UTF-8.
+  String::from_utf8(out).unwrap()
+}
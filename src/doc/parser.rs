@@ -11,6 +11,8 @@ use swc_common::errors::HandlerFlags;
 use swc_common::SourceMap;
 use swc_common::Span;
 
+use crate::doc::Location;
+
 pub type SwcDiagnostics = Vec<Diagnostic>;
 
 #[derive(Clone, Default)]
@@ -57,9 +59,14 @@ impl DocParser {
     }
   }
 
+  pub fn location_for_span(&self, span: Span) -> Location {
+    Location::from_span(&self.source_map, span)
+  }
+
   pub fn js_doc_for_span(&self, span: Span) -> Option<String> {
     let comments = self.comments.take_leading_comments(span.lo())?;
-    let js_doc_comment = comments.iter().find(|comment| {
+    // The closest JSDoc block documents the declaration.
+    let js_doc_comment = comments.iter().rev().find(|comment| {
       comment.kind == CommentKind::Block && comment.text.starts_with('*')
     })?;
 
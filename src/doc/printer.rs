@@ -0,0 +1,1339 @@
+use crate::doc::compat::rsplit_once;
+use crate::doc::compat::split_once;
+use crate::doc::graph::RelationshipKind;
+use crate::doc::graph::SymbolGraph;
+use crate::doc::js_doc::JsDoc;
+use crate::doc::module_graph::ModuleGraph;
+use crate::doc::tags::group_by_category;
+use crate::doc::ts_type::MappedModifier;
+use crate::doc::ts_type::TsTypeDef;
+use crate::doc::ts_type::TsTypeDefKind;
+use crate::doc::ts_type::TsTypeLiteralDef;
+use crate::doc::ts_type::TsTypeParamDef;
+use crate::doc::usage::usage_stub;
+use crate::doc::Augmentation;
+use crate::doc::ClassDef;
+use crate::doc::DecoratorDef;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+use crate::doc::FunctionDef;
+use crate::doc::InterfaceCallSignatureDef;
+use crate::doc::InterfaceIndexSignatureDef;
+use crate::doc::InterfaceMethodDef;
+use crate::doc::InterfacePropertyDef;
+use crate::doc::Location;
+use crate::doc::ParamDef;
+use crate::doc::ParamKind;
+use crate::doc::Stability;
+
+#[derive(Debug, Default)]
+pub struct PrinterOptions {
+  /// Put each member of a type alias' top level union or intersection on its
+  /// own line, like prettier does.
+  pub wrap_types: bool,
+  /// Print variables and enum members as aligned columns of names, types and
+  /// the first line of their description.
+  pub columns: bool,
+  /// Print a minimal call of each function, which can seed an `@example`.
+  pub usage_stubs: bool,
+  /// Print at most this many top level symbols, namespace elements are
+  /// always printed along with their namespace.
+  pub limit: Option<usize>,
+  /// Page of `limit` symbols to print, starting at 0.
+  pub page: usize,
+  /// List symbols in the order they're declared in, instead of by kind and
+  /// name.
+  pub source_order: bool,
+}
+
+pub fn print(doc_nodes: &[DocNode], options: &PrinterOptions) -> String {
+  let mut out = String::new();
+  let limit = match options.limit {
+    Some(limit) => limit.max(1),
+    None => {
+      print_(&mut out, doc_nodes, 0, options);
+      return out;
+    }
+  };
+
+  // Pages follow the printed order, which puts categories together.
+  let ordered: Vec<&DocNode> =
+    group_by_category(&sort_nodes(doc_nodes, options))
+      .into_iter()
+      .flat_map(|(_, nodes)| nodes)
+      .collect();
+  let pages = ordered.chunks(limit).len();
+  let start = (options.page * limit).min(ordered.len());
+  let end = (start + limit).min(ordered.len());
+  print_sorted(&mut out, &ordered[start..end], 0, options);
+
+  if start == end && !ordered.is_empty() {
+    push_line(
+      &mut out,
+      0,
+      &format!(
+        "Page {} is empty, there are {} pages",
+        options.page + 1,
+        pages
+      ),
+    );
+  } else if end < ordered.len() {
+    push_line(
+      &mut out,
+      0,
+      &format!(
+        "{} more not shown, pass --page {} to see the next {}",
+        ordered.len() - end,
+        options.page + 2,
+        limit.min(ordered.len() - end)
+      ),
+    );
+  }
+  out
+}
+
+/// Detailed documentation of the symbol at `path`, with namespace elements
+/// addressed as `Ns.name`. Starts with the statement importing the symbol, or
+/// its outermost namespace, and ends with the symbols from `module_graph` it
+/// refers to.
+pub fn print_details(
+  doc_nodes: &[DocNode],
+  module_graph: &ModuleGraph,
+  path: &str,
+  options: &PrinterOptions,
+) -> Option<String> {
+  // `Class#member` picks a member of a class or interface.
+  let (path, member) = match split_once(path, "#") {
+    Some((path, member)) => (path, Some(member)),
+    None => (path, None),
+  };
+  let root = find_node(doc_nodes, path.split('.').next()?)?;
+  let node = find_path(doc_nodes, path)?;
+
+  let mut out = String::new();
+  if let Some(import_statement) = &root.import_statement {
+    push_line(&mut out, 0, import_statement);
+    out.push('\n');
+  }
+
+  if let Some(member) = member {
+    print_member_details(&mut out, node, member)?;
+    return Some(out);
+  }
+
+  if let Some(class_def) = &node.class_def {
+    print_decorators(&mut out, &class_def.decorators, 0);
+  }
+  print_signature(&mut out, node, 0, options);
+  if let Some(js_doc) = &node.js_doc {
+    print_jsdoc_details(&mut out, js_doc, 1);
+  }
+  if options.usage_stubs && node.kind == DocNodeKind::Function {
+    print_usage_stub(&mut out, node, 1);
+  }
+
+  match node.kind {
+    DocNodeKind::Class => {
+      let class_def = node.class_def.as_ref().unwrap();
+      for note in iteration_notes(class_def) {
+        out.push('\n');
+        push_line(&mut out, 1, note);
+      }
+      let members = class_def
+        .constructors
+        .iter()
+        .map(|ctor| (&ctor.snippet, &ctor.js_doc, &[][..]))
+        .chain(class_def.properties.iter().map(|prop| {
+          (&prop.snippet, &prop.js_doc, prop.decorators.as_slice())
+        }))
+        .chain(class_def.methods.iter().map(|method| {
+          (
+            &method.snippet,
+            &method.js_doc,
+            method.decorators.as_slice(),
+          )
+        }));
+      for (snippet, js_doc, decorators) in members {
+        out.push('\n');
+        print_decorators(&mut out, decorators, 1);
+        for line in snippet.lines() {
+          push_line(&mut out, 1, line);
+        }
+        if let Some(js_doc) = js_doc {
+          print_jsdoc(&mut out, js_doc, 2);
+        }
+      }
+    }
+    DocNodeKind::Enum => {
+      out.push('\n');
+      print_enum_member_columns(&mut out, node, 1);
+    }
+    DocNodeKind::Variable if node.interface_def.is_some() => {
+      print_interface_members(&mut out, node, 1);
+    }
+    DocNodeKind::Interface => {
+      print_interface_members(&mut out, node, 1);
+      let scope = rsplit_once(path, ".").map(|(scope, _)| scope).unwrap_or("");
+      let mut visited = vec![path.to_string()];
+      print_inherited_members(&mut out, doc_nodes, scope, node, &mut visited);
+    }
+    _ => {}
+  }
+  // Also covers namespaces merged into other declarations.
+  if let Some(namespace_def) = &node.namespace_def {
+    out.push('\n');
+    print_(&mut out, &namespace_def.elements, 1, options);
+  }
+  print_imported_references(&mut out, doc_nodes, module_graph, node, options);
+
+  Some(out)
+}
+
+/// Every member of the class or interface `node` named `name`, methods can
+/// be declared several times with different overloads.
+fn print_member_details(
+  out: &mut String,
+  node: &DocNode,
+  name: &str,
+) -> Option<()> {
+  let mut members: Vec<(String, &Option<String>, &Location)> = vec![];
+  if let Some(class_def) = &node.class_def {
+    for ctor in &class_def.constructors {
+      if name == "constructor" {
+        members.push((ctor.snippet.to_string(), &ctor.js_doc, &ctor.location));
+      }
+    }
+    for prop in &class_def.properties {
+      if prop.name == name {
+        members.push((prop.snippet.to_string(), &prop.js_doc, &prop.location));
+      }
+    }
+    for method in &class_def.methods {
+      if method.name == name {
+        let snippet = method.snippet.to_string();
+        members.push((snippet, &method.js_doc, &method.location));
+      }
+    }
+  }
+  if let Some(interface_def) = &node.interface_def {
+    for prop in &interface_def.properties {
+      if prop.name == name {
+        let signature = format_interface_property(prop);
+        members.push((signature, &prop.js_doc, &prop.location));
+      }
+    }
+    for method in &interface_def.methods {
+      if method.name == name {
+        let signature = format_interface_method(method);
+        members.push((signature, &method.js_doc, &method.location));
+      }
+    }
+  }
+  if members.is_empty() {
+    return None;
+  }
+
+  for (i, (signature, js_doc, location)) in members.into_iter().enumerate() {
+    if i > 0 {
+      out.push('\n');
+    }
+    for line in signature.lines() {
+      push_line(out, 0, line);
+    }
+    if let Some(js_doc) = js_doc {
+      print_jsdoc_details(out, js_doc, 1);
+    }
+    out.push('\n');
+    let defined_at = format!(
+      "Defined at {}:{}:{}",
+      location.filename,
+      location.line,
+      location.col + 1
+    );
+    push_line(out, 1, &defined_at);
+  }
+  Some(())
+}
+
+/// Symbols imported from other modules which `node` or its members refer to.
+fn print_imported_references(
+  out: &mut String,
+  doc_nodes: &[DocNode],
+  module_graph: &ModuleGraph,
+  node: &DocNode,
+  options: &PrinterOptions,
+) {
+  let graph = SymbolGraph::new(std::slice::from_ref(node));
+  let mut printed: Vec<&str> = vec![];
+  for relationship in &graph.relationships {
+    let name = relationship.target.as_str();
+    if relationship.kind != RelationshipKind::References
+      || !relationship.external
+      || printed.contains(&name)
+      || find_node(doc_nodes, name).is_some()
+    {
+      continue;
+    }
+    let import = match module_graph.find(name) {
+      Some(import) => import,
+      None => continue,
+    };
+    printed.push(name);
+
+    out.push('\n');
+    push_line(out, 1, &format!("Imported from {}:", import.specifier));
+    print_signature(out, &import.doc_node, 2, options);
+    if let Some(js_doc) = &import.doc_node.js_doc {
+      print_jsdoc(out, js_doc, 3);
+    }
+  }
+}
+
+fn find_node<'a>(doc_nodes: &'a [DocNode], name: &str) -> Option<&'a DocNode> {
+  doc_nodes.iter().find(|node| node.name == name)
+}
+
+/// Node at `path`, with namespace elements addressed as `Ns.name`.
+pub fn find_path<'a>(
+  doc_nodes: &'a [DocNode],
+  path: &str,
+) -> Option<&'a DocNode> {
+  let mut names = path.split('.');
+  let mut node = find_node(doc_nodes, names.next()?)?;
+  for name in names {
+    let namespace_def = node.namespace_def.as_ref()?;
+    node = find_node(&namespace_def.elements, name)?;
+  }
+  Some(node)
+}
+
+/// Path of the interface `name` refers to from within the namespace at
+/// `scope`, looking in the namespace and its parents before the top level.
+fn resolve_interface<'a>(
+  doc_nodes: &'a [DocNode],
+  scope: &str,
+  name: &str,
+) -> Option<(String, &'a DocNode)> {
+  let mut scope = scope;
+  loop {
+    let path = match scope {
+      "" => name.to_string(),
+      scope => format!("{}.{}", scope, name),
+    };
+    if let Some(node) = find_path(doc_nodes, &path) {
+      if node.kind == DocNodeKind::Interface {
+        return Some((path, node));
+      }
+    }
+    if scope.is_empty() {
+      return None;
+    }
+    scope = rsplit_once(scope, ".")
+      .map(|(scope, _)| scope)
+      .unwrap_or("");
+  }
+}
+
+fn print_interface_members(out: &mut String, node: &DocNode, indent: usize) {
+  for (signature, js_doc) in interface_members(node) {
+    out.push('\n');
+    push_line(out, indent, &signature);
+    if let Some(js_doc) = js_doc {
+      print_jsdoc(out, js_doc, indent + 1);
+    }
+  }
+}
+
+/// Members of the documented interfaces `node` extends, and the ones they
+/// extend in turn. `visited` has the paths of interfaces already printed.
+fn print_inherited_members(
+  out: &mut String,
+  doc_nodes: &[DocNode],
+  scope: &str,
+  node: &DocNode,
+  visited: &mut Vec<String>,
+) {
+  let interface_def = node.interface_def.as_ref().unwrap();
+  for extends in &interface_def.extends {
+    let name = extends.split('<').next().unwrap().trim();
+    let (path, parent) = match resolve_interface(doc_nodes, scope, name) {
+      Some(resolved) => resolved,
+      None => continue,
+    };
+    if visited.contains(&path) {
+      continue;
+    }
+    visited.push(path.to_string());
+
+    out.push('\n');
+    push_line(out, 1, &format!("Inherited from {}:", path));
+    print_interface_members(out, parent, 2);
+    let parent_scope = rsplit_once(&path, ".").map(|(scope, _)| scope);
+    let parent_scope = parent_scope.unwrap_or("");
+    print_inherited_members(out, doc_nodes, parent_scope, parent, visited);
+  }
+}
+
+fn kind_order(kind: &DocNodeKind) -> i64 {
+  match kind {
+    DocNodeKind::Function => 0,
+    DocNodeKind::Variable => 1,
+    DocNodeKind::Class => 2,
+    DocNodeKind::Enum => 3,
+    DocNodeKind::Interface => 4,
+    DocNodeKind::TypeAlias => 5,
+    DocNodeKind::Namespace => 6,
+  }
+}
+
+fn sort_nodes<'a>(
+  doc_nodes: &'a [DocNode],
+  options: &PrinterOptions,
+) -> Vec<&'a DocNode> {
+  let mut sorted: Vec<&DocNode> = doc_nodes.iter().collect();
+  if options.source_order {
+    sorted.sort_by_key(|node| node.index);
+    return sorted;
+  }
+  // The default export comes first.
+  sorted.sort_by(|a, b| {
+    b.is_default
+      .cmp(&a.is_default)
+      .then_with(|| kind_order(&a.kind).cmp(&kind_order(&b.kind)))
+      .then_with(|| a.name.cmp(&b.name))
+  });
+  sorted
+}
+
+fn print_(
+  out: &mut String,
+  doc_nodes: &[DocNode],
+  indent: usize,
+  options: &PrinterOptions,
+) {
+  print_sorted(out, &sort_nodes(doc_nodes, options), indent, options);
+}
+
+fn print_sorted(
+  out: &mut String,
+  sorted: &[&DocNode],
+  indent: usize,
+  options: &PrinterOptions,
+) {
+  for (category, nodes) in group_by_category(sorted) {
+    match category {
+      Some(category) => {
+        push_line(out, indent, category);
+        print_nodes(out, &nodes, indent + 1, options);
+      }
+      None => print_nodes(out, &nodes, indent, options),
+    }
+  }
+}
+
+fn print_nodes(
+  out: &mut String,
+  sorted: &[&DocNode],
+  indent: usize,
+  options: &PrinterOptions,
+) {
+  for (i, node) in sorted.iter().enumerate() {
+    if options.columns && node.kind == DocNodeKind::Variable {
+      // Consecutive variables are listed in a single table.
+      if i == 0 || sorted[i - 1].kind != DocNodeKind::Variable {
+        let run = sorted[i..]
+          .iter()
+          .take_while(|node| node.kind == DocNodeKind::Variable)
+          .count();
+        print_variable_columns(out, &sorted[i..i + run], indent);
+        out.push('\n');
+      }
+      continue;
+    }
+
+    print_signature(out, node, indent, options);
+    if options.columns && node.kind == DocNodeKind::Enum {
+      print_enum_member_columns(out, node, indent + 1);
+    }
+    if let Some(js_doc) = &node.js_doc {
+      print_jsdoc(out, js_doc, indent + 1);
+    }
+    print_custom_tags(out, node, indent + 1);
+    if options.usage_stubs && node.kind == DocNodeKind::Function {
+      print_usage_stub(out, node, indent + 1);
+    }
+    out.push('\n');
+
+    if let Some(namespace_def) = &node.namespace_def {
+      print_(out, &namespace_def.elements, indent + 1, options);
+    }
+  }
+}
+
+fn push_line(out: &mut String, indent: usize, line: &str) {
+  if !line.is_empty() {
+    for _ in 0..indent {
+      out.push_str("  ");
+    }
+  }
+  out.push_str(line);
+  out.push('\n');
+}
+
+/// Print `rows` with every column padded to its widest cell.
+fn push_columns(out: &mut String, indent: usize, rows: &[Vec<String>]) {
+  let mut widths: Vec<usize> = vec![];
+  for row in rows {
+    for (i, cell) in row.iter().enumerate() {
+      let width = cell.chars().count();
+      match widths.get_mut(i) {
+        Some(max) => *max = (*max).max(width),
+        None => widths.push(width),
+      }
+    }
+  }
+
+  for row in rows {
+    let line: Vec<String> = row
+      .iter()
+      .enumerate()
+      .map(|(i, cell)| format!("{:width$}", cell, width = widths[i]))
+      .collect();
+    push_line(out, indent, line.join("  ").trim_end());
+  }
+}
+
+fn first_description_line(js_doc: &Option<String>) -> String {
+  match js_doc {
+    Some(js_doc) => {
+      let js_doc = JsDoc::parse(js_doc);
+      js_doc.description.lines().next().unwrap_or("").to_string()
+    }
+    None => "".to_string(),
+  }
+}
+
+fn print_variable_columns(out: &mut String, nodes: &[&DocNode], indent: usize) {
+  use swc_ecma_ast::VarDeclKind;
+
+  let rows: Vec<Vec<String>> = nodes
+    .iter()
+    .map(|node| {
+      let variable_def = node.variable_def.as_ref().unwrap();
+      let kind = match variable_def.kind {
+        VarDeclKind::Const => "const",
+        VarDeclKind::Let => "let",
+        VarDeclKind::Var => "var",
+      };
+      let ts_type = match &variable_def.ts_type {
+        Some(ts_type) => render_ts_type(ts_type),
+        None => "".to_string(),
+      };
+      vec![
+        kind.to_string(),
+        node.name.to_string(),
+        ts_type,
+        first_description_line(&node.js_doc),
+      ]
+    })
+    .collect();
+  push_columns(out, indent, &rows);
+}
+
+fn print_enum_member_columns(out: &mut String, node: &DocNode, indent: usize) {
+  let enum_def = node.enum_def.as_ref().unwrap();
+  let rows: Vec<Vec<String>> = enum_def
+    .members
+    .iter()
+    .map(|member| {
+      let value = match (&member.value, &member.init) {
+        (Some(value), _) => format!("= {}", value),
+        (None, Some(init)) => format!("= {}", init),
+        (None, None) => "".to_string(),
+      };
+      vec![member.name.to_string(), value]
+    })
+    .collect();
+  push_columns(out, indent, &rows);
+}
+
+fn print_jsdoc(out: &mut String, js_doc: &str, indent: usize) {
+  let js_doc = JsDoc::parse(js_doc);
+  for line in js_doc.description.lines() {
+    push_line(out, indent, line);
+  }
+}
+
+/// Description of the module, printed as a header before its symbols.
+pub fn print_module_doc(js_doc: &str) -> String {
+  let mut out = String::new();
+  print_jsdoc(&mut out, js_doc, 0);
+  if !out.is_empty() {
+    out.push('\n');
+  }
+  out
+}
+
+/// Description followed by all tags.
+fn print_jsdoc_details(out: &mut String, js_doc: &str, indent: usize) {
+  let js_doc = JsDoc::parse(js_doc);
+  for line in js_doc.description.lines() {
+    push_line(out, indent, line);
+  }
+  for tag in &js_doc.tags {
+    out.push('\n');
+    for line in tag.text().lines() {
+      push_line(out, indent, line);
+    }
+  }
+}
+
+fn print_custom_tags(out: &mut String, node: &DocNode, indent: usize) {
+  for tag in &node.custom_tags {
+    let mut lines = tag.value.lines();
+    let first = lines.next().unwrap_or("");
+    push_line(out, indent, &format!("{}: {}", tag.label, first));
+    for line in lines {
+      push_line(out, indent + 1, line);
+    }
+  }
+}
+
+fn print_usage_stub(out: &mut String, node: &DocNode, indent: usize) {
+  push_line(out, indent, "Usage:");
+  push_line(out, indent + 1, &usage_stub(node));
+}
+
+fn print_signature(
+  out: &mut String,
+  node: &DocNode,
+  indent: usize,
+  options: &PrinterOptions,
+) {
+  let mut signature = format_signature(node, options);
+  // Public is the default, so only pre-release and internal symbols stand out.
+  match node.stability {
+    Some(Stability::Public) | None => {}
+    Some(stability) => {
+      signature.push_str(&format!(" [{}]", stability.as_str()));
+    }
+  }
+  for line in signature.lines() {
+    push_line(out, indent, line);
+  }
+}
+
+pub fn format_signature(node: &DocNode, options: &PrinterOptions) -> String {
+  let signature = match node.kind {
+    DocNodeKind::Function => format_function_signature(node),
+    DocNodeKind::Variable => format_variable_signature(node),
+    DocNodeKind::Class => format_class_signature(node),
+    DocNodeKind::Enum => format_enum_signature(node),
+    DocNodeKind::Interface => format_interface_signature(node),
+    DocNodeKind::TypeAlias => format_type_alias_signature(node, options),
+    DocNodeKind::Namespace => format_namespace_signature(node),
+  };
+  if !node.is_ambient {
+    return signature;
+  }
+  if node.kind == DocNodeKind::Function {
+    // Each overload goes on its own line.
+    let lines: Vec<String> = signature
+      .lines()
+      .map(|line| format!("declare {}", line))
+      .collect();
+    lines.join("\n")
+  } else {
+    format!("declare {}", signature)
+  }
+}
+
+fn format_enum_signature(node: &DocNode) -> String {
+  let enum_def = node.enum_def.as_ref().unwrap();
+  if enum_def.is_const {
+    format!("const enum {}", node.name)
+  } else {
+    format!("enum {}", node.name)
+  }
+}
+
+fn format_namespace_signature(node: &DocNode) -> String {
+  let namespace_def = node.namespace_def.as_ref().unwrap();
+  match namespace_def.augmentation {
+    Some(Augmentation::Global) => "declare global".to_string(),
+    Some(Augmentation::Module) => format!("declare module {:?}", node.name),
+    None => format!("namespace {}", node.name),
+  }
+}
+
+fn format_function_signature(node: &DocNode) -> String {
+  let function_def = node.function_def.as_ref().unwrap();
+  let mut signatures = vec![render_function_def(&node.name, function_def)];
+  for overload in &function_def.overloads {
+    signatures.push(render_function_def(&node.name, &overload.function_def));
+  }
+  // Each overload goes on its own line.
+  signatures.join("\n")
+}
+
+fn render_function_def(name: &str, function_def: &FunctionDef) -> String {
+  let return_type = match &function_def.return_type {
+    Some(return_type) => format!(": {}", render_ts_type(return_type)),
+    None => "".to_string(),
+  };
+
+  let keyword = match (function_def.is_async, function_def.is_generator) {
+    (false, false) => "function",
+    (false, true) => "function*",
+    (true, false) => "async function",
+    (true, true) => "async function*",
+  };
+
+  format!(
+    "{} {}{}({}){}",
+    keyword,
+    name,
+    render_type_params(&function_def.type_params),
+    render_params(&function_def.params),
+    return_type
+  )
+}
+
+fn format_decorator(decorator: &DecoratorDef) -> String {
+  match &decorator.args {
+    Some(args) => format!("@{}({})", decorator.name, args.join(", ")),
+    None => format!("@{}", decorator.name),
+  }
+}
+
+fn print_decorators(
+  out: &mut String,
+  decorators: &[DecoratorDef],
+  indent: usize,
+) {
+  for decorator in decorators {
+    push_line(out, indent, &format_decorator(decorator));
+  }
+}
+
+/// Notes on the ways instances of a class can be iterated.
+pub fn iteration_notes(class_def: &ClassDef) -> Vec<&'static str> {
+  let mut notes = vec![];
+  if class_def.is_iterable {
+    notes.push("Iterable with for...of.");
+  }
+  if class_def.is_async_iterable {
+    notes.push("Async iterable with for await...of.");
+  }
+  notes
+}
+
+fn format_class_signature(node: &DocNode) -> String {
+  let class_def = node.class_def.as_ref().unwrap();
+  let mut signature = format!(
+    "{}class {}{}",
+    if class_def.is_abstract {
+      "abstract "
+    } else {
+      ""
+    },
+    node.name,
+    render_type_params(&class_def.type_params)
+  );
+
+  if let Some(extends) = &class_def.extends {
+    signature.push_str(&format!(" extends {}", extends));
+    if !class_def.super_type_params.is_empty() {
+      let type_params: Vec<String> = class_def
+        .super_type_params
+        .iter()
+        .map(render_ts_type)
+        .collect();
+      signature.push_str(&format!("<{}>", type_params.join(", ")));
+    }
+  }
+  if !class_def.implements.is_empty() {
+    signature
+      .push_str(&format!(" implements {}", class_def.implements.join(", ")));
+  }
+
+  signature
+}
+
+fn format_interface_signature(node: &DocNode) -> String {
+  let interface_def = node.interface_def.as_ref().unwrap();
+  let mut signature = format!(
+    "interface {}{}",
+    node.name,
+    render_type_params(&interface_def.type_params)
+  );
+  if !interface_def.extends.is_empty() {
+    signature
+      .push_str(&format!(" extends {}", interface_def.extends.join(", ")));
+  }
+  signature
+}
+
+fn format_type_alias_signature(
+  node: &DocNode,
+  options: &PrinterOptions,
+) -> String {
+  let type_alias_def = node.type_alias_def.as_ref().unwrap();
+  let ts_type = &type_alias_def.ts_type;
+  let rendered = match (&ts_type.kind, options.wrap_types) {
+    (Some(TsTypeDefKind::Union), true) => render_wrapped(
+      render_ts_types(ts_type.union.as_ref().unwrap(), Precedence::Union),
+      "|",
+    ),
+    (Some(TsTypeDefKind::Intersection), true) => render_wrapped(
+      render_ts_types(
+        ts_type.intersection.as_ref().unwrap(),
+        Precedence::Intersection,
+      ),
+      "&",
+    ),
+    _ => format!(" {}", render_ts_type(ts_type)),
+  };
+
+  format!(
+    "type {}{} ={}",
+    node.name,
+    render_type_params(&type_alias_def.type_params),
+    rendered
+  )
+}
+
+/// Each of `ts_types` on its own line, led by `operator`.
+fn render_wrapped(ts_types: Vec<String>, operator: &str) -> String {
+  ts_types
+    .iter()
+    .map(|ts_type| format!("\n  {} {}", operator, ts_type))
+    .collect()
+}
+
+fn format_variable_signature(node: &DocNode) -> String {
+  use swc_ecma_ast::VarDeclKind;
+
+  let variable_def = node.variable_def.as_ref().unwrap();
+  let kind = match variable_def.kind {
+    VarDeclKind::Const => "const",
+    VarDeclKind::Let => "let",
+    VarDeclKind::Var => "var",
+  };
+
+  let mut signature = format!("{} {}", kind, node.name);
+  if let Some(members) = object_members(node) {
+    signature.push_str(&format!(": {}", members));
+    return signature;
+  }
+  if let Some(ts_type) = &variable_def.ts_type {
+    // The literal type of a constant repeats its value.
+    let is_literal = ts_type.kind == Some(TsTypeDefKind::Literal);
+    let is_const = variable_def.kind == VarDeclKind::Const;
+    if !(is_literal && is_const && variable_def.is_literal) {
+      signature.push_str(&format!(": {}", render_ts_type(ts_type)));
+    }
+  }
+  // Initializers spanning several lines would drown the signature.
+  let init = variable_def
+    .init
+    .as_ref()
+    .filter(|init| variable_def.is_literal || !init.contains('\n'));
+  if let Some(init) = init {
+    signature.push_str(&format!(" = {}", init));
+  }
+  signature
+}
+
+/// Members of the object literal a variable is initialized with on a single
+/// line, unless its type is declared as something else.
+pub fn object_members(node: &DocNode) -> Option<String> {
+  node.interface_def.as_ref()?;
+  let variable_def = node.variable_def.as_ref()?;
+  if let Some(ts_type) = &variable_def.ts_type {
+    if ts_type.kind != Some(TsTypeDefKind::TypeLiteral) {
+      return None;
+    }
+  }
+  let members: Vec<String> = interface_members(node)
+    .into_iter()
+    .map(|(signature, _)| signature)
+    .collect();
+  Some(format!("{{ {} }}", members.join("; ")))
+}
+
+fn render_return_type(ts_type: &Option<TsTypeDef>) -> String {
+  match ts_type {
+    Some(ts_type) => format!(": {}", render_ts_type(ts_type)),
+    None => "".to_string(),
+  }
+}
+
+fn property_signature(
+  readonly: bool,
+  name: &str,
+  optional: bool,
+  ts_type: &Option<TsTypeDef>,
+) -> String {
+  format!(
+    "{}{}{}{}",
+    if readonly { "readonly " } else { "" },
+    name,
+    if optional { "?" } else { "" },
+    render_return_type(ts_type)
+  )
+}
+
+fn method_signature(
+  name: &str,
+  optional: bool,
+  params: &[ParamDef],
+  return_type: &Option<TsTypeDef>,
+) -> String {
+  format!(
+    "{}{}({}){}",
+    name,
+    if optional { "?" } else { "" },
+    render_params(params),
+    render_return_type(return_type)
+  )
+}
+
+fn index_signature(
+  readonly: bool,
+  params: &[ParamDef],
+  ts_type: &Option<TsTypeDef>,
+) -> String {
+  format!(
+    "{}[{}]{}",
+    if readonly { "readonly " } else { "" },
+    render_params(params),
+    render_return_type(ts_type)
+  )
+}
+
+fn call_signature(
+  constructor: bool,
+  params: &[ParamDef],
+  ts_type: &Option<TsTypeDef>,
+) -> String {
+  format!(
+    "{}({}){}",
+    if constructor { "new " } else { "" },
+    render_params(params),
+    render_return_type(ts_type)
+  )
+}
+
+pub fn format_interface_property(prop: &InterfacePropertyDef) -> String {
+  property_signature(prop.readonly, &prop.name, prop.optional, &prop.ts_type)
+}
+
+pub fn format_interface_method(method: &InterfaceMethodDef) -> String {
+  method_signature(
+    &method.name,
+    method.optional,
+    &method.params,
+    &method.return_type,
+  )
+}
+
+pub fn format_index_signature(index: &InterfaceIndexSignatureDef) -> String {
+  index_signature(index.readonly, &index.params, &index.ts_type)
+}
+
+/// `constructor` tells whether `call` is a construct signature.
+pub fn format_call_signature(
+  call: &InterfaceCallSignatureDef,
+  constructor: bool,
+) -> String {
+  call_signature(constructor, &call.params, &call.ts_type)
+}
+
+/// Signatures of all members of an interface, in the order call and
+/// construct signatures, index signatures, properties and methods.
+fn interface_members(node: &DocNode) -> Vec<(String, &Option<String>)> {
+  let interface_def = node.interface_def.as_ref().unwrap();
+  let mut members = vec![];
+  for call in &interface_def.call_signatures {
+    members.push((format_call_signature(call, false), &call.js_doc));
+  }
+  for construct in &interface_def.construct_signatures {
+    members.push((format_call_signature(construct, true), &construct.js_doc));
+  }
+  for index in &interface_def.index_signatures {
+    members.push((format_index_signature(index), &index.js_doc));
+  }
+  for prop in &interface_def.properties {
+    members.push((format_interface_property(prop), &prop.js_doc));
+  }
+  for method in &interface_def.methods {
+    members.push((format_interface_method(method), &method.js_doc));
+  }
+  members
+}
+
+pub fn render_params(params: &[ParamDef]) -> String {
+  params
+    .iter()
+    .map(|param| {
+      let mut rendered = match param.kind {
+        ParamKind::Rest => format!("...{}", param.name),
+        ParamKind::Identifier
+        | ParamKind::Object
+        | ParamKind::Array
+        | ParamKind::This => param.name.to_string(),
+      };
+      if param.optional {
+        rendered.push('?');
+      }
+      if let Some(ts_type) = &param.ts_type {
+        rendered.push_str(&format!(": {}", render_ts_type(ts_type)));
+      }
+      if let Some(default) = &param.default {
+        rendered.push_str(&format!(" = {}", default));
+      }
+      rendered
+    })
+    .collect::<Vec<String>>()
+    .join(", ")
+}
+
+pub fn render_type_params(type_params: &[TsTypeParamDef]) -> String {
+  if type_params.is_empty() {
+    return "".to_string();
+  }
+
+  let type_params: Vec<String> = type_params
+    .iter()
+    .map(|type_param| {
+      let mut rendered = type_param.name.to_string();
+      if let Some(constraint) = &type_param.constraint {
+        rendered.push_str(&format!(" extends {}", render_ts_type(constraint)));
+      }
+      if let Some(default) = &type_param.default {
+        rendered.push_str(&format!(" = {}", render_ts_type(default)));
+      }
+      rendered
+    })
+    .collect();
+
+  format!("<{}>", type_params.join(", "))
+}
+
+/// How tightly a type binds, from loosest to tightest. A type has to be
+/// parenthesized when it appears in a position requiring a tighter binding,
+/// eg. a union as the element type of an array.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Precedence {
+  Conditional,
+  Function,
+  Union,
+  Intersection,
+  TypeOperator,
+  Postfix,
+  Primary,
+}
+
+fn precedence(ts_type: &TsTypeDef) -> Precedence {
+  match &ts_type.kind {
+    Some(TsTypeDefKind::Conditional) => Precedence::Conditional,
+    Some(TsTypeDefKind::Rest) => Precedence::Conditional,
+    Some(TsTypeDefKind::FnOrConstructor) => Precedence::Function,
+    Some(TsTypeDefKind::Union) => Precedence::Union,
+    Some(TsTypeDefKind::Intersection) => Precedence::Intersection,
+    Some(TsTypeDefKind::TypeOperator) => Precedence::TypeOperator,
+    Some(TsTypeDefKind::Infer) => Precedence::TypeOperator,
+    Some(TsTypeDefKind::Array) => Precedence::Postfix,
+    Some(TsTypeDefKind::IndexedAccess) => Precedence::Postfix,
+    Some(TsTypeDefKind::Parenthesized) => {
+      let inner = ts_type.parenthesized.as_ref().unwrap();
+      match inner.kind {
+        Some(_) => precedence(inner),
+        // Keeps its parentheses, see `render_ts_type`.
+        None => Precedence::Primary,
+      }
+    }
+    _ => Precedence::Primary,
+  }
+}
+
+fn render_ts_type_with(ts_type: &TsTypeDef, min: Precedence) -> String {
+  let rendered = render_ts_type(ts_type);
+  if precedence(ts_type) < min {
+    format!("({})", rendered)
+  } else {
+    rendered
+  }
+}
+
+/// Element of a tuple, with its label if it has one.
+fn render_tuple_element(element: &TsTypeDef) -> String {
+  let label = match &element.label {
+    Some(label) => label,
+    None => return render_ts_type_with(element, Precedence::Conditional),
+  };
+  match element.kind {
+    Some(TsTypeDefKind::Optional) => format!(
+      "{}?: {}",
+      label,
+      render_ts_type_with(
+        element.optional.as_ref().unwrap(),
+        Precedence::Conditional
+      )
+    ),
+    Some(TsTypeDefKind::Rest) => format!(
+      "...{}: {}",
+      label,
+      render_ts_type_with(
+        element.rest.as_ref().unwrap(),
+        Precedence::Conditional
+      )
+    ),
+    _ => format!(
+      "{}: {}",
+      label,
+      render_ts_type_with(element, Precedence::Conditional)
+    ),
+  }
+}
+
+fn render_ts_types(ts_types: &[TsTypeDef], min: Precedence) -> Vec<String> {
+  ts_types
+    .iter()
+    .map(|ts_type| render_ts_type_with(ts_type, min))
+    .collect()
+}
+
+/// Members of a type literal on a single line, in the same order as members
+/// of interfaces.
+fn render_type_literal(type_literal: &TsTypeLiteralDef) -> String {
+  let mut members = vec![];
+  for call in &type_literal.call_signatures {
+    members.push(call_signature(false, &call.params, &call.ts_type));
+  }
+  for construct in &type_literal.construct_signatures {
+    members.push(call_signature(true, &construct.params, &construct.ts_type));
+  }
+  for index in &type_literal.index_signatures {
+    members.push(index_signature(
+      index.readonly,
+      &index.params,
+      &index.ts_type,
+    ));
+  }
+  for prop in &type_literal.properties {
+    members.push(property_signature(
+      prop.readonly,
+      &prop.name,
+      prop.optional,
+      &prop.ts_type,
+    ));
+  }
+  for method in &type_literal.methods {
+    members.push(method_signature(
+      &method.name,
+      method.optional,
+      &method.params,
+      &method.return_type,
+    ));
+  }
+
+  if members.is_empty() {
+    "{}".to_string()
+  } else {
+    format!("{{ {} }}", members.join("; "))
+  }
+}
+
+fn render_mapped_modifier(modifier: &MappedModifier) -> &'static str {
+  match modifier {
+    MappedModifier::True => "",
+    MappedModifier::Plus => "+",
+    MappedModifier::Minus => "-",
+  }
+}
+
+/// Render a type from its structure, adding parentheses only where operator
+/// precedence requires them. Types which aren't understood yet fall back to
+/// their source text.
+pub fn render_ts_type(ts_type: &TsTypeDef) -> String {
+  use crate::doc::ts_type::LiteralDefKind;
+
+  let kind = match &ts_type.kind {
+    Some(kind) => kind,
+    None => return ts_type.repr.to_string(),
+  };
+
+  match kind {
+    TsTypeDefKind::Keyword => ts_type.keyword.as_ref().unwrap().to_string(),
+    TsTypeDefKind::Literal => {
+      let literal = ts_type.literal.as_ref().unwrap();
+      match literal.kind {
+        LiteralDefKind::Number => literal.number.unwrap().to_string(),
+        LiteralDefKind::String => {
+          serde_json::to_string(literal.string.as_ref().unwrap()).unwrap()
+        }
+        LiteralDefKind::Boolean => literal.boolean.unwrap().to_string(),
+        LiteralDefKind::BigInt => {
+          format!("{}n", literal.big_int.as_ref().unwrap())
+        }
+      }
+    }
+    TsTypeDefKind::TypeRef => {
+      let type_ref = ts_type.type_ref.as_ref().unwrap();
+      match &type_ref.type_params {
+        Some(type_params) => format!(
+          "{}<{}>",
+          type_ref.type_name,
+          render_ts_types(type_params, Precedence::Conditional).join(", ")
+        ),
+        None => type_ref.type_name.to_string(),
+      }
+    }
+    TsTypeDefKind::Union => {
+      render_ts_types(ts_type.union.as_ref().unwrap(), Precedence::Union)
+        .join(" | ")
+    }
+    TsTypeDefKind::Intersection => render_ts_types(
+      ts_type.intersection.as_ref().unwrap(),
+      Precedence::Intersection,
+    )
+    .join(" & "),
+    TsTypeDefKind::Array => format!(
+      "{}[]",
+      render_ts_type_with(ts_type.array.as_ref().unwrap(), Precedence::Postfix)
+    ),
+    TsTypeDefKind::Tuple => format!(
+      "[{}]",
+      ts_type
+        .tuple
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(render_tuple_element)
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+    TsTypeDefKind::TypeOperator => {
+      let type_operator = ts_type.type_operator.as_ref().unwrap();
+      format!(
+        "{} {}",
+        type_operator.operator,
+        render_ts_type_with(&type_operator.ts_type, Precedence::TypeOperator)
+      )
+    }
+    TsTypeDefKind::Parenthesized => {
+      let inner = ts_type.parenthesized.as_ref().unwrap();
+      match inner.kind {
+        Some(_) => render_ts_type(inner),
+        None => format!("({})", inner.repr),
+      }
+    }
+    TsTypeDefKind::Rest => format!(
+      "...{}",
+      render_ts_type_with(
+        ts_type.rest.as_ref().unwrap(),
+        Precedence::Conditional
+      )
+    ),
+    TsTypeDefKind::Optional => format!(
+      "{}?",
+      render_ts_type_with(
+        ts_type.optional.as_ref().unwrap(),
+        Precedence::Postfix
+      )
+    ),
+    TsTypeDefKind::TypeQuery => {
+      format!("typeof {}", ts_type.type_query.as_ref().unwrap())
+    }
+    TsTypeDefKind::This => "this".to_string(),
+    TsTypeDefKind::FnOrConstructor => {
+      let fn_or_constructor = ts_type.fn_or_constructor.as_ref().unwrap();
+      format!(
+        "{}{}({}) => {}",
+        if fn_or_constructor.constructor {
+          "new "
+        } else {
+          ""
+        },
+        render_type_params(&fn_or_constructor.type_params),
+        render_params(&fn_or_constructor.params),
+        render_ts_type(&fn_or_constructor.ts_type)
+      )
+    }
+    TsTypeDefKind::Conditional => {
+      let conditional = ts_type.conditional_type.as_ref().unwrap();
+      format!(
+        "{} extends {} ? {} : {}",
+        render_ts_type_with(&conditional.check_type, Precedence::Union),
+        render_ts_type_with(&conditional.extends_type, Precedence::Union),
+        render_ts_type(&conditional.true_type),
+        render_ts_type(&conditional.false_type)
+      )
+    }
+    TsTypeDefKind::IndexedAccess => {
+      let indexed_access = ts_type.indexed_access.as_ref().unwrap();
+      format!(
+        "{}[{}]",
+        render_ts_type_with(&indexed_access.obj_type, Precedence::Postfix),
+        render_ts_type(&indexed_access.index_type)
+      )
+    }
+    TsTypeDefKind::TypePredicate => {
+      let type_predicate = ts_type.type_predicate.as_ref().unwrap();
+      let mut rendered = type_predicate.param_name.to_string();
+      if type_predicate.asserts {
+        rendered = format!("asserts {}", rendered);
+      }
+      if let Some(ts_type) = &type_predicate.ts_type {
+        rendered.push_str(&format!(" is {}", render_ts_type(ts_type)));
+      }
+      rendered
+    }
+    TsTypeDefKind::Import => {
+      let import_type = ts_type.import_type.as_ref().unwrap();
+      let mut rendered = format!(
+        "import({})",
+        serde_json::to_string(&import_type.specifier).unwrap()
+      );
+      if let Some(qualifier) = &import_type.qualifier {
+        rendered.push_str(&format!(".{}", qualifier));
+      }
+      if let Some(type_params) = &import_type.type_params {
+        rendered.push_str(&format!(
+          "<{}>",
+          render_ts_types(type_params, Precedence::Conditional).join(", ")
+        ));
+      }
+      rendered
+    }
+    TsTypeDefKind::TypeLiteral => {
+      render_type_literal(ts_type.type_literal.as_ref().unwrap())
+    }
+    TsTypeDefKind::Infer => {
+      format!("infer {}", ts_type.infer.as_ref().unwrap())
+    }
+    TsTypeDefKind::Mapped => {
+      let mapped_type = ts_type.mapped_type.as_ref().unwrap();
+      let mut rendered = "{ ".to_string();
+      if let Some(readonly) = &mapped_type.readonly {
+        rendered
+          .push_str(&format!("{}readonly ", render_mapped_modifier(readonly)));
+      }
+      rendered.push_str(&format!("[{}", mapped_type.type_param));
+      if let Some(constraint) = &mapped_type.constraint {
+        rendered.push_str(&format!(" in {}", render_ts_type(constraint)));
+      }
+      rendered.push(']');
+      if let Some(optional) = &mapped_type.optional {
+        rendered.push_str(&format!("{}?", render_mapped_modifier(optional)));
+      }
+      if let Some(ts_type) = &mapped_type.ts_type {
+        rendered.push_str(&format!(": {}", render_ts_type(ts_type)));
+      }
+      rendered.push_str(" }");
+      rendered
+    }
+  }
+}
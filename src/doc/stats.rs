@@ -0,0 +1,150 @@
+use crate::doc::js_doc::JsDoc;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+
+/// How many of the largest types are listed.
+const LARGEST_TYPES: usize = 5;
+
+#[derive(Debug)]
+pub struct Stats {
+  pub exported: usize,
+  pub internal: usize,
+  pub kind_counts: Vec<(&'static str, usize)>,
+  pub documented: usize,
+  /// Average length of the descriptions of documented symbols, in
+  /// characters.
+  pub average_doc_length: usize,
+  /// Name, kind and number of lines of the largest classes, enums,
+  /// interfaces and type aliases.
+  pub largest_types: Vec<(String, &'static str, usize)>,
+}
+
+fn kind_name(kind: &DocNodeKind) -> &'static str {
+  match kind {
+    DocNodeKind::Function => "function",
+    DocNodeKind::Variable => "variable",
+    DocNodeKind::Class => "class",
+    DocNodeKind::Enum => "enum",
+    DocNodeKind::Interface => "interface",
+    DocNodeKind::TypeAlias => "type alias",
+    DocNodeKind::Namespace => "namespace",
+  }
+}
+
+fn collect_nodes<'a>(
+  doc_nodes: &'a [DocNode],
+  namespace: &str,
+  nodes: &mut Vec<(String, &'a DocNode)>,
+) {
+  for node in doc_nodes {
+    let name = format!("{}{}", namespace, node.name);
+    nodes.push((name.to_string(), node));
+    if let Some(namespace_def) = &node.namespace_def {
+      collect_nodes(&namespace_def.elements, &format!("{}.", name), nodes);
+    }
+  }
+}
+
+impl Stats {
+  /// Statistics of `doc_nodes`, including namespace elements, for a module
+  /// which also has `internal` declarations that aren't exported.
+  pub fn new(doc_nodes: &[DocNode], internal: usize) -> Stats {
+    let mut nodes = vec![];
+    collect_nodes(doc_nodes, "", &mut nodes);
+
+    let mut kind_counts: Vec<(&'static str, usize)> = vec![];
+    for (_, node) in &nodes {
+      let kind = kind_name(&node.kind);
+      match kind_counts.iter_mut().find(|(name, _)| *name == kind) {
+        Some((_, count)) => *count += 1,
+        None => kind_counts.push((kind, 1)),
+      }
+    }
+    let order = |kind: &str| {
+      [
+        DocNodeKind::Function,
+        DocNodeKind::Variable,
+        DocNodeKind::Class,
+        DocNodeKind::Enum,
+        DocNodeKind::Interface,
+        DocNodeKind::TypeAlias,
+        DocNodeKind::Namespace,
+      ]
+      .iter()
+      .position(|other| kind_name(other) == kind)
+    };
+    kind_counts.sort_by_key(|(kind, _)| order(kind));
+
+    let doc_lengths: Vec<usize> = nodes
+      .iter()
+      .filter_map(|(_, node)| node.js_doc.as_ref())
+      .map(|js_doc| JsDoc::parse(js_doc).description.chars().count())
+      .filter(|length| *length > 0)
+      .collect();
+    let average_doc_length = match doc_lengths.len() {
+      0 => 0,
+      count => doc_lengths.iter().sum::<usize>() / count,
+    };
+
+    let mut largest_types: Vec<(String, &'static str, usize)> = nodes
+      .iter()
+      .filter(|(_, node)| {
+        matches!(
+          node.kind,
+          DocNodeKind::Class
+            | DocNodeKind::Enum
+            | DocNodeKind::Interface
+            | DocNodeKind::TypeAlias
+        )
+      })
+      .map(|(name, node)| {
+        (
+          name.to_string(),
+          kind_name(&node.kind),
+          node.snippet.lines().count(),
+        )
+      })
+      .collect();
+    largest_types.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+    largest_types.truncate(LARGEST_TYPES);
+
+    Stats {
+      exported: nodes.len(),
+      internal,
+      kind_counts,
+      documented: doc_lengths.len(),
+      average_doc_length,
+      largest_types,
+    }
+  }
+
+  pub fn print(&self) -> String {
+    let mut out = format!(
+      "Symbols: {} exported, {} internal\n",
+      self.exported, self.internal
+    );
+    let width = self
+      .kind_counts
+      .iter()
+      .map(|(kind, _)| kind.len())
+      .max()
+      .unwrap_or(0);
+    for (kind, count) in &self.kind_counts {
+      out.push_str(&format!("  {:width$}  {}\n", kind, count, width = width));
+    }
+
+    out.push_str(&format!(
+      "\nDocumented: {} of {}, {} characters on average\n",
+      self.documented, self.exported, self.average_doc_length
+    ));
+
+    if !self.largest_types.is_empty() {
+      out.push_str("\nLargest types:\n");
+      for (name, kind, lines) in &self.largest_types {
+        out.push_str(&format!("  {} ({}, {} lines)\n", name, kind, lines));
+      }
+    }
+
+    out
+  }
+}
@@ -0,0 +1,134 @@
+use crate::doc::compat::split_once;
+use crate::doc::js_doc::JsDoc;
+use crate::doc::CustomTagDef;
+use crate::doc::DocNode;
+use crate::doc::Stability;
+
+/// Fill in the fields of `doc_nodes` which are derived from JSDoc tags,
+/// including namespace elements.
+pub fn set_tag_fields(doc_nodes: &mut [DocNode]) {
+  for node in doc_nodes {
+    if let Some(js_doc) = &node.js_doc {
+      let js_doc = JsDoc::parse(js_doc);
+      node.category = category(&js_doc);
+      node.stability = js_doc
+        .tags
+        .iter()
+        .find_map(|tag| Stability::from_tag(&tag.name));
+    }
+    if let Some(namespace_def) = node.namespace_def.as_mut() {
+      set_tag_fields(&mut namespace_def.elements);
+    }
+  }
+}
+
+/// Value of the first `@category` or `@group` tag.
+fn category(js_doc: &JsDoc) -> Option<String> {
+  js_doc
+    .tags
+    .iter()
+    .filter(|tag| tag.name == "category" || tag.name == "group")
+    .map(|tag| tag.value.trim())
+    .find(|category| !category.is_empty())
+    .map(|category| category.to_string())
+}
+
+/// Split `doc_nodes` by category, keeping their order. Uncategorized nodes
+/// come first, followed by categories in alphabetical order.
+pub fn group_by_category<'a>(
+  doc_nodes: &[&'a DocNode],
+) -> Vec<(Option<&'a str>, Vec<&'a DocNode>)> {
+  let mut groups: Vec<(Option<&'a str>, Vec<&'a DocNode>)> = vec![];
+  for node in doc_nodes {
+    let category = node.category.as_deref();
+    match groups.iter_mut().find(|(other, _)| *other == category) {
+      Some((_, nodes)) => nodes.push(node),
+      None => groups.push((category, vec![node])),
+    }
+  }
+  // `None` sorts before any category.
+  groups.sort_by(|a, b| a.0.cmp(&b.0));
+  groups
+}
+
+/// A JSDoc tag to keep in the doc model, configured as `name=Label`.
+#[derive(Debug)]
+pub struct CustomTag {
+  pub name: String,
+  pub label: String,
+}
+
+impl CustomTag {
+  /// Parse `name=Label`, the label defaults to the name.
+  pub fn parse(arg: &str) -> CustomTag {
+    let (name, label) = split_once(arg, "=").unwrap_or((arg, arg));
+    CustomTag {
+      name: name.trim_start_matches('@').to_string(),
+      label: label.to_string(),
+    }
+  }
+}
+
+/// Keep the `custom_tags` documented on each of `doc_nodes`.
+pub fn set_custom_tags(doc_nodes: &mut [DocNode], custom_tags: &[CustomTag]) {
+  if custom_tags.is_empty() {
+    return;
+  }
+
+  for node in doc_nodes {
+    if let Some(js_doc) = &node.js_doc {
+      let js_doc = JsDoc::parse(js_doc);
+      node.custom_tags = js_doc
+        .tags
+        .iter()
+        .filter_map(|tag| {
+          let custom_tag = custom_tags.iter().find(|c| c.name == tag.name)?;
+          Some(CustomTagDef {
+            name: custom_tag.name.to_string(),
+            label: custom_tag.label.to_string(),
+            value: tag.value.to_string(),
+          })
+        })
+        .collect();
+    }
+    if let Some(namespace_def) = node.namespace_def.as_mut() {
+      set_custom_tags(&mut namespace_def.elements, custom_tags);
+    }
+  }
+}
+
+/// Keep the symbols whose stability is one of `only`, if any are given, and
+/// not one of `exclude`. Symbols without a stability tag inherit the one of
+/// their namespace, top-level ones are public.
+pub fn filter_by_stability(
+  doc_nodes: Vec<DocNode>,
+  only: &[Stability],
+  exclude: &[Stability],
+) -> Vec<DocNode> {
+  filter_by_stability_(doc_nodes, Stability::Public, only, exclude)
+}
+
+fn filter_by_stability_(
+  doc_nodes: Vec<DocNode>,
+  inherited: Stability,
+  only: &[Stability],
+  exclude: &[Stability],
+) -> Vec<DocNode> {
+  doc_nodes
+    .into_iter()
+    .filter_map(|mut node| {
+      let stability = node.stability.unwrap_or(inherited);
+      if (!only.is_empty() && !only.contains(&stability))
+        || exclude.contains(&stability)
+      {
+        return None;
+      }
+      if let Some(namespace_def) = node.namespace_def.as_mut() {
+        let elements = std::mem::take(&mut namespace_def.elements);
+        namespace_def.elements =
+          filter_by_stability_(elements, stability, only, exclude);
+      }
+      Some(node)
+    })
+    .collect()
+}
@@ -0,0 +1,81 @@
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+use std::thread;
+
+use crate::doc::DocNode;
+
+/// Hook rewriting or annotating doc nodes between parsing and printing, eg.
+/// to apply conventions of an organization like renames or injected links.
+pub trait Transformer {
+  fn transform(&self, doc_nodes: Vec<DocNode>) -> Result<Vec<DocNode>, String>;
+}
+
+impl<F> Transformer for F
+where
+  F: Fn(Vec<DocNode>) -> Vec<DocNode>,
+{
+  fn transform(&self, doc_nodes: Vec<DocNode>) -> Result<Vec<DocNode>, String> {
+    Ok(self(doc_nodes))
+  }
+}
+
+/// Transformer running an external program, which gets the doc nodes as JSON
+/// on stdin and prints the transformed nodes as JSON on stdout.
+pub struct CommandTransformer {
+  pub command: String,
+}
+
+impl Transformer for CommandTransformer {
+  fn transform(&self, doc_nodes: Vec<DocNode>) -> Result<Vec<DocNode>, String> {
+    let mut words = self.command.split_whitespace();
+    let program = words
+      .next()
+      .ok_or_else(|| "Empty transformer command".to_string())?;
+    let mut child = Command::new(program)
+      .args(words)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .map_err(|err| format!("Failed to run {}: {}", self.command, err))?;
+
+    let input = serde_json::to_vec(&doc_nodes).unwrap();
+    // The input is written while the output is read, otherwise both sides
+    // block once the pipes are full.
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || stdin.write_all(&input));
+    let output = child
+      .wait_with_output()
+      .map_err(|err| format!("Failed to run {}: {}", self.command, err))?;
+    let written = writer.join().unwrap();
+    // A transformer which failed likely stopped reading its input, so its
+    // exit status is reported rather than the broken pipe.
+    if !output.status.success() {
+      return Err(format!(
+        "{} failed: {}",
+        self.command,
+        String::from_utf8_lossy(&output.stderr).trim_end()
+      ));
+    }
+    written.map_err(|err| {
+      format!("Failed to write doc nodes to {}: {}", self.command, err)
+    })?;
+
+    serde_json::from_slice(&output.stdout).map_err(|err| {
+      format!("{} printed invalid doc nodes: {}", self.command, err)
+    })
+  }
+}
+
+/// Run `transformers` one after the other.
+pub fn apply(
+  doc_nodes: Vec<DocNode>,
+  transformers: &[Box<dyn Transformer + '_>],
+) -> Result<Vec<DocNode>, String> {
+  transformers
+    .iter()
+    .try_fold(doc_nodes, |doc_nodes, transformer| {
+      transformer.transform(doc_nodes)
+    })
+}
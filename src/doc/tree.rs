@@ -0,0 +1,67 @@
+use crate::doc::printer::format_call_signature;
+use crate::doc::DocNode;
+
+struct TreeNode {
+  name: String,
+  children: Vec<TreeNode>,
+}
+
+fn tree_nodes(doc_nodes: &[DocNode]) -> Vec<TreeNode> {
+  doc_nodes
+    .iter()
+    .map(|node| {
+      let mut children = vec![];
+      let leaf = |name: &str| TreeNode {
+        name: name.to_string(),
+        children: vec![],
+      };
+      if let Some(class_def) = &node.class_def {
+        children.extend(class_def.constructors.iter().map(|c| leaf(&c.name)));
+        children.extend(class_def.properties.iter().map(|p| leaf(&p.name)));
+        children.extend(class_def.methods.iter().map(|m| leaf(&m.name)));
+      }
+      if let Some(interface_def) = &node.interface_def {
+        // Signatures have no name, so they're listed by their signature.
+        for call in &interface_def.call_signatures {
+          children.push(leaf(&format_call_signature(call, false)));
+        }
+        for construct in &interface_def.construct_signatures {
+          children.push(leaf(&format_call_signature(construct, true)));
+        }
+        children.extend(interface_def.properties.iter().map(|p| leaf(&p.name)));
+        children.extend(interface_def.methods.iter().map(|m| leaf(&m.name)));
+      }
+      if let Some(enum_def) = &node.enum_def {
+        children.extend(enum_def.members.iter().map(|m| leaf(&m.name)));
+      }
+      if let Some(namespace_def) = &node.namespace_def {
+        children = tree_nodes(&namespace_def.elements);
+      }
+      TreeNode {
+        name: node.name.to_string(),
+        children,
+      }
+    })
+    .collect()
+}
+
+fn print_children(children: &[TreeNode], prefix: &str, out: &mut String) {
+  for (i, child) in children.iter().enumerate() {
+    let last = i == children.len() - 1;
+    let (branch, indent) = if last {
+      ("└── ", "    ")
+    } else {
+      ("├── ", "│   ")
+    };
+    out.push_str(&format!("{}{}{}\n", prefix, branch, child.name));
+    print_children(&child.children, &format!("{}{}", prefix, indent), out);
+  }
+}
+
+/// Names of the symbols of the module, with namespace elements and class,
+/// interface and enum members drawn as branches below them.
+pub fn print_tree(module: &str, doc_nodes: &[DocNode]) -> String {
+  let mut out = format!("{}\n", module);
+  print_children(&tree_nodes(doc_nodes), "", &mut out);
+  out
+}
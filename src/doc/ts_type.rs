@@ -1,8 +1,16 @@
+use serde::Deserialize;
 use serde::Serialize;
+use swc_common::BytePos;
 use swc_common::SourceMap;
-use swc_ecma_ast;
+use swc_common::Span;
+use swc_common::Spanned;
+use swc_ecma_ast::TsType;
 use swc_ecma_ast::TsTypeAnn;
 
+use crate::doc::compat::strip_suffix;
+use crate::doc::ParamDef;
+use crate::doc::ParamKind;
+
 // pub enum TsType {
 //     TsKeywordType(TsKeywordType),
 //     TsThisType(TsThisType),
@@ -26,20 +34,1365 @@ use swc_ecma_ast::TsTypeAnn;
 //     TsImportType(TsImportType),
 // }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TsTypeDefKind {
+  Keyword,
+  Literal,
+  TypeRef,
+  Union,
+  Intersection,
+  Array,
+  Tuple,
+  TypeOperator,
+  Parenthesized,
+  Rest,
+  Optional,
+  TypeQuery,
+  This,
+  FnOrConstructor,
+  Conditional,
+  IndexedAccess,
+  TypePredicate,
+  Mapped,
+  Infer,
+  Import,
+  TypeLiteral,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LiteralDefKind {
+  Number,
+  String,
+  Boolean,
+  /// Like `10n`, the value is kept as decimal digits since it can exceed the
+  /// range of `f64`. Hexadecimal, octal and binary literals are converted.
+  BigInt,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteralDef {
+  pub kind: LiteralDefKind,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub number: Option<f64>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub string: Option<String>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub boolean: Option<bool>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub big_int: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsTypeRefDef {
+  pub type_name: String,
+  pub type_params: Option<Vec<TsTypeDef>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsTypeOperatorDef {
+  pub operator: String,
+  pub ts_type: TsTypeDef,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsFnOrConstructorDef {
+  pub constructor: bool,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub type_params: Vec<TsTypeParamDef>,
+  pub params: Vec<ParamDef>,
+  pub ts_type: TsTypeDef,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsConditionalDef {
+  pub check_type: TsTypeDef,
+  pub extends_type: TsTypeDef,
+  pub true_type: TsTypeDef,
+  pub false_type: TsTypeDef,
+}
+
+/// `param is Type` return type of a type guard, or an assertion signature
+/// like `asserts param` or `asserts param is Type`; `param` can be `this`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsTypePredicateDef {
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub asserts: bool,
+  pub param_name: String,
+  pub ts_type: Option<TsTypeDef>,
+}
+
+/// `readonly` or `?` modifier of a mapped type, `+` and `-` explicitly add
+/// or remove it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MappedModifier {
+  True,
+  Plus,
+  Minus,
+}
+
+/// Mapped type like `{ readonly [K in keyof T]?: T[K] }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsMappedTypeDef {
+  pub readonly: Option<MappedModifier>,
+  pub type_param: String,
+  pub constraint: Option<TsTypeDef>,
+  pub optional: Option<MappedModifier>,
+  pub ts_type: Option<TsTypeDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteralPropertyDef {
+  pub name: String,
+  pub ts_type: Option<TsTypeDef>,
+  pub readonly: bool,
+  pub optional: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteralMethodDef {
+  pub name: String,
+  pub params: Vec<ParamDef>,
+  pub return_type: Option<TsTypeDef>,
+  pub optional: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteralIndexSignatureDef {
+  pub readonly: bool,
+  pub params: Vec<ParamDef>,
+  pub ts_type: Option<TsTypeDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteralCallSignatureDef {
+  pub params: Vec<ParamDef>,
+  pub ts_type: Option<TsTypeDef>,
+}
+
+/// Object type like `{ a: string; b(): void }`, with the same kinds of
+/// members as an interface.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsTypeLiteralDef {
+  pub properties: Vec<LiteralPropertyDef>,
+  pub methods: Vec<LiteralMethodDef>,
+  pub index_signatures: Vec<LiteralIndexSignatureDef>,
+  pub call_signatures: Vec<LiteralCallSignatureDef>,
+  pub construct_signatures: Vec<LiteralCallSignatureDef>,
+}
+
+fn param_types(params: &[ParamDef]) -> impl Iterator<Item = &TsTypeDef> {
+  params.iter().filter_map(|param| param.ts_type.as_ref())
+}
+
+impl TsTypeLiteralDef {
+  /// Types of all members, including the types of their parameters, in the
+  /// order members are rendered.
+  fn member_types(&self) -> Vec<&TsTypeDef> {
+    let mut types = vec![];
+    for call in self
+      .call_signatures
+      .iter()
+      .chain(&self.construct_signatures)
+    {
+      types.extend(param_types(&call.params));
+      types.extend(call.ts_type.as_ref());
+    }
+    for index in &self.index_signatures {
+      types.extend(param_types(&index.params));
+      types.extend(index.ts_type.as_ref());
+    }
+    for prop in &self.properties {
+      types.extend(prop.ts_type.as_ref());
+    }
+    for method in &self.methods {
+      types.extend(param_types(&method.params));
+      types.extend(method.return_type.as_ref());
+    }
+    types
+  }
+}
+
+/// Import type like `import("./mod.ts").Foo<T>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsImportTypeDef {
+  pub specifier: String,
+  pub qualifier: Option<String>,
+  pub type_params: Option<Vec<TsTypeDef>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsIndexedAccessDef {
+  pub readonly: bool,
+  pub obj_type: TsTypeDef,
+  pub index_type: TsTypeDef,
+}
+
+/// `repr` always holds the source text of the type, `kind` and the matching
+/// field are only set for kinds of types that are understood.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TsTypeDef {
   pub repr: String,
-  // TODO: make this struct more conrete
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kind: Option<TsTypeDefKind>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub keyword: Option<String>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub literal: Option<LiteralDef>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub type_ref: Option<TsTypeRefDef>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub union: Option<Vec<TsTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub intersection: Option<Vec<TsTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub array: Option<Box<TsTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub tuple: Option<Vec<TsTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub type_operator: Option<Box<TsTypeOperatorDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub parenthesized: Option<Box<TsTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rest: Option<Box<TsTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub optional: Option<Box<TsTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub type_query: Option<String>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fn_or_constructor: Option<Box<TsFnOrConstructorDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub conditional_type: Option<Box<TsConditionalDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub indexed_access: Option<Box<TsIndexedAccessDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub type_predicate: Option<Box<TsTypePredicateDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub mapped_type: Option<Box<TsMappedTypeDef>>,
+
+  /// Name of the type parameter declared by `infer R`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub infer: Option<String>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub import_type: Option<Box<TsImportTypeDef>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub type_literal: Option<TsTypeLiteralDef>,
+
+  /// Label of a tuple element, eg. `name` in `[name: string]`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub label: Option<String>,
+}
+
+impl TsTypeDef {
+  /// Names of all types referenced in this type, in order of appearance, eg.
+  /// `Map` and `Key` for `Map<Key, string>`.
+  pub fn type_ref_names(&self) -> Vec<&str> {
+    let mut names = vec![];
+    self.collect_type_ref_names(&mut names);
+    names
+  }
+
+  fn collect_type_ref_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+    if let Some(type_ref) = &self.type_ref {
+      names.push(&type_ref.type_name);
+      for type_param in type_ref.type_params.iter().flatten() {
+        type_param.collect_type_ref_names(names);
+      }
+    }
+
+    let children = self
+      .union
+      .iter()
+      .flatten()
+      .chain(self.intersection.iter().flatten())
+      .chain(self.tuple.iter().flatten())
+      .chain(self.array.as_deref())
+      .chain(self.type_operator.as_ref().map(|op| &op.ts_type))
+      .chain(self.parenthesized.as_deref())
+      .chain(self.rest.as_deref())
+      .chain(self.optional.as_deref());
+    for child in children {
+      child.collect_type_ref_names(names);
+    }
+
+    if let Some(fn_or_constructor) = &self.fn_or_constructor {
+      for type_param in &fn_or_constructor.type_params {
+        let bounds = type_param.constraint.iter().chain(&type_param.default);
+        for ts_type in bounds {
+          ts_type.collect_type_ref_names(names);
+        }
+      }
+      for param in &fn_or_constructor.params {
+        if let Some(ts_type) = &param.ts_type {
+          ts_type.collect_type_ref_names(names);
+        }
+      }
+      fn_or_constructor.ts_type.collect_type_ref_names(names);
+    }
+    if let Some(conditional) = &self.conditional_type {
+      conditional.check_type.collect_type_ref_names(names);
+      conditional.extends_type.collect_type_ref_names(names);
+      conditional.true_type.collect_type_ref_names(names);
+      conditional.false_type.collect_type_ref_names(names);
+    }
+    if let Some(indexed_access) = &self.indexed_access {
+      indexed_access.obj_type.collect_type_ref_names(names);
+      indexed_access.index_type.collect_type_ref_names(names);
+    }
+    if let Some(type_predicate) = &self.type_predicate {
+      if let Some(ts_type) = &type_predicate.ts_type {
+        ts_type.collect_type_ref_names(names);
+      }
+    }
+    // The qualifier of an import type names a symbol of another module.
+    if let Some(import_type) = &self.import_type {
+      for type_param in import_type.type_params.iter().flatten() {
+        type_param.collect_type_ref_names(names);
+      }
+    }
+    if let Some(type_literal) = &self.type_literal {
+      for ts_type in type_literal.member_types() {
+        ts_type.collect_type_ref_names(names);
+      }
+    }
+    if let Some(mapped_type) = &self.mapped_type {
+      let children = mapped_type
+        .constraint
+        .iter()
+        .chain(mapped_type.ts_type.iter());
+      for child in children {
+        child.collect_type_ref_names(names);
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsTypeParamDef {
+  pub name: String,
+  pub constraint: Option<TsTypeDef>,
+  pub default: Option<TsTypeDef>,
+}
+
+pub fn ts_type_param_decl_to_defs(
+  source_map: &SourceMap,
+  type_params: Option<&swc_ecma_ast::TsTypeParamDecl>,
+) -> Vec<TsTypeParamDef> {
+  let type_params = match type_params {
+    Some(type_params) => &type_params.params,
+    None => return vec![],
+  };
+
+  type_params
+    .iter()
+    .map(|type_param| TsTypeParamDef {
+      name: type_param.name.sym.to_string(),
+      constraint: type_param
+        .constraint
+        .as_ref()
+        .map(|constraint| ts_type_to_def(source_map, constraint)),
+      default: type_param
+        .default
+        .as_ref()
+        .map(|default| ts_type_to_def(source_map, default)),
+    })
+    .collect()
+}
+
+pub fn ts_entity_name_to_name(
+  entity_name: &swc_ecma_ast::TsEntityName,
+) -> String {
+  use swc_ecma_ast::TsEntityName::*;
+
+  match entity_name {
+    Ident(ident) => ident.sym.to_string(),
+    TsQualifiedName(qualified_name) => format!(
+      "{}.{}",
+      ts_entity_name_to_name(&qualified_name.left),
+      qualified_name.right.sym
+    ),
+  }
+}
+
+fn keyword_to_str(kind: swc_ecma_ast::TsKeywordTypeKind) -> &'static str {
+  use swc_ecma_ast::TsKeywordTypeKind::*;
+
+  match kind {
+    TsAnyKeyword => "any",
+    TsUnknownKeyword => "unknown",
+    TsNumberKeyword => "number",
+    TsObjectKeyword => "object",
+    TsBooleanKeyword => "boolean",
+    TsBigIntKeyword => "bigint",
+    TsStringKeyword => "string",
+    TsSymbolKeyword => "symbol",
+    TsVoidKeyword => "void",
+    TsUndefinedKeyword => "undefined",
+    TsNullKeyword => "null",
+    TsNeverKeyword => "never",
+  }
+}
+
+fn ts_types_to_defs(
+  source_map: &SourceMap,
+  ts_types: &[Box<TsType>],
+) -> Vec<TsTypeDef> {
+  ts_types
+    .iter()
+    .map(|ts_type| ts_type_to_def(source_map, ts_type))
+    .collect()
+}
+
+/// Tuple element starting at `start`. Labels are newer than the parser, which
+/// reads them as spaces (see `newer_syntax`), so they're taken from the
+/// source in front of the type, eg. `age?:` in `age?: number`.
+fn tuple_element_def(
+  source_map: &SourceMap,
+  start: BytePos,
+  elem_type: &TsType,
+) -> TsTypeDef {
+  // Rest elements are labeled after the `...`.
+  let (label_start, labeled_type) = match elem_type {
+    TsType::TsRestType(rest_type) => {
+      (rest_type.span.lo() + BytePos(3), &*rest_type.type_ann)
+    }
+    _ => (start, elem_type),
+  };
+  let mut def = ts_type_to_def(source_map, elem_type);
+  let label_span =
+    Span::new(label_start, labeled_type.span().lo(), Default::default());
+  let label = match source_map.span_to_snippet(label_span) {
+    Ok(label) => label,
+    Err(_) => return def,
+  };
+  let label = label.trim().trim_start_matches(',').trim_start();
+  let label = match strip_suffix(label, ":") {
+    Some(label) => label.trim_end(),
+    None => return def,
+  };
+
+  let (name, optional) = match strip_suffix(label, "?") {
+    Some(name) => (name.trim_end(), true),
+    None => (label, false),
+  };
+  let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+  if name.is_empty() || !name.chars().all(is_ident) {
+    return def;
+  }
+  if optional {
+    def = TsTypeDef {
+      repr: def.repr.clone(),
+      kind: Some(TsTypeDefKind::Optional),
+      optional: Some(Box::new(def)),
+      ..Default::default()
+    };
+  }
+  def.label = Some(name.to_string());
+  def
+}
+
+fn ts_fn_or_constructor_to_def(
+  source_map: &SourceMap,
+  fn_or_constructor: &swc_ecma_ast::TsFnOrConstructorType,
+) -> TsFnOrConstructorDef {
+  use swc_ecma_ast::TsFnOrConstructorType::*;
+
+  let (constructor, params, type_params, type_ann) = match fn_or_constructor {
+    TsFnType(fn_type) => (
+      false,
+      &fn_type.params,
+      &fn_type.type_params,
+      &fn_type.type_ann,
+    ),
+    TsConstructorType(ctor_type) => (
+      true,
+      &ctor_type.params,
+      &ctor_type.type_params,
+      &ctor_type.type_ann,
+    ),
+  };
+
+  TsFnOrConstructorDef {
+    constructor,
+    type_params: ts_type_param_decl_to_defs(source_map, type_params.as_ref()),
+    params: params
+      .iter()
+      .map(|param| ts_fn_param_to_def(source_map, param))
+      .collect(),
+    ts_type: ts_type_ann_to_def(source_map, type_ann),
+  }
+}
+
+fn ident_param_kind(ident: &swc_ecma_ast::Ident) -> ParamKind {
+  if ident.sym == *"this" {
+    ParamKind::This
+  } else {
+    ParamKind::Identifier
+  }
+}
+
+/// Parameter of a signature, destructured parameters are named after their
+/// pattern.
+pub fn ts_fn_param_to_def(
+  source_map: &SourceMap,
+  param: &swc_ecma_ast::TsFnParam,
+) -> ParamDef {
+  use swc_ecma_ast::TsFnParam;
+
+  let (kind, name, type_ann, optional) = match param {
+    TsFnParam::Ident(ident) => (
+      ident_param_kind(ident),
+      ident.sym.to_string(),
+      &ident.type_ann,
+      ident.optional,
+    ),
+    TsFnParam::Rest(rest) => {
+      let arg = source_map.span_to_snippet(rest.arg.span()).unwrap();
+      (ParamKind::Rest, arg, &rest.type_ann, false)
+    }
+    TsFnParam::Array(array) => (
+      ParamKind::Array,
+      pattern_name(source_map, array.span),
+      &array.type_ann,
+      false,
+    ),
+    TsFnParam::Object(object) => (
+      ParamKind::Object,
+      pattern_name(source_map, object.span),
+      &object.type_ann,
+      false,
+    ),
+  };
+
+  ParamDef {
+    kind,
+    name,
+    ts_type: type_ann
+      .as_ref()
+      .map(|type_ann| ts_type_ann_to_def(source_map, type_ann)),
+    optional,
+    default: None,
+  }
+}
+
+/// Parameter of a function declaration.
+pub fn pat_to_param_def(
+  source_map: &SourceMap,
+  pat: &swc_ecma_ast::Pat,
+) -> ParamDef {
+  use swc_ecma_ast::Pat;
+
+  match pat {
+    Pat::Ident(ident) => ParamDef {
+      kind: ident_param_kind(ident),
+      name: ident.sym.to_string(),
+      ts_type: ident
+        .type_ann
+        .as_ref()
+        .map(|rt| ts_type_ann_to_def(source_map, rt)),
+      optional: ident.optional,
+      default: None,
+    },
+    Pat::Assign(assign) => {
+      let mut param = pat_to_param_def(source_map, &assign.left);
+      if param.ts_type.is_none() {
+        param.ts_type = assign
+          .type_ann
+          .as_ref()
+          .map(|rt| ts_type_ann_to_def(source_map, rt));
+      }
+      param.default =
+        Some(source_map.span_to_snippet(assign.right.span()).unwrap());
+      param
+    }
+    Pat::Rest(rest) => {
+      let mut param = pat_to_param_def(source_map, &rest.arg);
+      param.kind = ParamKind::Rest;
+      if param.ts_type.is_none() {
+        param.ts_type = rest
+          .type_ann
+          .as_ref()
+          .map(|rt| ts_type_ann_to_def(source_map, rt));
+      }
+      param
+    }
+    Pat::Object(object) => ParamDef {
+      kind: ParamKind::Object,
+      name: pattern_name(source_map, object.span),
+      ts_type: object
+        .type_ann
+        .as_ref()
+        .map(|rt| ts_type_ann_to_def(source_map, rt)),
+      optional: false,
+      default: None,
+    },
+    Pat::Array(array) => ParamDef {
+      kind: ParamKind::Array,
+      name: pattern_name(source_map, array.span),
+      ts_type: array
+        .type_ann
+        .as_ref()
+        .map(|rt| ts_type_ann_to_def(source_map, rt)),
+      optional: false,
+      default: None,
+    },
+    _ => ParamDef {
+      kind: ParamKind::Identifier,
+      name: source_map.span_to_snippet(pat.span()).unwrap(),
+      ts_type: None,
+      optional: false,
+      default: None,
+    },
+  }
+}
+
+/// Source of a destructuring pattern on a single line. The span of a pattern
+/// can include its type annotation, which starts after the bracket closing
+/// the pattern.
+fn pattern_name(source_map: &SourceMap, span: swc_common::Span) -> String {
+  let pattern = source_map.span_to_snippet(span).unwrap();
+  let mut depth = 0;
+  let mut end = pattern.len();
+  for (index, c) in pattern.char_indices() {
+    match c {
+      '[' | '{' => depth += 1,
+      ']' | '}' => depth -= 1,
+      _ => continue,
+    }
+    if depth == 0 {
+      end = index + 1;
+      break;
+    }
+  }
+  pattern[..end]
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+pub fn ts_type_to_def(source_map: &SourceMap, ts_type: &TsType) -> TsTypeDef {
+  use swc_ecma_ast::TsType::*;
+
+  let repr = source_map
+    .span_to_snippet(ts_type.span())
+    .expect("Type not found");
+
+  match ts_type {
+    TsKeywordType(keyword_type) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Keyword),
+      keyword: Some(keyword_to_str(keyword_type.kind).to_string()),
+      ..Default::default()
+    },
+    TsLitType(lit_type) => {
+      use swc_ecma_ast::TsLit;
+
+      let literal = match &lit_type.lit {
+        TsLit::Number(num) => match big_int_digits(source_map, num.span) {
+          Some(digits) => LiteralDef {
+            kind: LiteralDefKind::BigInt,
+            number: None,
+            string: None,
+            boolean: None,
+            // The span of a negative literal type leaves out the minus.
+            big_int: Some(if num.value.is_sign_negative() {
+              format!("-{}", digits)
+            } else {
+              digits
+            }),
+          },
+          None => LiteralDef {
+            kind: LiteralDefKind::Number,
+            number: Some(num.value),
+            string: None,
+            boolean: None,
+            big_int: None,
+          },
+        },
+        TsLit::Str(str_) => LiteralDef {
+          kind: LiteralDefKind::String,
+          number: None,
+          string: Some(str_.value.to_string()),
+          boolean: None,
+          big_int: None,
+        },
+        TsLit::Bool(bool_) => LiteralDef {
+          kind: LiteralDefKind::Boolean,
+          number: None,
+          string: None,
+          boolean: Some(bool_.value),
+          big_int: None,
+        },
+      };
+
+      TsTypeDef {
+        repr,
+        kind: Some(TsTypeDefKind::Literal),
+        literal: Some(literal),
+        ..Default::default()
+      }
+    }
+    TsTypeRef(type_ref) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::TypeRef),
+      type_ref: Some(TsTypeRefDef {
+        type_name: ts_entity_name_to_name(&type_ref.type_name),
+        type_params: type_ref
+          .type_params
+          .as_ref()
+          .map(|type_params| ts_types_to_defs(source_map, &type_params.params)),
+      }),
+      ..Default::default()
+    },
+    TsUnionOrIntersectionType(union_or_intersection) => {
+      use swc_ecma_ast::TsUnionOrIntersectionType::*;
+
+      match union_or_intersection {
+        TsUnionType(union) => TsTypeDef {
+          repr,
+          kind: Some(TsTypeDefKind::Union),
+          union: Some(ts_types_to_defs(source_map, &union.types)),
+          ..Default::default()
+        },
+        TsIntersectionType(intersection) => TsTypeDef {
+          repr,
+          kind: Some(TsTypeDefKind::Intersection),
+          intersection: Some(ts_types_to_defs(source_map, &intersection.types)),
+          ..Default::default()
+        },
+      }
+    }
+    TsArrayType(array_type) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Array),
+      array: Some(Box::new(ts_type_to_def(source_map, &array_type.elem_type))),
+      ..Default::default()
+    },
+    TsTupleType(tuple_type) => {
+      let mut elements = vec![];
+      // Each element starts after the `[` or the previous element.
+      let mut start = tuple_type.span.lo() + BytePos(1);
+      for elem_type in &tuple_type.elem_types {
+        elements.push(tuple_element_def(source_map, start, elem_type));
+        start = elem_type.span().hi();
+      }
+      TsTypeDef {
+        repr,
+        kind: Some(TsTypeDefKind::Tuple),
+        tuple: Some(elements),
+        ..Default::default()
+      }
+    }
+    TsTypeOperator(type_operator) => {
+      use swc_ecma_ast::TsTypeOperatorOp::*;
+
+      let operator = match type_operator.op {
+        KeyOf => "keyof",
+        Unique => "unique",
+        ReadOnly => "readonly",
+      };
+
+      TsTypeDef {
+        repr,
+        kind: Some(TsTypeDefKind::TypeOperator),
+        type_operator: Some(Box::new(TsTypeOperatorDef {
+          operator: operator.to_string(),
+          ts_type: ts_type_to_def(source_map, &type_operator.type_ann),
+        })),
+        ..Default::default()
+      }
+    }
+    TsParenthesizedType(parenthesized) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Parenthesized),
+      parenthesized: Some(Box::new(ts_type_to_def(
+        source_map,
+        &parenthesized.type_ann,
+      ))),
+      ..Default::default()
+    },
+    TsRestType(rest_type) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Rest),
+      rest: Some(Box::new(ts_type_to_def(source_map, &rest_type.type_ann))),
+      ..Default::default()
+    },
+    TsOptionalType(optional_type) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Optional),
+      optional: Some(Box::new(ts_type_to_def(
+        source_map,
+        &optional_type.type_ann,
+      ))),
+      ..Default::default()
+    },
+    TsTypeQuery(type_query) => match &type_query.expr_name {
+      swc_ecma_ast::TsTypeQueryExpr::TsEntityName(entity_name) => TsTypeDef {
+        repr,
+        kind: Some(TsTypeDefKind::TypeQuery),
+        type_query: Some(ts_entity_name_to_name(entity_name)),
+        ..Default::default()
+      },
+      _ => TsTypeDef {
+        repr,
+        ..Default::default()
+      },
+    },
+    TsThisType(_) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::This),
+      ..Default::default()
+    },
+    TsFnOrConstructorType(fn_or_constructor) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::FnOrConstructor),
+      fn_or_constructor: Some(Box::new(ts_fn_or_constructor_to_def(
+        source_map,
+        fn_or_constructor,
+      ))),
+      ..Default::default()
+    },
+    TsConditionalType(conditional) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Conditional),
+      conditional_type: Some(Box::new(TsConditionalDef {
+        check_type: ts_type_to_def(source_map, &conditional.check_type),
+        extends_type: ts_type_to_def(source_map, &conditional.extends_type),
+        true_type: ts_type_to_def(source_map, &conditional.true_type),
+        false_type: ts_type_to_def(source_map, &conditional.false_type),
+      })),
+      ..Default::default()
+    },
+    TsIndexedAccessType(indexed_access) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::IndexedAccess),
+      indexed_access: Some(Box::new(TsIndexedAccessDef {
+        readonly: indexed_access.readonly,
+        obj_type: ts_type_to_def(source_map, &indexed_access.obj_type),
+        index_type: ts_type_to_def(source_map, &indexed_access.index_type),
+      })),
+      ..Default::default()
+    },
+    TsTypePredicate(type_predicate) => {
+      let param_name = match &type_predicate.param_name {
+        swc_ecma_ast::TsThisTypeOrIdent::TsThisType(_) => "this".to_string(),
+        swc_ecma_ast::TsThisTypeOrIdent::Ident(ident) => ident.sym.to_string(),
+      };
+      TsTypeDef {
+        repr,
+        kind: Some(TsTypeDefKind::TypePredicate),
+        type_predicate: Some(Box::new(TsTypePredicateDef {
+          asserts: type_predicate.asserts,
+          param_name,
+          ts_type: type_predicate
+            .type_ann
+            .as_ref()
+            .map(|type_ann| ts_type_ann_to_def(source_map, type_ann)),
+        })),
+        ..Default::default()
+      }
+    }
+    TsMappedType(mapped_type) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Mapped),
+      mapped_type: Some(Box::new(TsMappedTypeDef {
+        readonly: mapped_type.readonly.map(mapped_modifier),
+        type_param: mapped_type.type_param.name.sym.to_string(),
+        constraint: mapped_type
+          .type_param
+          .constraint
+          .as_ref()
+          .map(|constraint| ts_type_to_def(source_map, constraint)),
+        optional: mapped_type.optional.map(mapped_modifier),
+        ts_type: mapped_type
+          .type_ann
+          .as_ref()
+          .map(|type_ann| ts_type_to_def(source_map, type_ann)),
+      })),
+      ..Default::default()
+    },
+    TsInferType(infer) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Infer),
+      infer: Some(infer.type_param.name.sym.to_string()),
+      ..Default::default()
+    },
+    TsImportType(import_type) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::Import),
+      import_type: Some(Box::new(TsImportTypeDef {
+        specifier: import_type.arg.value.to_string(),
+        qualifier: import_type.qualifier.as_ref().map(ts_entity_name_to_name),
+        type_params: import_type
+          .type_args
+          .as_ref()
+          .map(|type_args| ts_types_to_defs(source_map, &type_args.params)),
+      })),
+      ..Default::default()
+    },
+    TsTypeLit(type_lit) => TsTypeDef {
+      repr,
+      kind: Some(TsTypeDefKind::TypeLiteral),
+      type_literal: Some(ts_type_lit_to_def(source_map, type_lit)),
+      ..Default::default()
+    },
+  }
+}
+
+/// Name of a member declared with a computed key, the value of literal keys
+/// like `["foo"]` or else the source of the key in brackets, eg.
+/// `[Symbol.iterator]`.
+pub fn computed_name(
+  source_map: &SourceMap,
+  key: &swc_ecma_ast::Expr,
+) -> String {
+  use swc_ecma_ast::Expr;
+  use swc_ecma_ast::Lit;
+  match key {
+    Expr::Lit(Lit::Str(str_)) => str_.value.to_string(),
+    Expr::Lit(Lit::Num(num)) => num.value.to_string(),
+    Expr::Paren(paren) => computed_name(source_map, &paren.expr),
+    key => format!("[{}]", source_map.span_to_snippet(key.span()).unwrap()),
+  }
+}
+
+/// Name of a property or method signature of an interface or type literal.
+pub fn type_element_name(
+  source_map: &SourceMap,
+  key: &swc_ecma_ast::Expr,
+  computed: bool,
+) -> String {
+  match key {
+    swc_ecma_ast::Expr::Ident(ident) if !computed => ident.sym.to_string(),
+    key => computed_name(source_map, key),
+  }
+}
+
+fn ts_type_lit_to_def(
+  source_map: &SourceMap,
+  type_lit: &swc_ecma_ast::TsTypeLit,
+) -> TsTypeLiteralDef {
+  use swc_ecma_ast::TsTypeElement::*;
+
+  let params = |params: &[swc_ecma_ast::TsFnParam]| {
+    params
+      .iter()
+      .map(|param| ts_fn_param_to_def(source_map, param))
+      .collect()
+  };
+  let ts_type = |type_ann: &Option<TsTypeAnn>| {
+    type_ann
+      .as_ref()
+      .map(|type_ann| ts_type_ann_to_def(source_map, type_ann))
+  };
+
+  let mut type_literal = TsTypeLiteralDef::default();
+  for type_element in &type_lit.members {
+    match type_element {
+      TsPropertySignature(prop) => {
+        type_literal.properties.push(LiteralPropertyDef {
+          name: type_element_name(source_map, &prop.key, prop.computed),
+          ts_type: ts_type(&prop.type_ann),
+          readonly: prop.readonly,
+          optional: prop.optional,
+        });
+      }
+      TsMethodSignature(method) => {
+        type_literal.methods.push(LiteralMethodDef {
+          name: type_element_name(source_map, &method.key, method.computed),
+          params: params(&method.params),
+          return_type: ts_type(&method.type_ann),
+          optional: method.optional,
+        });
+      }
+      TsIndexSignature(index) => {
+        type_literal
+          .index_signatures
+          .push(LiteralIndexSignatureDef {
+            readonly: index.readonly,
+            params: params(&index.params),
+            ts_type: ts_type(&index.type_ann),
+          });
+      }
+      TsCallSignatureDecl(call) => {
+        type_literal.call_signatures.push(LiteralCallSignatureDef {
+          params: params(&call.params),
+          ts_type: ts_type(&call.type_ann),
+        });
+      }
+      TsConstructSignatureDecl(construct) => {
+        type_literal
+          .construct_signatures
+          .push(LiteralCallSignatureDef {
+            params: params(&construct.params),
+            ts_type: ts_type(&construct.type_ann),
+          });
+      }
+    }
+  }
+  type_literal
+}
+
+fn mapped_modifier(modifier: swc_ecma_ast::TruePlusMinus) -> MappedModifier {
+  use swc_ecma_ast::TruePlusMinus;
+
+  match modifier {
+    TruePlusMinus::True => MappedModifier::True,
+    TruePlusMinus::Plus => MappedModifier::Plus,
+    TruePlusMinus::Minus => MappedModifier::Minus,
+  }
 }
 
 pub fn ts_type_ann_to_def(
   source_map: &SourceMap,
   type_ann: &TsTypeAnn,
 ) -> TsTypeDef {
-  let repr = source_map
-    .span_to_snippet(type_ann.span)
-    .expect("Class prop type not found");
-  let repr = repr.trim_start_matches(':').trim_start().to_string();
+  ts_type_to_def(source_map, &type_ann.type_ann)
+}
+
+fn keyword_def(keyword: &str) -> TsTypeDef {
+  TsTypeDef {
+    repr: keyword.to_string(),
+    kind: Some(TsTypeDefKind::Keyword),
+    keyword: Some(keyword.to_string()),
+    ..Default::default()
+  }
+}
+
+fn literal_def(repr: String, literal: LiteralDef) -> TsTypeDef {
+  TsTypeDef {
+    repr,
+    kind: Some(TsTypeDefKind::Literal),
+    literal: Some(literal),
+    ..Default::default()
+  }
+}
 
-  TsTypeDef { repr }
+fn string_literal_def(value: &str) -> TsTypeDef {
+  literal_def(
+    serde_json::to_string(value).unwrap(),
+    LiteralDef {
+      kind: LiteralDefKind::String,
+      number: None,
+      string: Some(value.to_string()),
+      boolean: None,
+      big_int: None,
+    },
+  )
+}
+
+fn big_int_literal_def(digits: String) -> TsTypeDef {
+  literal_def(
+    format!("{}n", digits),
+    LiteralDef {
+      kind: LiteralDefKind::BigInt,
+      number: None,
+      string: None,
+      boolean: None,
+      big_int: Some(digits),
+    },
+  )
+}
+
+/// Decimal digits of the BigInt literal at `span`, which is parsed as a
+/// number with the `n` suffix rewritten away (see `newer_syntax`).
+fn big_int_digits(source_map: &SourceMap, span: Span) -> Option<String> {
+  let literal = source_map.span_to_snippet(span).ok()?;
+  strip_suffix(&literal, "n").map(to_decimal)
+}
+
+/// Convert the digits of a hexadecimal, octal, binary or decimal integer
+/// literal to decimal, without limiting its size.
+fn to_decimal(literal: &str) -> String {
+  let literal = literal.replace('_', "");
+  let (radix, digits) = match literal.get(..2) {
+    Some("0x") | Some("0X") => (16, &literal[2..]),
+    Some("0o") | Some("0O") => (8, &literal[2..]),
+    Some("0b") | Some("0B") => (2, &literal[2..]),
+    _ => return literal,
+  };
+
+  // Decimal digits, least significant first.
+  let mut decimal: Vec<u32> = vec![0];
+  for digit in digits.chars().filter_map(|c| c.to_digit(radix)) {
+    let mut carry = digit;
+    for decimal_digit in &mut decimal {
+      let value = *decimal_digit * radix + carry;
+      *decimal_digit = value % 10;
+      carry = value / 10;
+    }
+    while carry > 0 {
+      decimal.push(carry % 10);
+      carry /= 10;
+    }
+  }
+  while decimal.len() > 1 && decimal.last() == Some(&0) {
+    decimal.pop();
+  }
+  decimal
+    .iter()
+    .rev()
+    .map(|digit| std::char::from_digit(*digit, 10).unwrap())
+    .collect()
+}
+
+fn number_literal_def(value: f64) -> TsTypeDef {
+  literal_def(
+    value.to_string(),
+    LiteralDef {
+      kind: LiteralDefKind::Number,
+      number: Some(value),
+      string: None,
+      boolean: None,
+      big_int: None,
+    },
+  )
+}
+
+/// Type of a simple initializer expression, the way TypeScript would infer
+/// it. Primitive literals keep their literal type unless `widen` is set,
+/// as for `let` declarations and values nested in arrays and objects.
+pub fn infer_expr_type(
+  source_map: &SourceMap,
+  expr: &swc_ecma_ast::Expr,
+  widen: bool,
+) -> Option<TsTypeDef> {
+  use swc_ecma_ast::Expr;
+  use swc_ecma_ast::Lit;
+
+  match expr {
+    Expr::Lit(lit) => match lit {
+      Lit::Str(_) if widen => Some(keyword_def("string")),
+      Lit::Str(str_) => Some(string_literal_def(&str_.value)),
+      Lit::Num(num) => match big_int_digits(source_map, num.span) {
+        Some(_) if widen => Some(keyword_def("bigint")),
+        Some(digits) => Some(big_int_literal_def(digits)),
+        None if widen => Some(keyword_def("number")),
+        None => Some(number_literal_def(num.value)),
+      },
+      Lit::Bool(_) if widen => Some(keyword_def("boolean")),
+      Lit::Bool(bool_) => Some(literal_def(
+        bool_.value.to_string(),
+        LiteralDef {
+          kind: LiteralDefKind::Boolean,
+          number: None,
+          string: None,
+          boolean: Some(bool_.value),
+          big_int: None,
+        },
+      )),
+      Lit::Null(_) => Some(keyword_def("null")),
+      Lit::BigInt(_) if widen => Some(keyword_def("bigint")),
+      Lit::BigInt(big_int) => {
+        Some(big_int_literal_def(big_int.value.to_string()))
+      }
+      Lit::Regex(_) => Some(type_ref_def("RegExp".to_string(), None)),
+      Lit::JSXText(_) => None,
+    },
+    Expr::Tpl(tpl) => match tpl.quasis.first() {
+      Some(quasi) if !widen && tpl.exprs.is_empty() => {
+        Some(string_literal_def(&quasi.raw.value))
+      }
+      _ => Some(keyword_def("string")),
+    },
+    Expr::Unary(unary) => match (unary.op, &*unary.arg) {
+      (swc_ecma_ast::UnaryOp::Minus, Expr::Lit(Lit::Num(num))) => {
+        match big_int_digits(source_map, num.span) {
+          Some(_) if widen => Some(keyword_def("bigint")),
+          Some(digits) => Some(big_int_literal_def(format!("-{}", digits))),
+          None if widen => Some(keyword_def("number")),
+          None => Some(number_literal_def(-num.value)),
+        }
+      }
+      (swc_ecma_ast::UnaryOp::Minus, _) | (swc_ecma_ast::UnaryOp::Plus, _) => {
+        Some(keyword_def("number"))
+      }
+      (swc_ecma_ast::UnaryOp::Bang, _) => Some(keyword_def("boolean")),
+      (swc_ecma_ast::UnaryOp::TypeOf, _) => Some(keyword_def("string")),
+      (swc_ecma_ast::UnaryOp::Void, _) => Some(keyword_def("undefined")),
+      _ => None,
+    },
+    Expr::Paren(paren) => infer_expr_type(source_map, &paren.expr, widen),
+    Expr::TsAs(as_expr) => Some(ts_type_to_def(source_map, &as_expr.type_ann)),
+    Expr::TsTypeAssertion(assertion) => {
+      Some(ts_type_to_def(source_map, &assertion.type_ann))
+    }
+    Expr::New(new_expr) => {
+      let name = match &*new_expr.callee {
+        Expr::Ident(ident) => ident.sym.to_string(),
+        _ => return None,
+      };
+      let type_params = new_expr
+        .type_args
+        .as_ref()
+        .map(|type_args| ts_types_to_defs(source_map, &type_args.params));
+      Some(type_ref_def(name, type_params))
+    }
+    Expr::Array(array) => {
+      let mut elem_types: Vec<TsTypeDef> = vec![];
+      for elem in &array.elems {
+        let elem = elem.as_ref()?;
+        if elem.spread.is_some() {
+          return None;
+        }
+        let elem_type = infer_expr_type(source_map, &elem.expr, true)?;
+        if !elem_types.contains(&elem_type) {
+          elem_types.push(elem_type);
+        }
+      }
+
+      let elem_type = match elem_types.len() {
+        0 => return None,
+        1 => elem_types.remove(0),
+        _ => {
+          let repr: Vec<&str> = elem_types
+            .iter()
+            .map(|ts_type| ts_type.repr.as_str())
+            .collect();
+          let union = TsTypeDef {
+            repr: repr.join(" | "),
+            kind: Some(TsTypeDefKind::Union),
+            union: Some(elem_types),
+            ..Default::default()
+          };
+          TsTypeDef {
+            repr: format!("({})", union.repr),
+            kind: Some(TsTypeDefKind::Parenthesized),
+            parenthesized: Some(Box::new(union)),
+            ..Default::default()
+          }
+        }
+      };
+      Some(TsTypeDef {
+        repr: format!("{}[]", elem_type.repr),
+        kind: Some(TsTypeDefKind::Array),
+        array: Some(Box::new(elem_type)),
+        ..Default::default()
+      })
+    }
+    Expr::Object(object) => {
+      use swc_ecma_ast::Prop;
+      use swc_ecma_ast::PropName;
+      use swc_ecma_ast::PropOrSpread;
+
+      let mut members = vec![];
+      let mut properties = vec![];
+      for prop in &object.props {
+        let prop = match prop {
+          PropOrSpread::Prop(prop) => prop,
+          PropOrSpread::Spread(_) => return None,
+        };
+        let (name, key, value) = match &**prop {
+          Prop::KeyValue(key_value) => {
+            let (name, key) = match &key_value.key {
+              PropName::Ident(ident) => {
+                (ident.sym.to_string(), ident.sym.to_string())
+              }
+              PropName::Str(str_) => (
+                str_.value.to_string(),
+                serde_json::to_string(&*str_.value).ok()?,
+              ),
+              PropName::Num(num) => {
+                (num.value.to_string(), num.value.to_string())
+              }
+              PropName::Computed(_) => return None,
+            };
+            let value = infer_expr_type(source_map, &key_value.value, true)?;
+            (name, key, value)
+          }
+          _ => return None,
+        };
+        members.push(format!("{}: {}", key, value.repr));
+        properties.push(LiteralPropertyDef {
+          name,
+          ts_type: Some(value),
+          readonly: false,
+          optional: false,
+        });
+      }
+
+      let repr = if members.is_empty() {
+        "{}".to_string()
+      } else {
+        format!("{{ {} }}", members.join("; "))
+      };
+      Some(TsTypeDef {
+        repr,
+        kind: Some(TsTypeDefKind::TypeLiteral),
+        type_literal: Some(TsTypeLiteralDef {
+          properties,
+          ..Default::default()
+        }),
+        ..Default::default()
+      })
+    }
+    _ => None,
+  }
+}
+
+fn type_ref_def(
+  type_name: String,
+  type_params: Option<Vec<TsTypeDef>>,
+) -> TsTypeDef {
+  let repr = match &type_params {
+    Some(type_params) => {
+      let params: Vec<&str> = type_params
+        .iter()
+        .map(|param| param.repr.as_str())
+        .collect();
+      format!("{}<{}>", type_name, params.join(", "))
+    }
+    None => type_name.clone(),
+  };
+  TsTypeDef {
+    repr,
+    kind: Some(TsTypeDefKind::TypeRef),
+    type_ref: Some(TsTypeRefDef {
+      type_name,
+      type_params,
+    }),
+    ..Default::default()
+  }
 }
@@ -0,0 +1,139 @@
+use crate::doc::printer::render_ts_type;
+use crate::doc::ts_type::LiteralDefKind;
+use crate::doc::ts_type::TsTypeDef;
+use crate::doc::ts_type::TsTypeDefKind;
+use crate::doc::DocNode;
+use crate::doc::ParamKind;
+
+/// Minimal call of a function, with a placeholder argument for each
+/// parameter, eg. `const result = add(0, 0);`.
+pub fn usage_stub(node: &DocNode) -> String {
+  let function_def = node.function_def.as_ref().unwrap();
+  let args: Vec<String> = function_def
+    .params
+    .iter()
+    .filter(|param| param.kind != ParamKind::This)
+    .map(|param| match &param.ts_type {
+      Some(ts_type) => placeholder(ts_type),
+      None => "undefined".to_string(),
+    })
+    .collect();
+
+  let mut call = format!("{}({})", node.name, args.join(", "));
+  if function_def.is_async {
+    call = format!("await {}", call);
+  }
+
+  let returns_value = match &function_def.return_type {
+    Some(return_type) => !is_void(return_type),
+    None => false,
+  };
+  if returns_value {
+    format!("const result = {};", call)
+  } else {
+    format!("{};", call)
+  }
+}
+
+fn is_void(ts_type: &TsTypeDef) -> bool {
+  match &ts_type.kind {
+    Some(TsTypeDefKind::Keyword) => {
+      let keyword = ts_type.keyword.as_ref().unwrap();
+      keyword == "void" || keyword == "never" || keyword == "undefined"
+    }
+    Some(TsTypeDefKind::TypeRef) => {
+      let type_ref = ts_type.type_ref.as_ref().unwrap();
+      type_ref.type_name == "Promise"
+        && matches!(
+          &type_ref.type_params,
+          Some(type_params) if type_params.iter().all(is_void)
+        )
+    }
+    // Assertion signatures throw instead of returning a value.
+    Some(TsTypeDefKind::TypePredicate) => {
+      ts_type.type_predicate.as_ref().unwrap().asserts
+    }
+    _ => false,
+  }
+}
+
+/// Simplest value of `ts_type`; types which can't be constructed from
+/// scratch get an empty object cast to the type.
+fn placeholder(ts_type: &TsTypeDef) -> String {
+  let kind = match &ts_type.kind {
+    Some(kind) => kind,
+    None => return cast_placeholder(ts_type),
+  };
+
+  match kind {
+    TsTypeDefKind::Keyword => {
+      match ts_type.keyword.as_ref().unwrap().as_str() {
+        "number" => "0".to_string(),
+        "bigint" => "0n".to_string(),
+        "string" => "\"\"".to_string(),
+        "boolean" => "false".to_string(),
+        "null" => "null".to_string(),
+        "object" => "{}".to_string(),
+        "symbol" => "Symbol()".to_string(),
+        _ => "undefined".to_string(),
+      }
+    }
+    TsTypeDefKind::Literal => {
+      let literal = ts_type.literal.as_ref().unwrap();
+      match literal.kind {
+        LiteralDefKind::Number => literal.number.unwrap().to_string(),
+        LiteralDefKind::String => {
+          serde_json::to_string(literal.string.as_ref().unwrap()).unwrap()
+        }
+        LiteralDefKind::Boolean => literal.boolean.unwrap().to_string(),
+        LiteralDefKind::BigInt => {
+          format!("{}n", literal.big_int.as_ref().unwrap())
+        }
+      }
+    }
+    TsTypeDefKind::Union => placeholder(&ts_type.union.as_ref().unwrap()[0]),
+    TsTypeDefKind::Array => "[]".to_string(),
+    TsTypeDefKind::Tuple => {
+      let elements: Vec<String> = ts_type
+        .tuple
+        .as_ref()
+        .unwrap()
+        .iter()
+        .filter(|element| {
+          element.kind != Some(TsTypeDefKind::Optional)
+            && element.kind != Some(TsTypeDefKind::Rest)
+        })
+        .map(placeholder)
+        .collect();
+      format!("[{}]", elements.join(", "))
+    }
+    TsTypeDefKind::Parenthesized => {
+      placeholder(ts_type.parenthesized.as_ref().unwrap())
+    }
+    TsTypeDefKind::FnOrConstructor => {
+      let fn_or_constructor = ts_type.fn_or_constructor.as_ref().unwrap();
+      if fn_or_constructor.constructor {
+        cast_placeholder(ts_type)
+      } else {
+        "() => {}".to_string()
+      }
+    }
+    TsTypeDefKind::TypeRef => {
+      let type_ref = ts_type.type_ref.as_ref().unwrap();
+      match type_ref.type_name.as_str() {
+        "Array" | "ReadonlyArray" => "[]".to_string(),
+        "Map" => "new Map()".to_string(),
+        "Set" => "new Set()".to_string(),
+        "Date" => "new Date()".to_string(),
+        "RegExp" => "/(?:)/".to_string(),
+        "Uint8Array" => "new Uint8Array()".to_string(),
+        _ => cast_placeholder(ts_type),
+      }
+    }
+    _ => cast_placeholder(ts_type),
+  }
+}
+
+fn cast_placeholder(ts_type: &TsTypeDef) -> String {
+  format!("{{}} as {}", render_ts_type(ts_type))
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::doc::compat::split_once;
+use crate::doc::DocNode;
+use crate::doc::DocNodeKind;
+
+/// Documentation of one package of a workspace.
+#[derive(Debug)]
+pub struct Package {
+  pub name: String,
+  pub entrypoint: String,
+  pub doc_nodes: Vec<DocNode>,
+}
+
+impl Package {
+  /// File the package's documentation is written to, relative to the output
+  /// directory.
+  pub fn docs_file(&self) -> String {
+    format!("{}.json", self.name)
+  }
+}
+
+/// Name of the package with `entrypoint`, taken from an explicit
+/// `name=path/to/mod.ts` or else the directory containing the entrypoint.
+pub fn package_name(arg: &str) -> (String, String) {
+  if let Some((name, entrypoint)) = split_once(arg, "=") {
+    return (name.to_string(), entrypoint.to_string());
+  }
+
+  let path = Path::new(arg);
+  let name = path
+    .canonicalize()
+    .unwrap_or_else(|_| path.to_path_buf())
+    .parent()
+    .and_then(|dir| dir.file_name())
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_else(|| "mod".to_string());
+  (name, arg.to_string())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSymbol {
+  /// Qualified name, eg. `Ns.name`.
+  pub name: String,
+  pub kind: DocNodeKind,
+  pub package: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageIndex {
+  pub name: String,
+  pub entrypoint: String,
+  /// File with the package's documentation.
+  pub docs: String,
+}
+
+/// Combined index of all packages in a workspace, listing every symbol
+/// along with the package documenting it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceIndex {
+  pub packages: Vec<PackageIndex>,
+  pub symbols: Vec<IndexSymbol>,
+}
+
+fn collect_symbols(
+  doc_nodes: &[DocNode],
+  namespace: &str,
+  package: &str,
+  symbols: &mut Vec<IndexSymbol>,
+) {
+  for node in doc_nodes {
+    let name = format!("{}{}", namespace, node.name);
+    symbols.push(IndexSymbol {
+      name: name.to_string(),
+      kind: node.kind.clone(),
+      package: package.to_string(),
+    });
+    if let Some(namespace_def) = &node.namespace_def {
+      let namespace = format!("{}.", name);
+      collect_symbols(&namespace_def.elements, &namespace, package, symbols);
+    }
+  }
+}
+
+impl WorkspaceIndex {
+  pub fn new(packages: &[Package]) -> WorkspaceIndex {
+    let mut symbols = vec![];
+    for package in packages {
+      collect_symbols(&package.doc_nodes, "", &package.name, &mut symbols);
+    }
+    symbols.sort_by(|a, b| {
+      a.name.cmp(&b.name).then_with(|| a.package.cmp(&b.package))
+    });
+
+    WorkspaceIndex {
+      packages: packages
+        .iter()
+        .map(|package| PackageIndex {
+          name: package.name.to_string(),
+          entrypoint: package.entrypoint.to_string(),
+          docs: package.docs_file(),
+        })
+        .collect(),
+      symbols,
+    }
+  }
+}
+
+fn modified_time(path: &str) -> Option<SystemTime> {
+  std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Modification times of the files a package is documented from, polled to
+/// rebuild its documentation when one of them changes.
+#[derive(Debug)]
+pub struct FileWatcher {
+  files: Vec<(String, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+  pub fn new(files: &[String]) -> FileWatcher {
+    FileWatcher {
+      files: files
+        .iter()
+        .map(|file| (file.to_string(), modified_time(file)))
+        .collect(),
+    }
+  }
+
+  /// The files that were modified, created or removed since the last poll.
+  pub fn changed(&mut self) -> Vec<String> {
+    let mut changed = vec![];
+    for (file, modified) in &mut self.files {
+      let current = modified_time(file);
+      if current != *modified {
+        *modified = current;
+        changed.push(file.clone());
+      }
+    }
+    changed
+  }
+}
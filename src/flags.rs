@@ -0,0 +1,74 @@
+/// Command line arguments of a subcommand, split into positional arguments
+/// and `--flags`.
+#[derive(Debug, Default)]
+pub struct Args {
+  pub positional: Vec<String>,
+  flags: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+  /// `known_flags` lists accepted flags along with whether they take a value,
+  /// which can be passed as `--flag value` or `--flag=value`.
+  pub fn parse(
+    args: &[String],
+    known_flags: &[(&str, bool)],
+  ) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+      if !arg.starts_with("--") {
+        parsed.positional.push(arg.to_string());
+        continue;
+      }
+
+      let mut parts = arg.splitn(2, '=');
+      let name = parts.next().unwrap();
+      let inline_value = parts.next();
+
+      let takes_value = match known_flags.iter().find(|flag| flag.0 == name) {
+        Some((_, takes_value)) => *takes_value,
+        None => return Err(format!("Unknown flag: {}", name)),
+      };
+
+      let value = match (takes_value, inline_value) {
+        (false, None) => None,
+        (false, Some(_)) => {
+          return Err(format!("Flag {} doesn't take a value", name))
+        }
+        (true, Some(value)) => Some(value.to_string()),
+        (true, None) => match args.next() {
+          Some(value) => Some(value.to_string()),
+          None => return Err(format!("Missing value for flag {}", name)),
+        },
+      };
+
+      parsed.flags.push((name.to_string(), value));
+    }
+
+    Ok(parsed)
+  }
+
+  pub fn has(&self, name: &str) -> bool {
+    self.flags.iter().any(|flag| flag.0 == name)
+  }
+
+  /// Values of all occurrences of the flag.
+  pub fn values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+    self
+      .flags
+      .iter()
+      .filter(move |flag| flag.0 == name)
+      .filter_map(|flag| flag.1.as_deref())
+  }
+
+  /// Value of the last occurrence of the flag.
+  pub fn value(&self, name: &str) -> Option<&str> {
+    self
+      .flags
+      .iter()
+      .rev()
+      .find(|flag| flag.0 == name)
+      .and_then(|flag| flag.1.as_deref())
+  }
+}
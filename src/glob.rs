@@ -0,0 +1,33 @@
+/// Whether `path` matches the glob `pattern`, where `*` matches any run of
+/// characters but `/`, `**/` any number of directories and `?` a single
+/// character. Symbol names can be matched as paths without a `/`.
+pub fn is_match(pattern: &str, path: &str) -> bool {
+  let pattern: Vec<char> = pattern.trim_start_matches("./").chars().collect();
+  let path: Vec<char> = path.trim_start_matches("./").chars().collect();
+  is_match_(&pattern, &path)
+}
+
+fn is_match_(pattern: &[char], path: &[char]) -> bool {
+  match pattern {
+    [] => path.is_empty(),
+    ['*', '*', '/', rest @ ..] => {
+      // Either no directory at all, or skip one and try again.
+      is_match_(rest, path)
+        || match path.iter().position(|c| *c == '/') {
+          Some(index) => is_match_(pattern, &path[index + 1..]),
+          None => false,
+        }
+    }
+    ['*', '*', rest @ ..] => {
+      (0..=path.len()).any(|i| is_match_(rest, &path[i..]))
+    }
+    ['*', rest @ ..] => {
+      let end = path.iter().take_while(|c| **c != '/').count();
+      (0..=end).any(|i| is_match_(rest, &path[i..]))
+    }
+    ['?', rest @ ..] => {
+      !path.is_empty() && path[0] != '/' && is_match_(rest, &path[1..])
+    }
+    [c, rest @ ..] => path.first() == Some(c) && is_match_(rest, &path[1..]),
+  }
+}
@@ -13,10 +13,24 @@ use swc_ecma_parser::SourceFileInput;
 use swc_ecma_parser::Syntax;
 use swc_ecma_parser::TsConfig;
 
+use crate::doc::compat::strip_prefix;
+use crate::doc::compat::strip_suffix;
+use crate::doc::js_doc::JsDoc;
+use crate::doc::js_doc::JsDocTag;
 use crate::doc::parser::DocParser;
 use crate::doc::parser::SwcDiagnostics;
+use crate::doc::transform::Transformer;
+use crate::doc::ts_type::computed_name;
+use crate::doc::ts_type::infer_expr_type;
+use crate::doc::ts_type::pat_to_param_def;
+use crate::doc::ts_type::ts_fn_param_to_def;
 use crate::doc::ts_type::ts_type_ann_to_def;
+use crate::doc::ts_type::ts_type_param_decl_to_defs;
+use crate::doc::ts_type::ts_type_to_def;
+use crate::doc::ts_type::type_element_name;
 mod doc;
+mod flags;
+mod glob;
 
 fn prop_name_to_string(
   source_map: &SourceMap,
@@ -28,11 +42,83 @@ fn prop_name_to_string(
     PropName::Str(str_) => str_.value.to_string(),
     PropName::Num(num) => num.value.to_string(),
     PropName::Computed(comp_prop_name) => {
-      source_map.span_to_snippet(comp_prop_name.span).unwrap()
+      computed_name(source_map, &comp_prop_name.expr)
     }
   }
 }
 
+fn decorators_to_defs(
+  source_map: &SourceMap,
+  decorators: &[swc_ecma_ast::Decorator],
+) -> Vec<doc::DecoratorDef> {
+  use swc_ecma_ast::Expr;
+  use swc_ecma_ast::ExprOrSuper;
+  decorators
+    .iter()
+    .map(|decorator| match &*decorator.expr {
+      Expr::Call(call) => {
+        let name = match &call.callee {
+          ExprOrSuper::Expr(callee) => {
+            source_map.span_to_snippet(callee.span()).unwrap()
+          }
+          ExprOrSuper::Super(super_) => {
+            source_map.span_to_snippet(super_.span).unwrap()
+          }
+        };
+        let args = call
+          .args
+          .iter()
+          .map(|arg| source_map.span_to_snippet(arg.span()).unwrap())
+          .collect();
+        doc::DecoratorDef {
+          name,
+          args: Some(args),
+        }
+      }
+      expr => doc::DecoratorDef {
+        name: source_map.span_to_snippet(expr.span()).unwrap(),
+        args: None,
+      },
+    })
+    .collect()
+}
+
+/// Span of a class member without its decorators, which are documented
+/// separately.
+fn span_without_decorators(
+  span: Span,
+  decorators: &[swc_ecma_ast::Decorator],
+) -> Span {
+  match decorators.last() {
+    Some(decorator) => span.with_lo(decorator.span.hi()),
+    None => span,
+  }
+}
+
+fn function_to_def(
+  source_map: &SourceMap,
+  function: &swc_ecma_ast::Function,
+) -> doc::FunctionDef {
+  doc::FunctionDef {
+    params: function
+      .params
+      .iter()
+      .map(|param| pat_to_param_def(source_map, param))
+      .collect(),
+    return_type: function
+      .return_type
+      .as_ref()
+      .map(|rt| ts_type_ann_to_def(source_map, rt)),
+    is_async: function.is_async,
+    is_generator: function.is_generator,
+    type_params: ts_type_param_decl_to_defs(
+      source_map,
+      function.type_params.as_ref(),
+    ),
+    overloads: vec![],
+  }
+}
+
 fn get_doc_for_fn_decl(
   doc_parser: &DocParser,
   parent_span: Span,
@@ -57,55 +143,23 @@ fn get_doc_for_fn_decl(
   }
 
   let snippet = snippet.trim_end().to_string();
-
-  let mut params = vec![];
-
-  for param in &fn_decl.function.params {
-    use swc_ecma_ast::Pat;
-
-    let param_def = match param {
-      Pat::Ident(ident) => {
-        let ts_type = ident
-          .type_ann
-          .as_ref()
-          .map(|rt| ts_type_ann_to_def(&doc_parser.source_map, rt));
-
-        doc::ParamDef {
-          name: ident.sym.to_string(),
-          ts_type,
-        }
-      }
-      _ => doc::ParamDef {
-        name: "<TODO>".to_string(),
-        ts_type: None,
-      },
-    };
-
-    params.push(param_def);
-  }
-
-  let maybe_return_type = fn_decl
-    .function
-    .return_type
-    .as_ref()
-    .map(|rt| ts_type_ann_to_def(&doc_parser.source_map, rt));
-
-  let fn_def = doc::FunctionDef {
-    params,
-    return_type: maybe_return_type,
-    is_async: fn_decl.function.is_async,
-    is_generator: fn_decl.function.is_generator,
-  };
+  let fn_def = function_to_def(&doc_parser.source_map, &fn_decl.function);
 
   doc::DocNode {
     kind: doc::DocNodeKind::Function,
     name: fn_decl.ident.sym.to_string(),
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(parent_span.lo())
-      .into(),
+    location: doc_parser.location_for_span(parent_span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: false,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: Some(fn_def),
     variable_def: None,
     enum_def: None,
@@ -116,11 +170,80 @@ fn get_doc_for_fn_decl(
   }
 }
 
+/// Function definition of an arrow function or function expression, along
+/// with the span of its body.
+fn function_expr_def(
+  doc_parser: &DocParser,
+  expr: &swc_ecma_ast::Expr,
+) -> Option<(doc::FunctionDef, Span)> {
+  use swc_ecma_ast::Expr;
+
+  let source_map = &doc_parser.source_map;
+  let (params, return_type, type_params, is_async, is_generator, body_span) =
+    match expr {
+      Expr::Paren(paren) => return function_expr_def(doc_parser, &paren.expr),
+      Expr::Arrow(arrow) => (
+        &arrow.params,
+        arrow.return_type.as_ref(),
+        arrow.type_params.as_ref(),
+        arrow.is_async,
+        arrow.is_generator,
+        match &arrow.body {
+          swc_ecma_ast::BlockStmtOrExpr::BlockStmt(block) => block.span,
+          swc_ecma_ast::BlockStmtOrExpr::Expr(expr) => expr.span(),
+        },
+      ),
+      Expr::Fn(fn_expr) => {
+        let function = &fn_expr.function;
+        (
+          &function.params,
+          function.return_type.as_ref(),
+          function.type_params.as_ref(),
+          function.is_async,
+          function.is_generator,
+          function.body.as_ref()?.span,
+        )
+      }
+      _ => return None,
+    };
+
+  let fn_def = doc::FunctionDef {
+    params: params
+      .iter()
+      .map(|param| pat_to_param_def(source_map, param))
+      .collect(),
+    return_type: return_type.map(|rt| ts_type_ann_to_def(source_map, rt)),
+    is_async,
+    is_generator,
+    type_params: ts_type_param_decl_to_defs(source_map, type_params),
+    overloads: vec![],
+  };
+  Some((fn_def, body_span))
+}
+
+fn is_literal_expr(expr: &swc_ecma_ast::Expr) -> bool {
+  use swc_ecma_ast::Expr;
+  use swc_ecma_ast::UnaryOp;
+
+  match expr {
+    Expr::Lit(_) => true,
+    Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+    Expr::Unary(unary) => {
+      matches!(unary.op, UnaryOp::Minus | UnaryOp::Plus)
+        && is_literal_expr(&unary.arg)
+    }
+    Expr::Paren(paren) => is_literal_expr(&paren.expr),
+    _ => false,
+  }
+}
+
+/// Document every declarator of `var_decl`. They share the JSDoc of the
+/// statement, unless they have their own.
 fn get_doc_for_var_decl(
   doc_parser: &DocParser,
   parent_span: Span,
   var_decl: &swc_ecma_ast::VarDecl,
-) -> doc::DocNode {
+) -> Vec<doc::DocNode> {
   let js_doc = doc_parser.js_doc_for_span(parent_span);
   let snippet = doc_parser
     .source_map
@@ -130,31 +253,259 @@ fn get_doc_for_var_decl(
     .to_string();
 
   assert!(!var_decl.decls.is_empty());
-  // TODO: support multiple declarators
-  let var_declarator = var_decl.decls.get(0).unwrap();
+  if var_decl.decls.len() == 1 {
+    let var_declarator = &var_decl.decls[0];
+    return vec![get_doc_for_var_declarator(
+      doc_parser,
+      parent_span,
+      snippet,
+      js_doc,
+      var_decl.kind,
+      var_declarator,
+    )];
+  }
+
+  // Snippets of the declarators repeat the keywords of the statement, eg.
+  // `export const`.
+  let first_lo = var_decl.decls[0].span.lo();
+  let keywords = doc_parser
+    .source_map
+    .span_to_snippet(parent_span.with_hi(first_lo))
+    .expect("Snippet not found");
+  let keywords = keywords.trim();
+
+  var_decl
+    .decls
+    .iter()
+    .enumerate()
+    .map(|(i, var_declarator)| {
+      // The first declarator starts with the statement.
+      let span = if i == 0 {
+        parent_span.with_hi(var_declarator.span.hi())
+      } else {
+        var_declarator.span
+      };
+      let declarator_snippet = doc_parser
+        .source_map
+        .span_to_snippet(var_declarator.span)
+        .expect("Snippet not found");
+      let js_doc = doc_parser
+        .js_doc_for_span(var_declarator.span)
+        .or_else(|| js_doc.clone());
+      get_doc_for_var_declarator(
+        doc_parser,
+        span,
+        format!("{} {}", keywords, declarator_snippet.trim_end()),
+        js_doc,
+        var_decl.kind,
+        var_declarator,
+      )
+    })
+    .collect()
+}
+
+fn get_doc_for_var_declarator(
+  doc_parser: &DocParser,
+  parent_span: Span,
+  snippet: String,
+  js_doc: Option<String>,
+  kind: swc_ecma_ast::VarDeclKind,
+  var_declarator: &swc_ecma_ast::VarDeclarator,
+) -> doc::DocNode {
+  let (var_name, ts_type) = match &var_declarator.name {
+    swc_ecma_ast::Pat::Ident(ident) => {
+      let ts_type = ident
+        .type_ann
+        .as_ref()
+        .map(|rt| ts_type_ann_to_def(&doc_parser.source_map, rt));
+      (ident.sym.to_string(), ts_type)
+    }
+    _ => ("<TODO>".to_string(), None),
+  };
+
+  if let Some(init) = &var_declarator.init {
+    if kind == swc_ecma_ast::VarDeclKind::Const && ts_type.is_none() {
+      if let Some((fn_def, body_span)) = function_expr_def(doc_parser, init) {
+        let mut snippet = snippet;
+        let body_snippet =
+          doc_parser.source_map.span_to_snippet(body_span).unwrap();
+        if let Some(index) = snippet.rfind(&body_snippet) {
+          let _ = snippet.split_off(index);
+        }
+
+        return doc::DocNode {
+          kind: doc::DocNodeKind::Function,
+          name: var_name,
+          snippet: snippet.trim_end().to_string(),
+          location: doc_parser.location_for_span(parent_span),
+          js_doc,
+          category: None,
+          stability: None,
+          custom_tags: vec![],
+          is_default: false,
+          is_ambient: false,
+          is_type_only: false,
+          index: 0,
+          reexported_from: None,
+          import_statement: None,
+          function_def: Some(fn_def),
+          variable_def: None,
+          enum_def: None,
+          class_def: None,
+          type_alias_def: None,
+          namespace_def: None,
+          interface_def: None,
+        };
+      }
+    }
+  }
+
+  let ts_type = ts_type.or_else(|| {
+    let init = var_declarator.init.as_ref()?;
+    let widen = kind != swc_ecma_ast::VarDeclKind::Const;
+    infer_expr_type(&doc_parser.source_map, init, widen)
+  });
+
+  // Functions and classes are documented by their signature instead.
+  let init = var_declarator
+    .init
+    .as_ref()
+    .filter(|init| {
+      use swc_ecma_ast::Expr;
+      !matches!(&***init, Expr::Fn(_) | Expr::Arrow(_) | Expr::Class(_))
+    })
+    .map(|init| doc_parser.source_map.span_to_snippet(init.span()).unwrap());
+
+  let is_literal = match &var_declarator.init {
+    Some(init) => is_literal_expr(init),
+    None => false,
+  };
+  let variable_def = doc::VariableDef {
+    ts_type,
+    kind,
+    init,
+    is_literal,
+  };
 
-  let var_name = match &var_declarator.name {
-    swc_ecma_ast::Pat::Ident(ident) => ident.sym.to_string(),
-    _ => "<TODO>".to_string(),
+  let interface_def = match &var_declarator.init {
+    Some(init) if kind == swc_ecma_ast::VarDeclKind::Const => {
+      object_lit_to_interface_def(doc_parser, init)
+    }
+    _ => None,
   };
 
   doc::DocNode {
     kind: doc::DocNodeKind::Variable,
     name: var_name,
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(parent_span.lo())
-      .into(),
+    location: doc_parser.location_for_span(parent_span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: false,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: None,
-    variable_def: None,
+    variable_def: Some(variable_def),
     enum_def: None,
     class_def: None,
     type_alias_def: None,
     namespace_def: None,
-    interface_def: None,
+    interface_def,
+  }
+}
+
+/// Properties and methods of an object literal, documented like members of
+/// an interface, eg. `get` and `timeout` for
+/// `const api = { get(url: string) {}, timeout: 5000 };`.
+fn object_lit_to_interface_def(
+  doc_parser: &DocParser,
+  expr: &swc_ecma_ast::Expr,
+) -> Option<doc::InterfaceDef> {
+  use swc_ecma_ast::Expr;
+  use swc_ecma_ast::Prop;
+  use swc_ecma_ast::PropOrSpread;
+
+  let object = match expr {
+    Expr::Object(object) => object,
+    Expr::Paren(paren) => {
+      return object_lit_to_interface_def(doc_parser, &paren.expr)
+    }
+    _ => return None,
+  };
+
+  let source_map = &doc_parser.source_map;
+  let mut properties = vec![];
+  let mut methods = vec![];
+  for prop in &object.props {
+    let prop = match prop {
+      PropOrSpread::Prop(prop) => prop,
+      PropOrSpread::Spread(_) => continue,
+    };
+    let span = prop.span();
+    let js_doc = doc_parser.js_doc_for_span(span);
+    let location = doc_parser.location_for_span(span);
+
+    let (name, function_def, ts_type) = match &**prop {
+      Prop::Method(method) => {
+        let name = prop_name_to_string(source_map, &method.key);
+        (
+          name,
+          Some(function_to_def(source_map, &method.function)),
+          None,
+        )
+      }
+      Prop::KeyValue(key_value) => {
+        let name = prop_name_to_string(source_map, &key_value.key);
+        match function_expr_def(doc_parser, &key_value.value) {
+          Some((fn_def, _)) => (name, Some(fn_def), None),
+          // Properties can be reassigned, so their types are widened.
+          None => {
+            let ts_type = infer_expr_type(source_map, &key_value.value, true);
+            (name, None, ts_type)
+          }
+        }
+      }
+      Prop::Shorthand(ident) => (ident.sym.to_string(), None, None),
+      _ => continue,
+    };
+
+    match function_def {
+      Some(function_def) => methods.push(doc::InterfaceMethodDef {
+        name,
+        js_doc,
+        location,
+        params: function_def.params,
+        return_type: function_def.return_type,
+        optional: false,
+      }),
+      None => properties.push(doc::InterfacePropertyDef {
+        name,
+        js_doc,
+        location,
+        ts_type,
+        readonly: false,
+        optional: false,
+      }),
+    }
+  }
+
+  if properties.is_empty() && methods.is_empty() {
+    return None;
   }
+  Some(doc::InterfaceDef {
+    type_params: vec![],
+    extends: vec![],
+    properties,
+    methods,
+    index_signatures: vec![],
+    call_signatures: vec![],
+    construct_signatures: vec![],
+  })
 }
 
 fn get_doc_for_ts_type_alias_decl(
@@ -171,18 +522,29 @@ fn get_doc_for_ts_type_alias_decl(
     .to_string();
 
   let alias_name = type_alias_decl.id.sym.to_string();
-  // TODO:
-  let type_alias_def = doc::TypeAliasDef {};
+  let type_alias_def = doc::TypeAliasDef {
+    ts_type: ts_type_to_def(&doc_parser.source_map, &type_alias_decl.type_ann),
+    type_params: ts_type_param_decl_to_defs(
+      &doc_parser.source_map,
+      type_alias_decl.type_params.as_ref(),
+    ),
+  };
 
   doc::DocNode {
     kind: doc::DocNodeKind::TypeAlias,
     name: alias_name,
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(parent_span.lo())
-      .into(),
+    location: doc_parser.location_for_span(parent_span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: true,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: None,
     variable_def: None,
     enum_def: None,
@@ -193,12 +555,135 @@ fn get_doc_for_ts_type_alias_decl(
   }
 }
 
+/// Add the property declared by a getter or a setter, merging it with the
+/// property declared by the other accessor of the pair.
+fn push_accessor(
+  properties: &mut Vec<doc::ClassPropertyDef>,
+  prop_def: doc::ClassPropertyDef,
+) {
+  let pair = properties.iter_mut().find(|prop| {
+    prop.accessor.is_some()
+      && prop.accessor != prop_def.accessor
+      && prop.name == prop_def.name
+      && prop.is_static == prop_def.is_static
+  });
+  let prop = match pair {
+    Some(prop) => prop,
+    None => {
+      properties.push(prop_def);
+      return;
+    }
+  };
+
+  prop.accessor = Some(doc::AccessorKind::GetterSetter);
+  prop.readonly = false;
+  prop.snippet = format!("{}\n{}", prop.snippet, prop_def.snippet);
+  // The location covers both accessors.
+  prop.location.end_line = prop_def.location.end_line;
+  prop.location.end_col = prop_def.location.end_col;
+  prop.location.byte_end = prop_def.location.byte_end;
+  if prop.js_doc.is_none() {
+    prop.js_doc = prop_def.js_doc;
+  }
+  if prop.ts_type.is_none() {
+    prop.ts_type = prop_def.ts_type;
+  }
+}
+
+/// Properties declared by constructor parameters with an accessibility
+/// modifier or `readonly`, eg. `constructor(private name: string)`. Unless
+/// they have their own JSDoc, they are documented by the `@param` tags of the
+/// constructor.
+fn param_props_to_defs(
+  doc_parser: &DocParser,
+  params: &[swc_ecma_ast::PatOrTsParamProp],
+  ctor_js_doc: Option<&str>,
+) -> Vec<doc::ClassPropertyDef> {
+  use swc_ecma_ast::PatOrTsParamProp;
+
+  let ctor_js_doc = ctor_js_doc.map(JsDoc::parse);
+  let mut properties = vec![];
+  for param in params {
+    let param_prop = match param {
+      PatOrTsParamProp::TsParamProp(param_prop) => param_prop,
+      PatOrTsParamProp::Pat(_) => continue,
+    };
+    let param_def = ctor_param_to_def(&doc_parser.source_map, param);
+
+    let js_doc = doc_parser.js_doc_for_span(param_prop.span).or_else(|| {
+      let tag = ctor_js_doc
+        .as_ref()?
+        .tags_named("param")
+        .find(|tag| tag.param_name() == Some(param_def.name.as_str()))?;
+      let description = tag.param_description();
+      if description.is_empty() {
+        None
+      } else {
+        Some(format!("/** {} */", description))
+      }
+    });
+    let snippet = doc_parser
+      .source_map
+      .span_to_snippet(span_without_decorators(
+        param_prop.span,
+        &param_prop.decorators,
+      ))
+      .unwrap();
+
+    properties.push(doc::ClassPropertyDef {
+      js_doc,
+      location: doc_parser.location_for_span(param_prop.span),
+      snippet,
+      ts_type: param_def.ts_type,
+      init: param_def.default,
+      readonly: param_prop.readonly,
+      optional: param_def.optional,
+      accessibility: param_prop.accessibility,
+      is_abstract: false,
+      is_static: false,
+      name: param_def.name,
+      accessor: None,
+      decorators: decorators_to_defs(
+        &doc_parser.source_map,
+        &param_prop.decorators,
+      ),
+    });
+  }
+  properties
+}
+
+/// Constructor parameter, leaving out the modifiers of one declaring a
+/// property.
+fn ctor_param_to_def(
+  source_map: &SourceMap,
+  param: &swc_ecma_ast::PatOrTsParamProp,
+) -> doc::ParamDef {
+  use swc_ecma_ast::Pat;
+  use swc_ecma_ast::PatOrTsParamProp;
+  use swc_ecma_ast::TsParamPropParam;
+
+  match param {
+    PatOrTsParamProp::Pat(pat) => pat_to_param_def(source_map, pat),
+    PatOrTsParamProp::TsParamProp(param_prop) => {
+      let pat = match &param_prop.param {
+        TsParamPropParam::Ident(ident) => Pat::Ident(ident.clone()),
+        TsParamPropParam::Assign(assign) => Pat::Assign(assign.clone()),
+      };
+      pat_to_param_def(source_map, &pat)
+    }
+  }
+}
+
 fn get_doc_for_class_decl(
   doc_parser: &DocParser,
   parent_span: Span,
   class_decl: &swc_ecma_ast::ClassDecl,
 ) -> doc::DocNode {
-  let js_doc = doc_parser.js_doc_for_span(parent_span);
+  // Decorators written before `export` come with the JSDoc of the class.
+  let js_doc = doc_parser.js_doc_for_span(parent_span).or_else(|| {
+    let decorator = class_decl.class.decorators.first()?;
+    doc_parser.js_doc_for_span(decorator.span)
+  });
 
   let mut snippet = doc_parser
     .source_map
@@ -256,11 +741,23 @@ fn get_doc_for_class_decl(
         let constructor_name =
           prop_name_to_string(&doc_parser.source_map, &ctor.key);
 
+        properties.extend(param_props_to_defs(
+          doc_parser,
+          &ctor.params,
+          ctor_js_doc.as_deref(),
+        ));
+
         let constructor_def = doc::ClassConstructorDef {
           js_doc: ctor_js_doc,
+          location: doc_parser.location_for_span(ctor.span()),
           snippet: ctor_snippet,
           accessibility: ctor.accessibility,
           name: constructor_name,
+          params: ctor
+            .params
+            .iter()
+            .map(|param| ctor_param_to_def(&doc_parser.source_map, param))
+            .collect(),
         };
         constructors.push(constructor_def);
       }
@@ -268,7 +765,10 @@ fn get_doc_for_class_decl(
         let method_js_doc = doc_parser.js_doc_for_span(class_method.span());
         let mut method_snippet = doc_parser
           .source_map
-          .span_to_snippet(class_method.span())
+          .span_to_snippet(span_without_decorators(
+            class_method.span(),
+            &class_method.function.decorators,
+          ))
           .unwrap();
 
         if let Some(body) = &class_method.function.body {
@@ -282,19 +782,72 @@ fn get_doc_for_class_decl(
           let _ = method_snippet.split_off(index);
         }
 
-        let method_snippet = method_snippet.trim_end().to_string();
+        let method_snippet = method_snippet.trim().to_string();
 
         let method_name =
           prop_name_to_string(&doc_parser.source_map, &class_method.key);
 
+        use swc_ecma_ast::MethodKind;
+        let accessor = match class_method.kind {
+          MethodKind::Method => None,
+          MethodKind::Getter => Some(doc::AccessorKind::Getter),
+          MethodKind::Setter => Some(doc::AccessorKind::Setter),
+        };
+        if let Some(accessor) = accessor {
+          let function = &class_method.function;
+          let ts_type = match accessor {
+            doc::AccessorKind::Setter => {
+              function.params.first().and_then(|param| {
+                pat_to_param_def(&doc_parser.source_map, param).ts_type
+              })
+            }
+            _ => function
+              .return_type
+              .as_ref()
+              .map(|rt| ts_type_ann_to_def(&doc_parser.source_map, rt)),
+          };
+          push_accessor(
+            &mut properties,
+            doc::ClassPropertyDef {
+              js_doc: method_js_doc,
+              location: doc_parser.location_for_span(class_method.span()),
+              snippet: method_snippet,
+              ts_type,
+              init: None,
+              readonly: accessor == doc::AccessorKind::Getter,
+              optional: class_method.is_optional,
+              is_abstract: class_method.is_abstract,
+              is_static: class_method.is_static,
+              accessibility: class_method.accessibility,
+              name: method_name,
+              accessor: Some(accessor),
+              decorators: decorators_to_defs(
+                &doc_parser.source_map,
+                &function.decorators,
+              ),
+            },
+          );
+          continue;
+        }
+
         let method_def = doc::ClassMethodDef {
           js_doc: method_js_doc,
+          location: doc_parser.location_for_span(class_method.span()),
           snippet: method_snippet,
           accessibility: class_method.accessibility,
           is_abstract: class_method.is_abstract,
           is_static: class_method.is_static,
+          optional: class_method.is_optional,
           name: method_name,
           kind: class_method.kind,
+          decorators: decorators_to_defs(
+            &doc_parser.source_map,
+            &class_method.function.decorators,
+          ),
+          function_def: function_to_def(
+            &doc_parser.source_map,
+            &class_method.function,
+          ),
         };
         methods.push(method_def);
       }
@@ -302,29 +855,53 @@ fn get_doc_for_class_decl(
         let prop_js_doc = doc_parser.js_doc_for_span(class_prop.span());
         let prop_snippet = doc_parser
           .source_map
-          .span_to_snippet(class_prop.span())
-          .unwrap();
+          .span_to_snippet(span_without_decorators(
+            class_prop.span(),
+            &class_prop.decorators,
+          ))
+          .unwrap()
+          .trim_start()
+          .to_string();
 
+        // Like `const`, readonly properties keep literal types.
         let ts_type = class_prop
           .type_ann
           .as_ref()
-          .map(|rt| ts_type_ann_to_def(&doc_parser.source_map, rt));
+          .map(|rt| ts_type_ann_to_def(&doc_parser.source_map, rt))
+          .or_else(|| {
+            let value = class_prop.value.as_ref()?;
+            let widen = !class_prop.readonly;
+            infer_expr_type(&doc_parser.source_map, value, widen)
+          });
+
+        let init = class_prop.value.as_ref().map(|value| {
+          doc_parser.source_map.span_to_snippet(value.span()).unwrap()
+        });
 
-        use swc_ecma_ast::Expr;
         let prop_name = match &*class_prop.key {
-          Expr::Ident(ident) => ident.sym.to_string(),
-          _ => "<TODO>".to_string(),
+          swc_ecma_ast::Expr::Ident(ident) if !class_prop.computed => {
+            ident.sym.to_string()
+          }
+          key => computed_name(&doc_parser.source_map, key),
         };
 
         let prop_def = doc::ClassPropertyDef {
           js_doc: prop_js_doc,
+          location: doc_parser.location_for_span(class_prop.span()),
           snippet: prop_snippet,
           ts_type,
+          init,
           readonly: class_prop.readonly,
+          optional: class_prop.is_optional,
           is_abstract: class_prop.is_abstract,
           is_static: class_prop.is_static,
           accessibility: class_prop.accessibility,
           name: prop_name,
+          accessor: None,
+          decorators: decorators_to_defs(
+            &doc_parser.source_map,
+            &class_prop.decorators,
+          ),
         };
         properties.push(prop_def);
       }
@@ -335,23 +912,73 @@ fn get_doc_for_class_decl(
     }
   }
 
+  let extends = class_decl.class.super_class.as_ref().map(|super_class| {
+    doc_parser
+      .source_map
+      .span_to_snippet(super_class.span())
+      .unwrap()
+  });
+
+  let super_type_params = match &class_decl.class.super_type_params {
+    Some(type_params) => type_params
+      .params
+      .iter()
+      .map(|ts_type| ts_type_to_def(&doc_parser.source_map, ts_type))
+      .collect(),
+    None => vec![],
+  };
+
+  let implements = class_decl
+    .class
+    .implements
+    .iter()
+    .map(|expr| doc_parser.source_map.span_to_snippet(expr.span).unwrap())
+    .collect();
+
   let class_name = class_decl.ident.sym.to_string();
+  let has_method = |name: &str| {
+    methods
+      .iter()
+      .any(|method| method.name == name && !method.is_static)
+  };
+  let is_iterable = has_method("[Symbol.iterator]");
+  let is_async_iterable = has_method("[Symbol.asyncIterator]");
+
   let class_def = doc::ClassDef {
     is_abstract: class_decl.class.is_abstract,
+    decorators: decorators_to_defs(
+      &doc_parser.source_map,
+      &class_decl.class.decorators,
+    ),
+    type_params: ts_type_param_decl_to_defs(
+      &doc_parser.source_map,
+      class_decl.class.type_params.as_ref(),
+    ),
+    extends,
+    super_type_params,
+    implements,
     constructors,
     properties,
     methods,
+    is_iterable,
+    is_async_iterable,
   };
 
   doc::DocNode {
     kind: doc::DocNodeKind::Class,
     name: class_name,
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(parent_span.lo())
-      .into(),
+    location: doc_parser.location_for_span(parent_span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: false,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: None,
     variable_def: None,
     enum_def: None,
@@ -375,25 +1002,257 @@ fn get_doc_for_ts_interface_decl(
 
   let interface_name = interface_decl.id.sym.to_string();
 
+  let extends = interface_decl
+    .extends
+    .iter()
+    .map(|expr| doc_parser.source_map.span_to_snippet(expr.span).unwrap())
+    .collect();
+
+  let source_map = &doc_parser.source_map;
+  let member_name = |key: &swc_ecma_ast::Expr, computed: bool| {
+    type_element_name(source_map, key, computed)
+  };
+  let params = |params: &[swc_ecma_ast::TsFnParam]| {
+    params
+      .iter()
+      .map(|param| ts_fn_param_to_def(source_map, param))
+      .collect()
+  };
+  let ts_type = |type_ann: &Option<swc_ecma_ast::TsTypeAnn>| {
+    type_ann
+      .as_ref()
+      .map(|type_ann| ts_type_ann_to_def(source_map, type_ann))
+  };
+
+  let mut properties = vec![];
+  let mut methods = vec![];
+  let mut index_signatures = vec![];
+  let mut call_signatures = vec![];
+  let mut construct_signatures = vec![];
+  for type_element in &interface_decl.body.body {
+    use swc_ecma_ast::TsTypeElement::*;
+
+    match type_element {
+      TsPropertySignature(prop) => {
+        properties.push(doc::InterfacePropertyDef {
+          name: member_name(&prop.key, prop.computed),
+          js_doc: doc_parser.js_doc_for_span(prop.span),
+          location: doc_parser.location_for_span(prop.span),
+          ts_type: ts_type(&prop.type_ann),
+          readonly: prop.readonly,
+          optional: prop.optional,
+        });
+      }
+      TsMethodSignature(method) => {
+        methods.push(doc::InterfaceMethodDef {
+          name: member_name(&method.key, method.computed),
+          js_doc: doc_parser.js_doc_for_span(method.span),
+          location: doc_parser.location_for_span(method.span),
+          params: params(&method.params),
+          return_type: ts_type(&method.type_ann),
+          optional: method.optional,
+        });
+      }
+      TsIndexSignature(index) => {
+        index_signatures.push(doc::InterfaceIndexSignatureDef {
+          js_doc: doc_parser.js_doc_for_span(index.span),
+          location: doc_parser.location_for_span(index.span),
+          readonly: index.readonly,
+          params: params(&index.params),
+          ts_type: ts_type(&index.type_ann),
+        });
+      }
+      TsCallSignatureDecl(call) => {
+        call_signatures.push(doc::InterfaceCallSignatureDef {
+          js_doc: doc_parser.js_doc_for_span(call.span),
+          location: doc_parser.location_for_span(call.span),
+          params: params(&call.params),
+          ts_type: ts_type(&call.type_ann),
+        });
+      }
+      TsConstructSignatureDecl(construct) => {
+        construct_signatures.push(doc::InterfaceCallSignatureDef {
+          js_doc: doc_parser.js_doc_for_span(construct.span),
+          location: doc_parser.location_for_span(construct.span),
+          params: params(&construct.params),
+          ts_type: ts_type(&construct.type_ann),
+        });
+      }
+    }
+  }
+
   doc::DocNode {
     kind: doc::DocNodeKind::Interface,
     name: interface_name,
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(parent_span.lo())
-      .into(),
+    location: doc_parser.location_for_span(parent_span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: true,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: None,
     variable_def: None,
     enum_def: None,
     class_def: None,
     type_alias_def: None,
     namespace_def: None,
-    interface_def: Some(doc::InterfaceDef {}),
+    interface_def: Some(doc::InterfaceDef {
+      type_params: ts_type_param_decl_to_defs(
+        source_map,
+        interface_decl.type_params.as_ref(),
+      ),
+      extends,
+      properties,
+      methods,
+      index_signatures,
+      call_signatures,
+      construct_signatures,
+    }),
+  }
+}
+
+enum EnumValue {
+  Num(f64),
+  Str(String),
+}
+
+impl EnumValue {
+  fn into_json(self) -> Option<serde_json::Value> {
+    match self {
+      EnumValue::Num(num) if num.fract() == 0.0 && num.abs() < 9e15 => {
+        Some((num as i64).into())
+      }
+      EnumValue::Num(num) => {
+        serde_json::Number::from_f64(num).map(serde_json::Value::Number)
+      }
+      EnumValue::Str(str_) => Some(str_.into()),
+    }
+  }
+}
+
+fn enum_value_of(member: &doc::EnumMemberDef) -> Option<EnumValue> {
+  match member.value.as_ref()? {
+    serde_json::Value::Number(num) => Some(EnumValue::Num(num.as_f64()?)),
+    serde_json::Value::String(str_) => Some(EnumValue::Str(str_.to_string())),
+    _ => None,
   }
 }
 
+/// Constant-fold the initializer of an enum member like TypeScript does for
+/// constant enum expressions, eg. `Flag = 1 << 3` or `All = Read | Write`.
+fn eval_enum_member_init(
+  expr: &swc_ecma_ast::Expr,
+  enum_name: &str,
+  members: &[doc::EnumMemberDef],
+) -> Option<EnumValue> {
+  use swc_ecma_ast::BinaryOp;
+  use swc_ecma_ast::Expr;
+  use swc_ecma_ast::ExprOrSuper;
+  use swc_ecma_ast::Lit;
+  use swc_ecma_ast::UnaryOp;
+
+  let eval_num =
+    |expr: &Expr| match eval_enum_member_init(expr, enum_name, members)? {
+      EnumValue::Num(num) => Some(num),
+      EnumValue::Str(_) => None,
+    };
+  let to_int32 = |num: f64| num as i64 as i32;
+  let to_uint32 = |num: f64| num as i64 as u32;
+  let member_value = |name: &str| {
+    let member = members.iter().find(|member| member.name == name)?;
+    enum_value_of(member)
+  };
+
+  let value = match expr {
+    Expr::Lit(Lit::Num(num)) => EnumValue::Num(num.value),
+    Expr::Lit(Lit::Str(str_)) => EnumValue::Str(str_.value.to_string()),
+    Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+      let quasi = tpl.quasis.first()?;
+      let str_ = quasi.cooked.as_ref().unwrap_or(&quasi.raw);
+      EnumValue::Str(str_.value.to_string())
+    }
+    Expr::Paren(paren) => {
+      eval_enum_member_init(&paren.expr, enum_name, members)?
+    }
+    Expr::Ident(ident) => member_value(&ident.sym)?,
+    Expr::Member(member_expr) => {
+      let is_own_enum = match &member_expr.obj {
+        ExprOrSuper::Expr(obj) => match &**obj {
+          Expr::Ident(ident) => ident.sym == *enum_name,
+          _ => false,
+        },
+        ExprOrSuper::Super(_) => false,
+      };
+      let name = match &*member_expr.prop {
+        Expr::Ident(ident) if !member_expr.computed => ident.sym.to_string(),
+        Expr::Lit(Lit::Str(str_)) => str_.value.to_string(),
+        _ => return None,
+      };
+      if !is_own_enum {
+        return None;
+      }
+      member_value(&name)?
+    }
+    Expr::Unary(unary) => {
+      let arg = eval_num(&unary.arg)?;
+      match unary.op {
+        UnaryOp::Minus => EnumValue::Num(-arg),
+        UnaryOp::Plus => EnumValue::Num(arg),
+        UnaryOp::Tilde => EnumValue::Num(f64::from(!to_int32(arg))),
+        _ => return None,
+      }
+    }
+    Expr::Bin(bin) => {
+      if bin.op == BinaryOp::Add {
+        let left = eval_enum_member_init(&bin.left, enum_name, members)?;
+        let right = eval_enum_member_init(&bin.right, enum_name, members)?;
+        return Some(match (left, right) {
+          (EnumValue::Num(left), EnumValue::Num(right)) => {
+            EnumValue::Num(left + right)
+          }
+          (left, right) => {
+            let to_string = |value: EnumValue| match value {
+              EnumValue::Num(num) => num.to_string(),
+              EnumValue::Str(str_) => str_,
+            };
+            EnumValue::Str(format!("{}{}", to_string(left), to_string(right)))
+          }
+        });
+      }
+
+      let left = eval_num(&bin.left)?;
+      let right = eval_num(&bin.right)?;
+      let shift = to_uint32(right) & 0x1f;
+      let num = match bin.op {
+        BinaryOp::Sub => left - right,
+        BinaryOp::Mul => left * right,
+        BinaryOp::Div => left / right,
+        BinaryOp::Mod => left % right,
+        BinaryOp::Exp => left.powf(right),
+        BinaryOp::BitOr => f64::from(to_int32(left) | to_int32(right)),
+        BinaryOp::BitAnd => f64::from(to_int32(left) & to_int32(right)),
+        BinaryOp::BitXor => f64::from(to_int32(left) ^ to_int32(right)),
+        BinaryOp::LShift => f64::from(to_int32(left).wrapping_shl(shift)),
+        BinaryOp::RShift => f64::from(to_int32(left).wrapping_shr(shift)),
+        BinaryOp::ZeroFillRShift => {
+          f64::from(to_uint32(left).wrapping_shr(shift))
+        }
+        _ => return None,
+      };
+      EnumValue::Num(num)
+    }
+    _ => return None,
+  };
+
+  Some(value)
+}
+
 fn get_doc_for_ts_enum_decl(
   doc_parser: &DocParser,
   parent_span: Span,
@@ -418,21 +1277,51 @@ fn get_doc_for_ts_enum_decl(
       Str(str_) => str_.value.to_string(),
     };
 
-    let member_def = doc::EnumMemberDef { name: member_name };
+    let init = enum_member
+      .init
+      .as_ref()
+      .map(|init| doc_parser.source_map.span_to_snippet(init.span()).unwrap());
+
+    let value = match &enum_member.init {
+      Some(init) => eval_enum_member_init(init, &enum_name, &members),
+      // Members without initializer auto-increment the previous value.
+      None => match members.last() {
+        None => Some(EnumValue::Num(0.0)),
+        Some(prev) => match enum_value_of(prev) {
+          Some(EnumValue::Num(num)) => Some(EnumValue::Num(num + 1.0)),
+          _ => None,
+        },
+      },
+    };
+
+    let member_def = doc::EnumMemberDef {
+      name: member_name,
+      init,
+      value: value.and_then(EnumValue::into_json),
+    };
     members.push(member_def);
   }
 
-  let enum_def = doc::EnumDef { members };
+  let enum_def = doc::EnumDef {
+    members,
+    is_const: enum_decl.is_const,
+  };
 
   doc::DocNode {
     kind: doc::DocNodeKind::Enum,
     name: enum_name,
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(parent_span.lo())
-      .into(),
+    location: doc_parser.location_for_span(parent_span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: false,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: None,
     variable_def: None,
     enum_def: Some(enum_def),
@@ -460,25 +1349,36 @@ fn get_doc_for_ts_namespace_decl(
   use swc_ecma_ast::TsNamespaceBody::*;
 
   let elements = match &*ts_namespace_decl.body {
-    TsModuleBlock(ts_module_block) => {
-      get_doc_nodes_for_module_body(doc_parser, ts_module_block.body.clone())
-    }
+    TsModuleBlock(ts_module_block) => get_doc_nodes_for_module_body(
+      doc_parser,
+      ts_module_block.body.clone(),
+      false,
+    ),
     TsNamespaceDecl(ts_namespace_decl) => {
       vec![get_doc_for_ts_namespace_decl(doc_parser, ts_namespace_decl)]
     }
   };
 
-  let ns_def = doc::NamespaceDef { elements };
+  let ns_def = doc::NamespaceDef {
+    elements,
+    augmentation: None,
+  };
 
   doc::DocNode {
     kind: doc::DocNodeKind::Namespace,
     name: namespace_name,
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(ts_namespace_decl.span.lo())
-      .into(),
+    location: doc_parser.location_for_span(ts_namespace_decl.span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: false,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: None,
     variable_def: None,
     enum_def: None,
@@ -512,9 +1412,13 @@ fn get_doc_for_ts_module(
     use swc_ecma_ast::TsNamespaceBody::*;
 
     match &body {
-      TsModuleBlock(ts_module_block) => {
-        get_doc_nodes_for_module_body(doc_parser, ts_module_block.body.clone())
-      }
+      // Declarations in augmentations and `declare namespace` don't need to
+      // be exported.
+      TsModuleBlock(ts_module_block) => get_doc_nodes_for_module_body(
+        doc_parser,
+        ts_module_block.body.clone(),
+        is_augmentation(ts_module_decl) || ts_module_decl.declare,
+      ),
       TsNamespaceDecl(ts_namespace_decl) => {
         vec![get_doc_for_ts_namespace_decl(doc_parser, ts_namespace_decl)]
       }
@@ -523,17 +1427,31 @@ fn get_doc_for_ts_module(
     vec![]
   };
 
-  let ns_def = doc::NamespaceDef { elements };
+  let augmentation = match &ts_module_decl.id {
+    _ if ts_module_decl.global => Some(doc::Augmentation::Global),
+    TsModuleName::Str(_) => Some(doc::Augmentation::Module),
+    TsModuleName::Ident(_) => None,
+  };
+  let ns_def = doc::NamespaceDef {
+    elements,
+    augmentation,
+  };
 
   doc::DocNode {
     kind: doc::DocNodeKind::Namespace,
     name: namespace_name,
     snippet,
-    location: doc_parser
-      .source_map
-      .lookup_char_pos(parent_span.lo())
-      .into(),
+    location: doc_parser.location_for_span(parent_span),
     js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: false,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
     function_def: None,
     variable_def: None,
     enum_def: None,
@@ -544,70 +1462,427 @@ fn get_doc_for_ts_module(
   }
 }
 
-pub fn get_doc_node_for_export_decl(
+pub fn get_doc_nodes_for_export_decl(
   doc_parser: &DocParser,
   export_decl: &swc_ecma_ast::ExportDecl,
-) -> doc::DocNode {
-  let export_span = export_decl.span();
+) -> Vec<doc::DocNode> {
+  let export_span = export_decl_span(doc_parser, export_decl);
+  get_doc_nodes_for_decl(doc_parser, export_span, &export_decl.decl)
+}
+
+/// Span of `export_decl` from the `export` keyword. The parser starts it at
+/// `const` for `export const enum`.
+fn export_decl_span(
+  doc_parser: &DocParser,
+  export_decl: &swc_ecma_ast::ExportDecl,
+) -> Span {
+  let span = export_decl.span();
+  let is_const_enum = matches!(
+    &export_decl.decl,
+    swc_ecma_ast::Decl::TsEnum(ts_enum) if ts_enum.is_const
+  );
+  if !is_const_enum {
+    return span;
+  }
+  let lookup = doc_parser.source_map.lookup_byte_offset(span.lo());
+  let before = lookup.sf.src[..lookup.pos.0 as usize].trim_end();
+  match strip_suffix(before, "export") {
+    Some(rest) => {
+      span.with_lo(lookup.sf.start_pos + swc_common::BytePos(rest.len() as u32))
+    }
+    None => span,
+  }
+}
+
+fn get_doc_nodes_for_decl(
+  doc_parser: &DocParser,
+  export_span: Span,
+  decl: &swc_ecma_ast::Decl,
+) -> Vec<doc::DocNode> {
   use swc_ecma_ast::Decl;
-  match &export_decl.decl {
+  let mut doc_nodes = match decl {
     Decl::Class(class_decl) => {
-      get_doc_for_class_decl(doc_parser, export_span, class_decl)
+      vec![get_doc_for_class_decl(doc_parser, export_span, class_decl)]
+    }
+    Decl::Fn(fn_decl) => {
+      vec![get_doc_for_fn_decl(doc_parser, export_span, fn_decl)]
     }
-    Decl::Fn(fn_decl) => get_doc_for_fn_decl(doc_parser, export_span, fn_decl),
     Decl::Var(var_decl) => {
       get_doc_for_var_decl(doc_parser, export_span, var_decl)
     }
     Decl::TsInterface(ts_interface_decl) => {
-      get_doc_for_ts_interface_decl(doc_parser, export_span, ts_interface_decl)
+      vec![get_doc_for_ts_interface_decl(
+        doc_parser,
+        export_span,
+        ts_interface_decl,
+      )]
     }
     Decl::TsTypeAlias(ts_type_alias) => {
-      get_doc_for_ts_type_alias_decl(doc_parser, export_span, ts_type_alias)
+      vec![get_doc_for_ts_type_alias_decl(
+        doc_parser,
+        export_span,
+        ts_type_alias,
+      )]
     }
     Decl::TsEnum(ts_enum) => {
-      get_doc_for_ts_enum_decl(doc_parser, export_span, ts_enum)
+      vec![get_doc_for_ts_enum_decl(doc_parser, export_span, ts_enum)]
     }
     Decl::TsModule(ts_module) => {
-      get_doc_for_ts_module(doc_parser, export_span, ts_module)
+      vec![get_doc_for_ts_module(doc_parser, export_span, ts_module)]
     }
+  };
+  let declare = match decl {
+    Decl::Class(class_decl) => class_decl.declare,
+    Decl::Fn(fn_decl) => fn_decl.declare,
+    Decl::Var(var_decl) => var_decl.declare,
+    Decl::TsInterface(ts_interface_decl) => ts_interface_decl.declare,
+    Decl::TsTypeAlias(ts_type_alias) => ts_type_alias.declare,
+    Decl::TsEnum(ts_enum) => ts_enum.declare,
+    Decl::TsModule(ts_module) => ts_module.declare,
+  };
+  for doc_node in doc_nodes.iter_mut() {
+    doc_node.is_ambient = declare;
   }
+  doc_nodes
 }
 
-pub fn get_doc_nodes_for_named_export(
-  _doc_parser: &DocParser,
-  named_export: &swc_ecma_ast::NamedExport,
-) -> Vec<doc::DocNode> {
-  let file_name = named_export.src.as_ref().expect("").value.to_string();
-  // TODO: resolve specifier
-  let source_code =
-    std::fs::read_to_string(&file_name).expect("Failed to read file");
-  let doc_nodes =
-    get_docs(file_name, source_code).expect("Failed to print docs");
-  let reexports: Vec<String> = named_export
-    .specifiers
-    .iter()
-    .map(|export_specifier| {
-      use swc_ecma_ast::ExportSpecifier::*;
-
-      match export_specifier {
-        Named(named_export_specifier) => {
-          Some(named_export_specifier.orig.sym.to_string())
-        }
-        // TODO:
-        Namespace(_) => None,
-        Default(_) => None,
-      }
-    })
-    .filter(|s| s.is_some())
-    .map(|s| s.unwrap())
-    .collect();
-
-  let reexports_docs: Vec<doc::DocNode> = doc_nodes
-    .into_iter()
-    .filter(|doc_node| reexports.contains(&doc_node.name))
-    .collect();
+/// The span of `export default` declarations starts after `default`, extend
+/// it to include the keywords so JSDoc and snippets are found as usual.
+fn export_default_span(source_map: &SourceMap, span: Span) -> Span {
+  let prev_source = match source_map.span_to_prev_source(span) {
+    Ok(prev_source) => prev_source,
+    Err(_) => return span,
+  };
+  let keywords = strip_suffix(prev_source.trim_end(), "default")
+    .map(|source| source.trim_end())
+    .and_then(|source| strip_suffix(source, "export"));
+  match keywords {
+    Some(source) => {
+      let keywords_len = (prev_source.len() - source.len()) as u32;
+      span.with_lo(swc_common::BytePos(span.lo().0 - keywords_len))
+    }
+    None => span,
+  }
+}
 
-  reexports_docs
+/// Like `export_default_span`, for the `declare` keyword of declarations
+/// which aren't exported.
+fn declare_span(source_map: &SourceMap, span: Span) -> Span {
+  let prev_source = match source_map.span_to_prev_source(span) {
+    Ok(prev_source) => prev_source,
+    Err(_) => return span,
+  };
+  match strip_suffix(prev_source.trim_end(), "declare") {
+    Some(source) => {
+      let keyword_len = (prev_source.len() - source.len()) as u32;
+      span.with_lo(swc_common::BytePos(span.lo().0 - keyword_len))
+    }
+    None => span,
+  }
+}
+
+/// Document `export default` declarations like the declarations they stand
+/// for, named `default` whether or not they have a name of their own.
+pub fn get_doc_node_for_export_default_decl(
+  doc_parser: &DocParser,
+  export_default_decl: &swc_ecma_ast::ExportDefaultDecl,
+) -> doc::DocNode {
+  use swc_ecma_ast::DefaultDecl;
+  use swc_ecma_ast::Ident;
+
+  let export_span =
+    export_default_span(&doc_parser.source_map, export_default_decl.span);
+  let ident = |ident: &Option<Ident>| {
+    ident
+      .clone()
+      .unwrap_or_else(|| Ident::new("default".into(), export_span))
+  };
+  let mut doc_node = match &export_default_decl.decl {
+    DefaultDecl::Class(class_expr) => {
+      let class_decl = swc_ecma_ast::ClassDecl {
+        ident: ident(&class_expr.ident),
+        declare: false,
+        class: class_expr.class.clone(),
+      };
+      get_doc_for_class_decl(doc_parser, export_span, &class_decl)
+    }
+    DefaultDecl::Fn(fn_expr) => {
+      let fn_decl = swc_ecma_ast::FnDecl {
+        ident: ident(&fn_expr.ident),
+        declare: false,
+        function: fn_expr.function.clone(),
+      };
+      get_doc_for_fn_decl(doc_parser, export_span, &fn_decl)
+    }
+    DefaultDecl::TsInterfaceDecl(interface_decl) => {
+      get_doc_for_ts_interface_decl(doc_parser, export_span, interface_decl)
+    }
+  };
+  doc_node.name = "default".to_string();
+  doc_node.is_default = true;
+  doc_node
+}
+
+/// Document the expression exported by `export default expr` or
+/// `export = expr` as a constant named `default`.
+pub fn get_doc_node_for_default_expr(
+  doc_parser: &DocParser,
+  export_span: Span,
+  expr: &swc_ecma_ast::Expr,
+) -> doc::DocNode {
+  let snippet = doc_parser
+    .source_map
+    .span_to_snippet(export_span)
+    .expect("Snippet not found")
+    .trim_end()
+    .to_string();
+  doc::DocNode {
+    kind: doc::DocNodeKind::Variable,
+    name: "default".to_string(),
+    snippet,
+    location: doc_parser.location_for_span(export_span),
+    js_doc: doc_parser.js_doc_for_span(export_span),
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: true,
+    is_ambient: false,
+    is_type_only: false,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
+    function_def: None,
+    variable_def: Some(doc::VariableDef {
+      ts_type: None,
+      kind: swc_ecma_ast::VarDeclKind::Const,
+      init: Some(doc_parser.source_map.span_to_snippet(expr.span()).unwrap()),
+      is_literal: is_literal_expr(expr),
+    }),
+    enum_def: None,
+    class_def: None,
+    type_alias_def: None,
+    namespace_def: None,
+    interface_def: None,
+  }
+}
+
+/// Path or URL of the module `specifier` refers to, relative to the module
+/// `referrer`. Bare specifiers like `"react"` aren't supported.
+fn resolve_specifier(referrer: &str, specifier: &str) -> Option<String> {
+  if doc::fetch::is_remote(specifier) {
+    return Some(specifier.to_string());
+  }
+  if !specifier.starts_with("./") && !specifier.starts_with("../") {
+    return None;
+  }
+  if doc::fetch::is_remote(referrer) {
+    return Some(doc::fetch::join_url(referrer, specifier));
+  }
+  let dir = std::path::Path::new(referrer)
+    .parent()
+    .unwrap_or_else(|| std::path::Path::new(""));
+  Some(dir.join(specifier).to_string_lossy().to_string())
+}
+
+/// Document the module `specifier` imported by the module containing `span`.
+fn get_docs_for_specifier(
+  doc_parser: &DocParser,
+  span: Span,
+  specifier: &str,
+) -> Vec<doc::DocNode> {
+  let referrer = match doc_parser.source_map.span_to_filename(span) {
+    FileName::Custom(file_name) => file_name,
+    file_name => file_name.to_string(),
+  };
+  document_specifier(&referrer, specifier)
+}
+
+/// Document the module `specifier` imported by the module `referrer`.
+fn document_specifier(referrer: &str, specifier: &str) -> Vec<doc::DocNode> {
+  let file_name = match resolve_specifier(referrer, specifier) {
+    Some(file_name) => file_name,
+    None => {
+      eprintln!("Can't resolve {} from {}, skipping it", specifier, referrer);
+      return vec![];
+    }
+  };
+  if !doc::fetch::is_remote(&file_name) {
+    DOCUMENTED.with(|modules| modules.borrow_mut().push(file_name.clone()));
+  }
+  if let Some(module) =
+    MODULE_CACHE.with(|cache| cache.borrow().get(&file_name).cloned())
+  {
+    DOCUMENTED.with(|modules| {
+      modules.borrow_mut().extend(module.modules.iter().cloned())
+    });
+    return module.doc_nodes;
+  }
+  let source_code = match doc::fetch::read_module(&file_name) {
+    Ok(source_code) => source_code,
+    Err(err) => {
+      eprintln!("{}", err);
+      return vec![];
+    }
+  };
+
+  // Modules re-exporting each other would be documented forever.
+  let path = std::fs::canonicalize(&file_name)
+    .unwrap_or_else(|_| std::path::PathBuf::from(&file_name));
+  let is_cycle = REEXPORTING.with(|modules| {
+    let mut modules = modules.borrow_mut();
+    if let Some(index) = modules.iter().position(|(m, _)| *m == path) {
+      // Every module in the cycle misses the symbols of the one it ends at.
+      for (_, partial) in &mut modules[index..] {
+        *partial = true;
+      }
+      return true;
+    }
+    modules.push((path.clone(), false));
+    false
+  });
+  if is_cycle {
+    return vec![];
+  }
+
+  let documented = DOCUMENTED.with(|modules| modules.borrow().len());
+  let doc_nodes = match get_docs(file_name.clone(), source_code) {
+    Ok(doc_nodes) => doc_nodes,
+    Err(diagnostics) => {
+      let messages: Vec<String> =
+        diagnostics.iter().map(|d| d.message()).collect();
+      eprintln!("Failed to parse {}: {}", file_name, messages.join(", "));
+      vec![]
+    }
+  };
+  let partial = REEXPORTING.with(|modules| {
+    let mut modules = modules.borrow_mut();
+    let partial = modules.iter().any(|(m, partial)| *m == path && *partial);
+    modules.retain(|(m, _)| *m != path);
+    partial
+  });
+  if !partial {
+    let modules =
+      DOCUMENTED.with(|modules| modules.borrow()[documented..].to_vec());
+    let module = CachedModule {
+      doc_nodes: doc_nodes.clone(),
+      modules,
+    };
+    MODULE_CACHE.with(|cache| cache.borrow_mut().insert(file_name, module));
+  }
+  doc_nodes
+}
+
+/// Documentation of a re-exported module, along with the local modules it
+/// was documented from.
+#[derive(Clone)]
+struct CachedModule {
+  doc_nodes: Vec<doc::DocNode>,
+  modules: Vec<String>,
+}
+
+/// Drop the cached documentation of the `changed` modules and of every
+/// module documented from them, so they are parsed again.
+fn invalidate_modules(changed: &[String]) {
+  MODULE_CACHE.with(|cache| {
+    cache.borrow_mut().retain(|file_name, module| {
+      !changed.contains(file_name)
+        && !module.modules.iter().any(|m| changed.contains(m))
+    })
+  });
+}
+
+thread_local! {
+  /// Modules being documented for a re-export, and whether they are part of
+  /// a cycle.
+  static REEXPORTING: std::cell::RefCell<Vec<(std::path::PathBuf, bool)>> =
+    std::cell::RefCell::default();
+  /// Local modules documented for re-exports and imports, which is the
+  /// module graph `workspace --watch` polls.
+  static DOCUMENTED: std::cell::RefCell<Vec<String>> =
+    std::cell::RefCell::default();
+  /// Re-exported modules that were documented already, so `workspace
+  /// --watch` only parses the modules that changed and their re-exporters.
+  static MODULE_CACHE: std::cell::RefCell<
+    std::collections::HashMap<String, CachedModule>,
+  > =
+    std::cell::RefCell::default();
+}
+
+pub fn get_doc_nodes_for_named_export(
+  doc_parser: &DocParser,
+  named_export: &swc_ecma_ast::NamedExport,
+) -> Vec<doc::DocNode> {
+  // Local exports like `export { foo };` aren't supported yet.
+  let specifier = match &named_export.src {
+    Some(src) => src.value.to_string(),
+    None => return vec![],
+  };
+  // Pairs of the original and the exported name.
+  let reexports: Vec<(String, String)> = named_export
+    .specifiers
+    .iter()
+    .filter_map(|export_specifier| {
+      use swc_ecma_ast::ExportSpecifier::*;
+
+      match export_specifier {
+        Named(named_export_specifier) => {
+          let orig = named_export_specifier.orig.sym.to_string();
+          let exported = match &named_export_specifier.exported {
+            Some(exported) => exported.sym.to_string(),
+            None => orig.clone(),
+          };
+          Some((orig, exported))
+        }
+        // TODO:
+        Namespace(_) => None,
+        Default(_) => None,
+      }
+    })
+    .collect();
+  if reexports.is_empty() {
+    return vec![];
+  }
+
+  let doc_nodes =
+    get_docs_for_specifier(doc_parser, named_export.span, &specifier);
+  for (orig, _) in &reexports {
+    if !doc_nodes.iter().any(|doc_node| doc_node.name == *orig) {
+      eprintln!("{} isn't exported by {}", orig, specifier);
+    }
+  }
+
+  let mut reexports_docs = vec![];
+  for mut doc_node in doc_nodes {
+    let exported =
+      match reexports.iter().find(|(orig, _)| *orig == doc_node.name) {
+        Some((_, exported)) => exported,
+        None => continue,
+      };
+    doc_node.is_default = exported == "default";
+    doc_node.is_type_only |= named_export.type_only;
+    doc_node.name = exported.to_string();
+    doc_node.reexported_from = Some(specifier.clone());
+    reexports_docs.push(doc_node);
+  }
+
+  reexports_docs
+}
+
+/// `export * from "./module.ts";` re-exports everything but the default
+/// export.
+pub fn get_doc_nodes_for_export_all(
+  doc_parser: &DocParser,
+  export_all: &swc_ecma_ast::ExportAll,
+) -> Vec<doc::DocNode> {
+  let specifier = export_all.src.value.to_string();
+  get_docs_for_specifier(doc_parser, export_all.span, &specifier)
+    .into_iter()
+    .filter(|doc_node| !doc_node.is_default)
+    .map(|mut doc_node| {
+      doc_node.reexported_from = Some(specifier.clone());
+      doc_node
+    })
+    .collect()
 }
 
 pub fn get_doc_nodes_for_module_decl(
@@ -618,264 +1893,5763 @@ pub fn get_doc_nodes_for_module_decl(
 
   match module_decl {
     ModuleDecl::ExportDecl(export_decl) => {
-      vec![get_doc_node_for_export_decl(doc_parser, export_decl)]
+      get_doc_nodes_for_export_decl(doc_parser, export_decl)
     }
     ModuleDecl::ExportNamed(named_export) => {
       get_doc_nodes_for_named_export(doc_parser, named_export)
     }
-    ModuleDecl::ExportDefaultDecl(_) => vec![],
-    ModuleDecl::ExportDefaultExpr(_) => vec![],
-    ModuleDecl::ExportAll(_) => vec![],
-    ModuleDecl::TsExportAssignment(_) => vec![],
+    ModuleDecl::ExportDefaultDecl(export_default_decl) => {
+      vec![get_doc_node_for_export_default_decl(
+        doc_parser,
+        export_default_decl,
+      )]
+    }
+    ModuleDecl::ExportDefaultExpr(export_default_expr) => {
+      vec![get_doc_node_for_default_expr(
+        doc_parser,
+        export_default_expr.span,
+        &export_default_expr.expr,
+      )]
+    }
+    ModuleDecl::ExportAll(export_all) => {
+      get_doc_nodes_for_export_all(doc_parser, export_all)
+    }
+    // `export = name;` is handled along with the declarations of the module,
+    // see `get_doc_nodes_for_module_body`.
+    ModuleDecl::TsExportAssignment(assignment) => match &*assignment.expr {
+      swc_ecma_ast::Expr::Ident(_) => vec![],
+      expr => vec![get_doc_node_for_default_expr(
+        doc_parser,
+        assignment.span,
+        expr,
+      )],
+    },
     ModuleDecl::TsNamespaceExport(_) => vec![],
     _ => vec![],
   }
 }
 
+/// Document the exports of a module or namespace. In `ambient` ones, like
+/// declaration files, declarations are documented whether or not they're
+/// exported.
 fn get_doc_nodes_for_module_body(
   doc_parser: &DocParser,
   module_body: Vec<swc_ecma_ast::ModuleItem>,
+  ambient: bool,
 ) -> Vec<doc::DocNode> {
+  use swc_ecma_ast::ModuleDecl;
+  use swc_ecma_ast::ModuleItem;
+  use swc_ecma_ast::Stmt;
+
   let mut doc_entries: Vec<doc::DocNode> = vec![];
+  // Whether the last entry is a function overload, which the following
+  // overloads and implementation of the function are merged into.
+  let mut in_overloads = false;
+  let mut star_exports: Vec<doc::DocNode> = vec![];
+  // Name of the symbol exported with `export = name;`, whose declarations
+  // are documented even if they aren't exported themselves.
+  let export_assignment = module_body.iter().find_map(|node| match node {
+    ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(assignment)) => {
+      match &*assignment.expr {
+        swc_ecma_ast::Expr::Ident(ident) => Some(ident.sym.to_string()),
+        _ => None,
+      }
+    }
+    _ => None,
+  });
   for node in module_body.iter() {
-    if let swc_ecma_ast::ModuleItem::ModuleDecl(module_decl) = node {
-      doc_entries
-        .extend(get_doc_nodes_for_module_decl(&doc_parser, module_decl));
+    if let Some(ts_module_decl) = as_augmentation(node) {
+      let doc_node =
+        get_doc_for_ts_module(doc_parser, ts_module_decl.span, ts_module_decl);
+      push_augmentation(&mut doc_entries, doc_node);
+      in_overloads = false;
+      continue;
+    }
+    let (mut doc_nodes, is_overload) = match node {
+      ModuleItem::ModuleDecl(module_decl) => (
+        get_doc_nodes_for_module_decl(doc_parser, module_decl),
+        match module_decl {
+          ModuleDecl::ExportDecl(export_decl) => {
+            is_function_overload(&export_decl.decl)
+          }
+          _ => false,
+        },
+      ),
+      ModuleItem::Stmt(Stmt::Decl(decl))
+        if ambient
+          || export_assignment.is_some()
+            && decl_name(decl) == export_assignment =>
+      {
+        (
+          get_doc_nodes_for_decl(
+            doc_parser,
+            declare_span(&doc_parser.source_map, decl.span()),
+            decl,
+          ),
+          is_function_overload(decl),
+        )
+      }
+      ModuleItem::Stmt(_) => continue,
+    };
+    if in_overloads && doc_nodes.len() == 1 {
+      let last = doc_entries.last_mut().unwrap();
+      if last.name == doc_nodes[0].name
+        && doc_nodes[0].kind == doc::DocNodeKind::Function
+      {
+        let node = doc_nodes.pop().unwrap();
+        merge_overload(last, node, is_overload);
+        in_overloads = is_overload;
+        continue;
+      }
+    }
+    in_overloads = is_overload;
+    if let ModuleItem::ModuleDecl(ModuleDecl::ExportAll(_)) = node {
+      star_exports.extend(doc_nodes);
+    } else {
+      doc_entries.extend(doc_nodes);
+    }
+  }
+  merge_namespaces(&mut doc_entries);
+  // `export = name;` makes the declarations of `name` the default export.
+  if let Some(name) = &export_assignment {
+    for doc_node in doc_entries.iter_mut().filter(|node| &node.name == name) {
+      doc_node.name = "default".to_string();
+      doc_node.is_default = true;
+    }
+  }
+  // Names exported by the module itself take precedence over star exports,
+  // and the first star export of a name wins over the following ones.
+  for doc_node in star_exports {
+    if !doc_entries.iter().any(|entry| entry.name == doc_node.name) {
+      doc_entries.push(doc_node);
     }
   }
   doc_entries
 }
 
+fn is_augmentation(ts_module_decl: &swc_ecma_ast::TsModuleDecl) -> bool {
+  use swc_ecma_ast::TsModuleName;
+  ts_module_decl.global || matches!(ts_module_decl.id, TsModuleName::Str(_))
+}
+
+/// `declare global { ... }` and `declare module "specifier" { ... }` blocks,
+/// which are documented even though they aren't exported.
+fn as_augmentation(
+  item: &swc_ecma_ast::ModuleItem,
+) -> Option<&swc_ecma_ast::TsModuleDecl> {
+  use swc_ecma_ast::Decl;
+  use swc_ecma_ast::ModuleItem;
+  use swc_ecma_ast::Stmt;
+
+  match item {
+    ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(ts_module_decl)))
+      if is_augmentation(ts_module_decl) =>
+    {
+      Some(ts_module_decl)
+    }
+    _ => None,
+  }
+}
+
+/// Blocks augmenting the same scope are documented as one.
+fn push_augmentation(
+  doc_entries: &mut Vec<doc::DocNode>,
+  mut doc_node: doc::DocNode,
+) {
+  let augmentation = doc_node.namespace_def.as_ref().unwrap().augmentation;
+  let previous = doc_entries.iter_mut().find(|entry| {
+    entry.name == doc_node.name
+      && matches!(
+        &entry.namespace_def,
+        Some(def) if def.augmentation == augmentation
+      )
+  });
+  match previous {
+    Some(previous) => {
+      let elements = &mut doc_node.namespace_def.as_mut().unwrap().elements;
+      let previous_def = previous.namespace_def.as_mut().unwrap();
+      previous_def.elements.append(elements);
+      if previous.js_doc.is_none() {
+        previous.js_doc = doc_node.js_doc;
+      }
+    }
+    None => doc_entries.push(doc_node),
+  }
+}
+
+/// Namespaces merged with a function, class, enum, interface or another
+/// namespace of the same name are documented as part of that declaration,
+/// which then has both its own definition and a `namespace_def`.
+fn merge_namespaces(doc_entries: &mut Vec<doc::DocNode>) {
+  use doc::DocNodeKind;
+
+  let is_namespace = |node: &doc::DocNode| {
+    node.kind == DocNodeKind::Namespace
+      && node.namespace_def.as_ref().unwrap().augmentation.is_none()
+  };
+
+  let mut i = 0;
+  while i < doc_entries.len() {
+    if !is_namespace(&doc_entries[i]) {
+      i += 1;
+      continue;
+    }
+
+    let name = &doc_entries[i].name;
+    let declaration = doc_entries.iter().position(|entry| {
+      &entry.name == name
+        && matches!(
+          entry.kind,
+          DocNodeKind::Function
+            | DocNodeKind::Class
+            | DocNodeKind::Enum
+            | DocNodeKind::Interface
+        )
+    });
+    let target = declaration.or_else(|| {
+      doc_entries[..i]
+        .iter()
+        .position(|entry| &entry.name == name && is_namespace(entry))
+    });
+    let target = match target {
+      Some(target) => target,
+      None => {
+        i += 1;
+        continue;
+      }
+    };
+
+    let namespace = doc_entries.remove(i);
+    let target = if target > i { target - 1 } else { target };
+    let merged = &mut doc_entries[target];
+    let mut namespace_def = namespace.namespace_def.unwrap();
+    match merged.namespace_def.as_mut() {
+      Some(merged_def) => {
+        merged_def.elements.append(&mut namespace_def.elements)
+      }
+      None => merged.namespace_def = Some(namespace_def),
+    }
+    if merged.js_doc.is_none() {
+      merged.js_doc = namespace.js_doc;
+    }
+    // Namespaces can declare values, so an interface merged with one isn't
+    // only a type anymore.
+    merged.is_type_only = false;
+  }
+}
+
+/// Name of the symbol declared by `decl`.
+fn decl_name(decl: &swc_ecma_ast::Decl) -> Option<String> {
+  use swc_ecma_ast::Decl;
+
+  let name = match decl {
+    Decl::Class(class_decl) => &class_decl.ident.sym,
+    Decl::Fn(fn_decl) => &fn_decl.ident.sym,
+    Decl::Var(var_decl) => match &var_decl.decls.first()?.name {
+      swc_ecma_ast::Pat::Ident(ident) => &ident.sym,
+      _ => return None,
+    },
+    Decl::TsInterface(interface) => &interface.id.sym,
+    Decl::TsTypeAlias(type_alias) => &type_alias.id.sym,
+    Decl::TsEnum(ts_enum) => &ts_enum.id.sym,
+    Decl::TsModule(ts_module) => match &ts_module.id {
+      swc_ecma_ast::TsModuleName::Ident(ident) => &ident.sym,
+      swc_ecma_ast::TsModuleName::Str(_) => return None,
+    },
+  };
+  Some(name.to_string())
+}
+
+/// Whether `decl` is a function signature without a body.
+fn is_function_overload(decl: &swc_ecma_ast::Decl) -> bool {
+  match decl {
+    swc_ecma_ast::Decl::Fn(fn_decl) => fn_decl.function.body.is_none(),
+    _ => false,
+  }
+}
+
+/// Add the overload `node` to `first`, the first overload of the function.
+/// The implementation only contributes its JSDoc, if none of the overloads
+/// have one.
+fn merge_overload(
+  first: &mut doc::DocNode,
+  node: doc::DocNode,
+  is_overload: bool,
+) {
+  if !is_overload {
+    if first.js_doc.is_none() {
+      first.js_doc = node.js_doc;
+    }
+    return;
+  }
+  let function_def = first.function_def.as_mut().unwrap();
+  function_def.overloads.push(doc::FunctionOverloadDef {
+    js_doc: node.js_doc,
+    snippet: node.snippet,
+    function_def: node.function_def.unwrap(),
+  });
+}
+
 pub fn get_docs(
   file_name: String,
   source_code: String,
 ) -> Result<Vec<doc::DocNode>, SwcDiagnostics> {
-  let doc_parser = DocParser::default();
+  get_module_docs(file_name, source_code).map(|(_, doc_nodes)| doc_nodes)
+}
 
-  swc_common::GLOBALS.set(&swc_common::Globals::new(), || {
-    let swc_source_file = doc_parser
-      .source_map
-      .new_source_file(FileName::Custom(file_name), source_code);
+/// Like `get_docs`, along with the JSDoc of the module itself.
+fn get_module_docs(
+  file_name: String,
+  source_code: String,
+) -> Result<(Option<String>, Vec<doc::DocNode>), SwcDiagnostics> {
+  let references = reference_directives(&source_code);
+  let (module_doc, mut doc_nodes) =
+    parse_module(file_name.clone(), source_code, |doc_parser, module| {
+      let module_doc = take_module_doc(doc_parser, &module);
+      // Declaration files document all their declarations.
+      let ambient = file_name.ends_with(".d.ts");
+      let mut doc_nodes =
+        get_doc_nodes_for_module_body(doc_parser, module.body, ambient);
+      if is_js_file(&file_name) {
+        doc_nodes.extend(get_doc_nodes_for_js_doc_typedefs(doc_parser));
+      }
+      if ambient {
+        for doc_node in doc_nodes.iter_mut() {
+          let is_augmentation = matches!(
+            &doc_node.namespace_def,
+            Some(def) if def.augmentation.is_some()
+          );
+          if !is_augmentation && doc_node.reexported_from.is_none() {
+            doc_node.is_ambient = true;
+          }
+        }
+      }
+      doc::tags::set_tag_fields(&mut doc_nodes);
+      (module_doc, doc_nodes)
+    })?;
+  if is_js_file(&file_name) {
+    set_js_doc_types(&mut doc_nodes);
+  }
+  for specifier in references {
+    doc_nodes.extend(document_specifier(&file_name, &specifier));
+  }
+  set_indexes(&mut doc_nodes);
+  Ok((module_doc, doc_nodes))
+}
 
-    let buffered_err = doc_parser.buffered_error.clone();
-    let session = Session {
-      handler: &doc_parser.handler,
+/// Number symbols in the order they're declared, including namespace
+/// elements.
+fn set_indexes(doc_nodes: &mut [doc::DocNode]) {
+  for (index, node) in doc_nodes.iter_mut().enumerate() {
+    node.index = index;
+    if let Some(namespace_def) = node.namespace_def.as_mut() {
+      set_indexes(&mut namespace_def.elements);
+    }
+  }
+}
+
+/// The first JSDoc block of the file documents the module itself, rather
+/// than the first statement, when it's tagged `@module`, when another JSDoc
+/// block or an import follows it, or when there are no statements at all.
+/// It's taken out of the comments, so the first declaration doesn't get it.
+fn take_module_doc(
+  doc_parser: &DocParser,
+  module: &swc_ecma_ast::Module,
+) -> Option<String> {
+  use swc_common::comments::CommentKind;
+  use swc_ecma_ast::ModuleDecl;
+  use swc_ecma_ast::ModuleItem;
+
+  let first_item = module.body.first();
+  let pos = match first_item {
+    Some(item) => item.span().lo(),
+    None => module.span.lo(),
+  };
+  let mut comments = match first_item {
+    Some(_) => doc_parser.comments.take_leading_comments(pos)?,
+    // Without any code, comments trail the start of the file.
+    None => doc_parser.comments.take_trailing_comments(pos)?,
+  };
+  let mut js_docs = comments.iter().enumerate().filter(|(_, comment)| {
+    comment.kind == CommentKind::Block && comment.text.starts_with('*')
+  });
+  let (index, first) = js_docs.next()?;
+  let js_doc = format!("/*{}*/", first.text);
+
+  // Imports aren't documented, so a JSDoc block before them can't be theirs.
+  let precedes_declaration = match first_item {
+    Some(ModuleItem::ModuleDecl(ModuleDecl::Import(_))) | None => false,
+    Some(_) => true,
+  };
+  let is_module_doc = !precedes_declaration
+    || js_docs.next().is_some()
+    || JsDoc::parse(&js_doc)
+      .tags
+      .iter()
+      .any(|tag| tag.name == "module" || tag.name == "fileoverview");
+  if is_module_doc {
+    comments.remove(index);
+  }
+  doc_parser.comments.add_leading(pos, comments);
+  if is_module_doc {
+    Some(js_doc)
+  } else {
+    None
+  }
+}
+
+/// JavaScript files have no type annotations, their types come from JSDoc.
+fn is_js_file(file_name: &str) -> bool {
+  [".js", ".mjs", ".cjs", ".jsx"]
+    .iter()
+    .any(|extension| file_name.ends_with(extension))
+}
+
+/// Types of JavaScript declarations from their `@param`, `@returns` and
+/// `@type` tags, including namespace elements. As there are no annotations in
+/// JavaScript, these take precedence over types inferred from initializers.
+fn set_js_doc_types(doc_nodes: &mut [doc::DocNode]) {
+  for node in doc_nodes {
+    if let Some(namespace_def) = node.namespace_def.as_mut() {
+      set_js_doc_types(&mut namespace_def.elements);
+    }
+    if let Some(class_def) = node.class_def.as_mut() {
+      for prop in class_def.properties.iter_mut() {
+        if let Some(ts_type) = prop.js_doc.as_deref().and_then(js_doc_type) {
+          prop.ts_type = Some(ts_type);
+        }
+      }
+    }
+
+    let js_doc = match &node.js_doc {
+      Some(js_doc) => JsDoc::parse(js_doc),
+      None => continue,
+    };
+    if let Some(variable_def) = node.variable_def.as_mut() {
+      if let Some(ts_type) = node.js_doc.as_deref().and_then(js_doc_type) {
+        variable_def.ts_type = Some(ts_type);
+      }
+    }
+    let function_def = match node.function_def.as_mut() {
+      Some(function_def) => function_def,
+      None => continue,
     };
+    for param in function_def.params.iter_mut() {
+      let tag = js_doc
+        .tags_named("param")
+        .find(|tag| tag.param_name() == Some(param.name.as_str()));
+      let (ts_type, optional) = match tag.and_then(|tag| tag.param_type()) {
+        Some(type_text) => js_doc_type_to_def(type_text),
+        None => continue,
+      };
+      param.ts_type = Some(ts_type);
+      let bracketed = tag.and_then(|tag| tag.param_token());
+      if param.default.is_none()
+        && (optional
+          || matches!(bracketed, Some(name) if name.starts_with('[')))
+      {
+        param.optional = true;
+      }
+    }
+    let return_type = js_doc
+      .tags
+      .iter()
+      .filter(|tag| tag.name == "returns" || tag.name == "return")
+      .find_map(|tag| tag.param_type());
+    if let Some(type_text) = return_type {
+      function_def.return_type = Some(js_doc_type_to_def(type_text).0);
+    }
+  }
+}
 
-    let mut ts_config = TsConfig::default();
-    ts_config.dynamic_import = true;
-    let syntax = Syntax::Typescript(ts_config);
+/// Type of the `@type` tag of `js_doc`.
+fn js_doc_type(js_doc: &str) -> Option<doc::ts_type::TsTypeDef> {
+  let js_doc = JsDoc::parse(js_doc);
+  let type_text = js_doc.tags_named("type").find_map(|tag| tag.param_type())?;
+  Some(js_doc_type_to_def(type_text).0)
+}
 
-    let lexer = Lexer::new(
-      session,
-      syntax,
-      JscTarget::Es2019,
-      SourceFileInput::from(&*swc_source_file),
-      Some(&doc_parser.comments),
+/// Parse a JSDoc type like a TypeScript type, after translating the
+/// Closure syntax of `*`, `?string`, `!Object`, `Array.<string>` and
+/// `string=`. The flag tells whether the type was marked optional with `=`.
+/// Types that don't parse only have their `repr`.
+fn js_doc_type_to_def(type_text: &str) -> (doc::ts_type::TsTypeDef, bool) {
+  use swc_ecma_ast::Decl;
+  use swc_ecma_ast::ModuleItem;
+  use swc_ecma_ast::Stmt;
+
+  let type_text = type_text.trim();
+  let (type_text, optional) = match strip_suffix(type_text, "=") {
+    Some(type_text) => (type_text, true),
+    None => (type_text, false),
+  };
+  let mut type_text = type_text.replace(".<", "<");
+  if type_text == "*" {
+    type_text = "any".to_string();
+  } else if let Some(nullable) = strip_prefix(&type_text, "?") {
+    type_text = format!("{} | null", nullable);
+  } else if let Some(non_nullable) = strip_prefix(&type_text, "!") {
+    type_text = non_nullable.to_string();
+  }
+
+  let source_code = format!("type T = {};", type_text);
+  let ts_type =
+    parse_module("jsdoc.ts".to_string(), source_code, |doc_parser, module| {
+      match module.body.first() {
+        Some(ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(alias)))) => {
+          Some(ts_type_to_def(&doc_parser.source_map, &alias.type_ann))
+        }
+        _ => None,
+      }
+    });
+  let ts_type = ts_type.ok().flatten().unwrap_or(doc::ts_type::TsTypeDef {
+    repr: type_text,
+    ..Default::default()
+  });
+  (ts_type, optional)
+}
+
+/// Type aliases and interfaces declared with `@typedef` and `@callback` tags,
+/// which take the place of type declarations in JavaScript files. They can
+/// be in any JSDoc block of the file, including ones of other declarations.
+fn get_doc_nodes_for_js_doc_typedefs(
+  doc_parser: &DocParser,
+) -> Vec<doc::DocNode> {
+  let source_file = doc_parser.source_map.files()[0].clone();
+  let source_code = &source_file.src;
+
+  let mut doc_nodes = vec![];
+  let mut offset = 0;
+  while let Some(start) = source_code[offset..].find("/**") {
+    let start = offset + start;
+    let end = match source_code[start + 2..].find("*/") {
+      Some(end) => start + 2 + end + 2,
+      None => break,
+    };
+    offset = end;
+
+    let js_doc = JsDoc::parse(&source_code[start..end]);
+    // Each `@typedef` or `@callback` tag owns the tags following it.
+    let mut typedefs: Vec<(&JsDocTag, Vec<&JsDocTag>)> = vec![];
+    for tag in &js_doc.tags {
+      if tag.name == "typedef" || tag.name == "callback" {
+        typedefs.push((tag, vec![]));
+      } else if let Some((_, tags)) = typedefs.last_mut() {
+        tags.push(tag);
+      }
+    }
+
+    let span = Span::new(
+      swc_common::BytePos(source_file.start_pos.0 + start as u32),
+      swc_common::BytePos(source_file.start_pos.0 + end as u32),
+      Default::default(),
     );
+    for (typedef, tags) in typedefs {
+      let node =
+        js_doc_typedef_to_doc_node(doc_parser, span, &js_doc, typedef, &tags);
+      doc_nodes.extend(node);
+    }
+  }
+  doc_nodes
+}
 
-    let mut parser = Parser::new_from(session, lexer);
+fn js_doc_typedef_to_doc_node(
+  doc_parser: &DocParser,
+  span: Span,
+  js_doc: &JsDoc,
+  typedef: &JsDocTag,
+  tags: &[&JsDocTag],
+) -> Option<doc::DocNode> {
+  let name = typedef.param_name()?.to_string();
+  let location = doc_parser.location_for_span(span);
+  let tags_named = |names: &[&str]| -> Vec<&JsDocTag> {
+    tags
+      .iter()
+      .filter(|tag| names.contains(&tag.name.as_str()))
+      .copied()
+      .collect()
+  };
+  // Optional with `{type=}` or `[name]`.
+  let optional = |tag: &JsDocTag, type_optional: bool| {
+    type_optional
+      || matches!(tag.param_token(), Some(name) if name.starts_with('['))
+  };
 
-    let module =
-      parser
-        .parse_module()
-        .map_err(move |mut err: DiagnosticBuilder| {
-          err.cancel();
-          SwcDiagnostics::from(buffered_err)
-        })?;
+  let mut js_doc_lines: Vec<String> = js_doc
+    .description
+    .lines()
+    .map(|line| line.to_string())
+    .collect();
+  let type_def = typedef.param_type().unwrap_or("Object");
+  let properties = tags_named(&["property", "prop"]);
+
+  let (kind, snippet, type_alias_def, interface_def) = if typedef.name
+    == "typedef"
+    && (type_def == "Object" || type_def == "object")
+    && !properties.is_empty()
+  {
+    let mut members = vec![];
+    let mut property_defs = vec![];
+    for tag in properties {
+      // Nested properties like `options.port` are part of the type of their
+      // parent property.
+      let prop_name = match tag.param_path() {
+        Some(prop_name) if !prop_name.contains('.') => prop_name,
+        _ => continue,
+      };
+      let (ts_type, type_optional) = match tag.param_type() {
+        Some(type_text) => js_doc_type_to_def(type_text),
+        None => (js_doc_type_to_def("any").0, false),
+      };
+      let optional = optional(tag, type_optional);
+      members.push(format!(
+        "  {}{}: {};",
+        prop_name,
+        if optional { "?" } else { "" },
+        ts_type.repr
+      ));
+      let description = tag.param_description();
+      property_defs.push(doc::InterfacePropertyDef {
+        name: prop_name.to_string(),
+        js_doc: if description.is_empty() {
+          None
+        } else {
+          Some(format!("/** {} */", description))
+        },
+        location: location.clone(),
+        ts_type: Some(ts_type),
+        readonly: false,
+        optional,
+      });
+    }
+    let snippet = format!("interface {} {{\n{}\n}}", name, members.join("\n"));
+    let interface_def = doc::InterfaceDef {
+      type_params: vec![],
+      extends: vec![],
+      properties: property_defs,
+      methods: vec![],
+      index_signatures: vec![],
+      call_signatures: vec![],
+      construct_signatures: vec![],
+    };
+    (
+      doc::DocNodeKind::Interface,
+      snippet,
+      None,
+      Some(interface_def),
+    )
+  } else {
+    let ts_type = if typedef.name == "callback" {
+      let mut params = vec![];
+      for tag in tags_named(&["param", "arg", "argument"]) {
+        let param_name = match tag.param_name() {
+          Some(param_name) => param_name,
+          None => continue,
+        };
+        let (ts_type, type_optional) =
+          js_doc_type_to_def(tag.param_type().unwrap_or("any"));
+        params.push(format!(
+          "{}{}: {}",
+          param_name,
+          if optional(tag, type_optional) {
+            "?"
+          } else {
+            ""
+          },
+          ts_type.repr
+        ));
+        let param_doc = format!("@param {}", tag.value);
+        js_doc_lines.extend(param_doc.lines().map(|line| line.to_string()));
+      }
+      let return_type = tags_named(&["returns", "return"])
+        .into_iter()
+        .find_map(|tag| tag.param_type())
+        .unwrap_or("void");
+      let return_type = js_doc_type_to_def(return_type).0;
+      let fn_type = format!("({}) => {}", params.join(", "), return_type.repr);
+      js_doc_type_to_def(&fn_type).0
+    } else {
+      js_doc_type_to_def(type_def).0
+    };
+    let snippet = format!("type {} = {};", name, ts_type.repr);
+    let type_alias_def = doc::TypeAliasDef {
+      ts_type,
+      type_params: vec![],
+    };
+    (
+      doc::DocNodeKind::TypeAlias,
+      snippet,
+      Some(type_alias_def),
+      None,
+    )
+  };
+
+  let js_doc = if js_doc_lines.is_empty() {
+    None
+  } else {
+    let lines: Vec<String> = js_doc_lines
+      .iter()
+      .map(|line| format!(" * {}", line))
+      .collect();
+    Some(format!("/**\n{}\n */", lines.join("\n")))
+  };
+
+  Some(doc::DocNode {
+    kind,
+    name,
+    snippet,
+    location,
+    js_doc,
+    category: None,
+    stability: None,
+    custom_tags: vec![],
+    is_default: false,
+    is_ambient: false,
+    is_type_only: true,
+    index: 0,
+    reexported_from: None,
+    import_statement: None,
+    function_def: None,
+    variable_def: None,
+    enum_def: None,
+    class_def: None,
+    type_alias_def,
+    namespace_def: None,
+    interface_def,
+  })
+}
+
+/// Files referenced by `/// <reference path="..." />` and
+/// `/// <reference types="..." />` directives, which have to come before any
+/// code. Paths are relative to the file even without a leading `./`.
+fn reference_directives(source_code: &str) -> Vec<String> {
+  let mut references = vec![];
+  for line in source_code.lines() {
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+    let directive = match strip_prefix(line, "///") {
+      Some(directive) => directive.trim(),
+      None if line.starts_with("//") => continue,
+      None => break,
+    };
+    if !directive.starts_with("<reference ") {
+      continue;
+    }
+    for attribute in &["path", "types"] {
+      let value =
+        directive
+          .split(&format!("{}=", attribute))
+          .nth(1)
+          .and_then(|rest| {
+            let quote = rest.chars().next()?;
+            rest[1..].split(quote).next()
+          });
+      let value = match value {
+        Some(value) => value,
+        None => continue,
+      };
+      let is_path = value.starts_with('.')
+        || value.starts_with('/')
+        || doc::fetch::is_remote(value);
+      if is_path {
+        references.push(value.to_string());
+      } else if *attribute == "path" {
+        references.push(format!("./{}", value));
+      } else {
+        eprintln!("Can't resolve types {}, skipping it", value);
+      }
+    }
+  }
+  references
+}
+
+/// Number of top-level declarations which aren't exported.
+fn count_internal_declarations(
+  file_name: String,
+  source_code: String,
+) -> Result<usize, SwcDiagnostics> {
+  use swc_ecma_ast::Decl;
+  use swc_ecma_ast::ModuleItem;
+  use swc_ecma_ast::Stmt;
+
+  parse_module(file_name, source_code, |_, module| {
+    module
+      .body
+      .iter()
+      .map(|item| match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
+          var_decl.decls.len()
+        }
+        ModuleItem::Stmt(Stmt::Decl(_)) => 1,
+        _ => 0,
+      })
+      .sum()
+  })
+}
+
+fn parse_module<T>(
+  file_name: String,
+  source_code: String,
+  f: impl FnOnce(&DocParser, swc_ecma_ast::Module) -> T,
+) -> Result<T, SwcDiagnostics> {
+  let doc_parser = DocParser::default();
+
+  swc_common::GLOBALS.set(&swc_common::Globals::new(), || {
+    // Snippets are taken from the original source, while the parser reads
+    // it with newer syntax rewritten. Both start at the same position.
+    let jsx = file_name.ends_with(".tsx") || file_name.ends_with(".jsx");
+    let parser_source_file = SourceMap::default().new_source_file(
+      FileName::Custom(file_name.clone()),
+      doc::newer_syntax::rewrite(&source_code, jsx),
+    );
+    let swc_source_file = doc_parser
+      .source_map
+      .new_source_file(FileName::Custom(file_name.clone()), source_code);
+    debug_assert_eq!(parser_source_file.start_pos, swc_source_file.start_pos);
+
+    let buffered_err = doc_parser.buffered_error.clone();
+    let session = Session {
+      handler: &doc_parser.handler,
+    };
+
+    let mut ts_config = TsConfig::default();
+    ts_config.dynamic_import = true;
+    ts_config.decorators = true;
+    ts_config.tsx = jsx;
+    let syntax = Syntax::Typescript(ts_config);
+
+    let lexer = Lexer::new(
+      session,
+      syntax,
+      JscTarget::Es2019,
+      SourceFileInput::from(&*parser_source_file),
+      Some(&doc_parser.comments),
+    );
+
+    let mut parser = Parser::new_from(session, lexer);
+
+    let module =
+      parser
+        .parse_module()
+        .map_err(move |mut err: DiagnosticBuilder| {
+          // Emitting adds the error to the buffered diagnostics.
+          err.emit();
+          SwcDiagnostics::from(buffered_err)
+        })?;
+
+    Ok(f(&doc_parser, module))
+  })
+}
+
+/// Symbols `file_name` imports from local modules, with `import` statements
+/// of remote modules and namespace imports left out.
+fn build_module_graph(
+  file_name: &str,
+  source_code: String,
+) -> Result<doc::module_graph::ModuleGraph, SwcDiagnostics> {
+  use swc_ecma_ast::ImportSpecifier;
+  use swc_ecma_ast::ModuleDecl;
+  use swc_ecma_ast::ModuleItem;
+
+  // Local and imported name of each symbol.
+  type Names = Vec<(String, String)>;
+
+  // Names imported from each specifier, and whether the import is type-only.
+  let imports =
+    parse_module(file_name.to_string(), source_code, |_, module| {
+      let mut imports: Vec<(String, bool, Names)> = vec![];
+      for item in &module.body {
+        let import_decl = match item {
+          ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+            import_decl
+          }
+          _ => continue,
+        };
+        let names =
+          import_decl.specifiers.iter().filter_map(
+            |specifier| match specifier {
+              ImportSpecifier::Specific(specific) => {
+                let local = specific.local.sym.to_string();
+                let imported = match &specific.imported {
+                  Some(imported) => imported.sym.to_string(),
+                  None => local.clone(),
+                };
+                Some((local, imported))
+              }
+              ImportSpecifier::Default(default) => {
+                Some((default.local.sym.to_string(), "default".to_string()))
+              }
+              ImportSpecifier::Namespace(_) => None,
+            },
+          );
+        imports.push((
+          import_decl.src.value.to_string(),
+          import_decl.type_only,
+          names.collect(),
+        ));
+      }
+      imports
+    })?;
+
+  let mut module_graph = doc::module_graph::ModuleGraph::default();
+  for (specifier, type_only, names) in imports {
+    if names.is_empty() || resolve_specifier(file_name, &specifier).is_none() {
+      continue;
+    }
+    for doc_node in document_specifier(file_name, &specifier) {
+      for (local, imported) in &names {
+        if doc_node.name != *imported {
+          continue;
+        }
+        let imports = if type_only {
+          &mut module_graph.type_imports
+        } else {
+          &mut module_graph.imports
+        };
+        imports.push(doc::module_graph::ImportedSymbol {
+          local_name: local.to_string(),
+          specifier: specifier.clone(),
+          doc_node,
+        });
+        break;
+      }
+    }
+  }
+  Ok(module_graph)
+}
+
+fn read_docs(file_name: String) -> Vec<doc::DocNode> {
+  read_module_docs(file_name).1
+}
+
+fn read_module_docs(file_name: String) -> (Option<String>, Vec<doc::DocNode>) {
+  let source_code = read_source(&file_name);
+  parse_module_docs(file_name, source_code)
+}
+
+fn read_source(file_name: &str) -> String {
+  match doc::fetch::read_module(file_name) {
+    Ok(source_code) => source_code,
+    Err(err) => {
+      eprintln!("{}", err);
+      std::process::exit(1);
+    }
+  }
+}
+
+fn parse_module_docs(
+  file_name: String,
+  source_code: String,
+) -> (Option<String>, Vec<doc::DocNode>) {
+  match get_module_docs(file_name.clone(), source_code) {
+    Ok(docs) => docs,
+    Err(diagnostics) => {
+      let messages: Vec<String> =
+        diagnostics.iter().map(|d| d.message()).collect();
+      eprintln!("Failed to parse {}: {}", file_name, messages.join(", "));
+      std::process::exit(1);
+    }
+  }
+}
+
+/// JSON output of a module: its symbols, preceded by its description as a
+/// node of kind `moduleDoc` if it has one.
+fn module_json<'a>(
+  module_doc: Option<&'a str>,
+  doc_nodes: &'a [doc::DocNode],
+) -> Vec<doc::ModuleJsonNode<'a>> {
+  let module_doc = module_doc.map(|js_doc| doc::ModuleJsonNode::ModuleDoc {
+    kind: "moduleDoc",
+    js_doc,
+  });
+  module_doc
+    .into_iter()
+    .chain(doc_nodes.iter().map(doc::ModuleJsonNode::Symbol))
+    .collect()
+}
+
+fn parse_args(args: &[String], known_flags: &[(&str, bool)]) -> flags::Args {
+  match flags::Args::parse(args, known_flags) {
+    Ok(args) => args,
+    Err(err) => {
+      eprintln!("{}", err);
+      std::process::exit(1);
+    }
+  }
+}
+
+fn file_name_arg(args: &flags::Args) -> String {
+  match args.positional.first() {
+    Some(file_name) => file_name.to_string(),
+    None => {
+      eprintln!("Missing file name");
+      std::process::exit(1);
+    }
+  }
+}
+
+fn lint_command(args: &[String]) {
+  let args =
+    parse_args(args, &[("--check-examples", false), ("--format", true)]);
+  let format = args.value("--format").unwrap_or("text");
+  if !["text", "github", "sarif"].contains(&format) {
+    eprintln!("Unknown format: {}", format);
+    std::process::exit(1);
+  }
+  let file_name = file_name_arg(&args);
+
+  let doc_nodes = read_docs(file_name.clone());
+  let mut diagnostics = doc::lint::lint(&doc_nodes);
+
+  if args.has("--check-examples") {
+    match doc::lint::check_examples(&file_name, &doc_nodes) {
+      Ok(example_diagnostics) => diagnostics.extend(example_diagnostics),
+      Err(err) => {
+        eprintln!("Failed to check examples with `deno check`: {}", err);
+        std::process::exit(1);
+      }
+    }
+  }
+
+  if format == "sarif" {
+    let sarif = doc::lint::to_sarif(&diagnostics);
+    println!("{}", serde_json::to_string_pretty(&sarif).unwrap());
+  }
+
+  for diagnostic in &diagnostics {
+    match format {
+      "github" => println!("{}", diagnostic.to_github_annotation()),
+      "text" => println!(
+        "{}:{}:{} - {} ({})",
+        diagnostic.location.filename,
+        diagnostic.location.line,
+        diagnostic.location.col + 1,
+        diagnostic.message,
+        diagnostic.rule
+      ),
+      _ => {}
+    }
+  }
+
+  if !diagnostics.is_empty() {
+    eprintln!("Found {} problem(s)", diagnostics.len());
+    std::process::exit(1);
+  }
+}
+
+fn test_examples_command(args: &[String]) {
+  let args = parse_args(args, &[]);
+  let file_name = file_name_arg(&args);
+  let doc_nodes = read_docs(file_name.clone());
+  let results = match doc::examples::test_examples(&file_name, &doc_nodes) {
+    Ok(results) => results,
+    Err(err) => {
+      eprintln!("Failed to run examples with `deno test`: {}", err);
+      std::process::exit(1);
+    }
+  };
+
+  let mut failed = 0;
+  for result in &results {
+    let location = &result.example.location;
+    if result.passed {
+      println!(
+        "ok    {} ({}:{})",
+        result.example.symbol, location.filename, location.line
+      );
+    } else {
+      failed += 1;
+      println!(
+        "FAIL  {} ({}:{})",
+        result.example.symbol, location.filename, location.line
+      );
+      if !result.output.is_empty() {
+        println!("{}", result.output);
+      }
+    }
+  }
+
+  println!("\n{} passed; {} failed", results.len() - failed, failed);
+  if failed > 0 {
+    std::process::exit(1);
+  }
+}
+
+fn stats_command(args: &[String]) {
+  let args = parse_args(args, &[]);
+  let file_name = file_name_arg(&args);
+  let source_code = match doc::fetch::read_module(&file_name) {
+    Ok(source_code) => source_code,
+    Err(err) => {
+      eprintln!("{}", err);
+      std::process::exit(1);
+    }
+  };
+  let parsed =
+    get_docs(file_name.clone(), source_code.clone()).and_then(|doc_nodes| {
+      let internal =
+        count_internal_declarations(file_name.clone(), source_code)?;
+      Ok((doc_nodes, internal))
+    });
+  let (doc_nodes, internal) = match parsed {
+    Ok(parsed) => parsed,
+    Err(diagnostics) => {
+      let messages: Vec<String> =
+        diagnostics.iter().map(|d| d.message()).collect();
+      eprintln!("Failed to parse {}: {}", file_name, messages.join(", "));
+      std::process::exit(1);
+    }
+  };
+
+  let stats = doc::stats::Stats::new(&doc_nodes, internal);
+  print!("{}", stats.print());
+}
+
+/// Documentation of the workspace package with `entrypoint`, with excluded
+/// files and symbols left out, along with the local modules it was
+/// documented from.
+fn document_package(
+  entrypoint: &str,
+  exclude_files: &[&str],
+  exclude_symbols: &[&str],
+) -> Result<(Vec<doc::DocNode>, Vec<String>), String> {
+  DOCUMENTED.with(|modules| modules.borrow_mut().clear());
+  let source_code = doc::fetch::read_module(entrypoint)?;
+  let doc_nodes =
+    get_docs(entrypoint.to_string(), source_code).map_err(|diagnostics| {
+      let messages: Vec<String> =
+        diagnostics.iter().map(|d| d.message()).collect();
+      format!("Failed to parse {}: {}", entrypoint, messages.join(", "))
+    })?;
+  let doc_nodes = doc::exclude::exclude_files(doc_nodes, exclude_files);
+  let mut doc_nodes = doc::exclude::exclude_symbols(doc_nodes, exclude_symbols);
+  doc::import::set_import_statements(&mut doc_nodes, entrypoint);
+  let mut files = vec![entrypoint.to_string()];
+  for module in DOCUMENTED.with(|modules| modules.replace(vec![])) {
+    if !files.contains(&module) {
+      files.push(module);
+    }
+  }
+  Ok((doc_nodes, files))
+}
+
+fn workspace_command(args: &[String]) {
+  use doc::workspace::FileWatcher;
+  use doc::workspace::Package;
+  use doc::workspace::WorkspaceIndex;
+
+  let args = parse_args(
+    args,
+    &[
+      ("--out", true),
+      ("--exclude", true),
+      ("--exclude-symbol", true),
+      ("--watch", false),
+    ],
+  );
+  if args.positional.is_empty() {
+    eprintln!(
+      "Usage: deno_doc workspace [--out <dir>] [--watch] <entrypoint>..."
+    );
+    std::process::exit(1);
+  }
+  let exclude_files: Vec<&str> = args.values("--exclude").collect();
+  let exclude_symbols: Vec<&str> = args.values("--exclude-symbol").collect();
+
+  let mut packages: Vec<Package> = vec![];
+  let mut watchers: Vec<FileWatcher> = vec![];
+  for arg in &args.positional {
+    let (name, entrypoint) = doc::workspace::package_name(arg);
+    if exclude_files
+      .iter()
+      .any(|pattern| glob::is_match(pattern, &entrypoint))
+    {
+      continue;
+    }
+    if packages.iter().any(|package| package.name == name) {
+      eprintln!(
+        "Duplicate package name: {}, name it with {}=<entrypoint>",
+        name, name
+      );
+      std::process::exit(1);
+    }
+    let (doc_nodes, files) =
+      match document_package(&entrypoint, &exclude_files, &exclude_symbols) {
+        Ok(docs) => docs,
+        Err(err) => {
+          eprintln!("{}", err);
+          std::process::exit(1);
+        }
+      };
+    watchers.push(FileWatcher::new(&files));
+    packages.push(Package {
+      name,
+      entrypoint,
+      doc_nodes,
+    });
+  }
+
+  let out_dir = std::path::Path::new(args.value("--out").unwrap_or("docs"));
+  let write_json = |file_name: &str, json: &str| {
+    let path = out_dir.join(file_name);
+    if let Err(err) = std::fs::write(&path, json) {
+      eprintln!("Failed to write {}: {}", path.display(), err);
+      std::process::exit(1);
+    }
+  };
+
+  if let Err(err) = std::fs::create_dir_all(out_dir) {
+    eprintln!("Failed to create {}: {}", out_dir.display(), err);
+    std::process::exit(1);
+  }
+  for package in &packages {
+    let json = serde_json::to_string_pretty(&package.doc_nodes).unwrap();
+    write_json(&package.docs_file(), &json);
+  }
+  let index = WorkspaceIndex::new(&packages);
+  let mut index_json = serde_json::to_string_pretty(&index).unwrap();
+  write_json("index.json", &index_json);
+
+  if !args.has("--watch") {
+    return;
+  }
+
+  // Only the changed modules and the modules re-exporting them are parsed
+  // again, and only the outputs whose documentation changed are rewritten.
+  eprintln!("Watching {} packages for changes", packages.len());
+  loop {
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let mut changed = false;
+    for (package, watcher) in packages.iter_mut().zip(watchers.iter_mut()) {
+      let changed_files = watcher.changed();
+      if changed_files.is_empty() {
+        continue;
+      }
+      invalidate_modules(&changed_files);
+
+      match document_package(
+        &package.entrypoint,
+        &exclude_files,
+        &exclude_symbols,
+      ) {
+        Ok((doc_nodes, files)) => {
+          // Imports and re-exports may have been added or removed.
+          *watcher = FileWatcher::new(&files);
+          let json = serde_json::to_string_pretty(&doc_nodes).unwrap();
+          if json == serde_json::to_string_pretty(&package.doc_nodes).unwrap() {
+            continue;
+          }
+          package.doc_nodes = doc_nodes;
+          write_json(&package.docs_file(), &json);
+          eprintln!("Updated {}", package.docs_file());
+          changed = true;
+        }
+        // Keep the last documentation until the file parses again.
+        Err(err) => eprintln!("{}", err),
+      }
+    }
+
+    if changed {
+      let index = WorkspaceIndex::new(&packages);
+      let json = serde_json::to_string_pretty(&index).unwrap();
+      if json != index_json {
+        write_json("index.json", &json);
+        index_json = json;
+      }
+    }
+  }
+}
+
+fn diff_command(args: &[String]) {
+  let args = parse_args(args, &[("--json", false)]);
+  let (old_file, new_file) = match args.positional.as_slice() {
+    [old_file, new_file] => (old_file, new_file),
+    _ => {
+      eprintln!("Usage: deno_doc diff <old file> <new file>");
+      std::process::exit(1);
+    }
+  };
+
+  let old_nodes = read_docs(old_file.to_string());
+  let new_nodes = read_docs(new_file.to_string());
+  let changes = doc::diff::diff(&old_nodes, &new_nodes);
+
+  if args.has("--json") {
+    println!("{}", serde_json::to_string_pretty(&changes).unwrap());
+    return;
+  }
+
+  use doc::diff::ChangeKind;
+  for change in &changes {
+    let marker = match change.kind {
+      ChangeKind::Added => "+",
+      ChangeKind::Removed => "-",
+      ChangeKind::Changed => "~",
+    };
+    println!("{} {} {}", marker, change.symbol_kind, change.path);
+    if let Some(before) = &change.before {
+      for line in before.lines() {
+        println!("    - {}", line);
+      }
+    }
+    if let Some(after) = &change.after {
+      for line in after.lines() {
+        println!("    + {}", line);
+      }
+    }
+  }
+}
+
+/// Stabilities passed to a repeatable flag as `@beta`.
+fn stability_flag_values(name: &str, values: &[&str]) -> Vec<doc::Stability> {
+  values
+    .iter()
+    .map(|value| {
+      let stability =
+        strip_prefix(value, "@").and_then(doc::Stability::from_tag);
+      match stability {
+        Some(stability) => stability,
+        None => {
+          eprintln!(
+            "Invalid value for {}: {}, expected a stability tag like @beta",
+            name, value
+          );
+          std::process::exit(1);
+        }
+      }
+    })
+    .collect()
+}
+
+/// Symbols per page when `--page` is passed without `--limit`.
+const DEFAULT_PAGE_LIMIT: usize = 50;
+
+fn number_flag_value(args: &flags::Args, name: &str) -> Option<usize> {
+  let value = args.value(name)?;
+  match value.parse() {
+    Ok(number) if number > 0 => Some(number),
+    _ => {
+      eprintln!("Invalid value for {}: {}, expected a number", name, value);
+      std::process::exit(1);
+    }
+  }
+}
+
+fn doc_command(args: &[String]) {
+  let args = parse_args(
+    args,
+    &[
+      ("--format", true),
+      ("--wrap-types", false),
+      ("--layout", true),
+      ("--import-url", true),
+      ("--usage", false),
+      ("--tag", true),
+      ("--only", true),
+      ("--exclude", true),
+      ("--exclude-symbol", true),
+      ("--transform", true),
+      ("--tree", false),
+      ("--limit", true),
+      ("--page", true),
+      ("--sort", true),
+      ("--private", false),
+    ],
+  );
+  let file_name = file_name_arg(&args);
+  // The source is read once, it's needed again for the module graph.
+  let source_code = read_source(&file_name);
+  let (module_doc, doc_nodes) =
+    parse_module_docs(file_name.clone(), source_code.clone());
+  // `--exclude` takes stabilities like `@internal` as well as file globs.
+  let (exclude, exclude_files): (Vec<&str>, Vec<&str>) = args
+    .values("--exclude")
+    .partition(|value| value.starts_with('@'));
+  let only: Vec<&str> = args.values("--only").collect();
+  let only = stability_flag_values("--only", &only);
+  let exclude = stability_flag_values("--exclude", &exclude);
+  let exclude_symbols: Vec<&str> = args.values("--exclude-symbol").collect();
+  let specifier = args.value("--import-url").unwrap_or(&file_name);
+  let custom_tags: Vec<doc::tags::CustomTag> = args
+    .values("--tag")
+    .map(doc::tags::CustomTag::parse)
+    .collect();
+
+  let mut transformers: Vec<Box<dyn Transformer>> = vec![
+    Box::new(|doc_nodes| {
+      if only.is_empty() && exclude.is_empty() {
+        return doc_nodes;
+      }
+      doc::tags::filter_by_stability(doc_nodes, &only, &exclude)
+    }),
+    Box::new(|doc_nodes| {
+      doc::exclude::exclude_files(doc_nodes, &exclude_files)
+    }),
+    Box::new(|doc_nodes| {
+      doc::exclude::exclude_symbols(doc_nodes, &exclude_symbols)
+    }),
+    Box::new(|mut doc_nodes: Vec<doc::DocNode>| {
+      if !args.has("--private") {
+        doc::exclude::exclude_non_public_members(&mut doc_nodes);
+      }
+      doc::import::set_import_statements(&mut doc_nodes, specifier);
+      doc::tags::set_custom_tags(&mut doc_nodes, &custom_tags);
+      doc_nodes
+    }),
+  ];
+  for command in args.values("--transform") {
+    transformers.push(Box::new(doc::transform::CommandTransformer {
+      command: command.to_string(),
+    }));
+  }
+  let doc_nodes = match doc::transform::apply(doc_nodes, &transformers) {
+    Ok(doc_nodes) => doc_nodes,
+    Err(err) => {
+      eprintln!("{}", err);
+      std::process::exit(1);
+    }
+  };
+
+  if args.has("--tree") {
+    print!("{}", doc::tree::print_tree(&file_name, &doc_nodes));
+    return;
+  }
+
+  match args.value("--format").unwrap_or("json") {
+    "json" => {
+      let docs_json = module_json(module_doc.as_deref(), &doc_nodes);
+      println!("{}", serde_json::to_string_pretty(&docs_json).unwrap());
+    }
+    "dts" => print!("{}", doc::dts::print_dts(&doc_nodes)),
+    "markdown" => {
+      if let Some(module_doc) = &module_doc {
+        print!("{}", doc::printer::print_module_doc(module_doc));
+      }
+      print!("{}", doc::markdown::print_markdown(&doc_nodes));
+    }
+    "ctags" => print!("{}", doc::ctags::print_ctags(&doc_nodes)),
+    "etags" => print!("{}", doc::ctags::print_etags(&doc_nodes)),
+    "lsif" => print!("{}", doc::lsif::print_lsif(&doc_nodes, specifier)),
+    "dot" => {
+      let graph = doc::graph::SymbolGraph::new(&doc_nodes);
+      print!("{}", doc::dot::print_dot(&graph));
+    }
+    "mermaid" => print!("{}", doc::mermaid::print_mermaid(&doc_nodes)),
+    "symbol-graph" => {
+      let graph = doc::graph::SymbolGraph::new(&doc_nodes);
+      println!("{}", serde_json::to_string_pretty(&graph).unwrap());
+    }
+    "json-schema" => {
+      let schema = doc::json_schema::json_schema(&doc_nodes);
+      println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    }
+    "text" => {
+      let columns = match args.value("--layout").unwrap_or("default") {
+        "default" => false,
+        "columns" => true,
+        layout => {
+          eprintln!("Unknown layout: {}", layout);
+          std::process::exit(1);
+        }
+      };
+      let source_order = match args.value("--sort").unwrap_or("kind") {
+        "kind" => false,
+        "source" => true,
+        sort => {
+          eprintln!("Unknown sort order: {}", sort);
+          std::process::exit(1);
+        }
+      };
+      let page = number_flag_value(&args, "--page");
+      let limit = match number_flag_value(&args, "--limit") {
+        Some(limit) => Some(limit),
+        None if page.is_some() => Some(DEFAULT_PAGE_LIMIT),
+        None => None,
+      };
+      let options = doc::printer::PrinterOptions {
+        wrap_types: args.has("--wrap-types"),
+        columns,
+        usage_stubs: args.has("--usage"),
+        limit,
+        page: page.unwrap_or(1).saturating_sub(1),
+        source_order,
+      };
+      match args.positional.get(1) {
+        Some(symbol) => {
+          let module_graph =
+            build_module_graph(&file_name, source_code).unwrap_or_default();
+          match doc::printer::print_details(
+            &doc_nodes,
+            &module_graph,
+            symbol,
+            &options,
+          ) {
+            Some(details) => print!("{}", details),
+            None => {
+              eprintln!("Symbol not found: {}", symbol);
+              std::process::exit(1);
+            }
+          }
+        }
+        None => {
+          if let Some(module_doc) = &module_doc {
+            print!("{}", doc::printer::print_module_doc(module_doc));
+          }
+          print!("{}", doc::printer::print(&doc_nodes, &options));
+        }
+      }
+    }
+    format => {
+      eprintln!("Unknown format: {}", format);
+      std::process::exit(1);
+    }
+  }
+}
+
+fn main() {
+  let args: Vec<String> = std::env::args().collect();
+
+  match args.get(1).map(|arg| arg.as_str()) {
+    Some("diff") => diff_command(&args[2..]),
+    Some("lint") => lint_command(&args[2..]),
+    Some("stats") => stats_command(&args[2..]),
+    Some("test-examples") => test_examples_command(&args[2..]),
+    Some("workspace") => workspace_command(&args[2..]),
+    _ => doc_command(&args[1..]),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn export_fn() {
+    let source_code = r#"/**
+* Hello there, this is a multiline JSdoc.
+* 
+* It has many lines
+* 
+* Or not that many?
+*/
+export function foo(a: string, b: number): void {
+    console.log("Hello world");
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.kind, doc::DocNodeKind::Function);
+    assert_eq!(
+      entry.js_doc,
+      Some(
+        r#"/**
+* Hello there, this is a multiline JSdoc.
+* 
+* It has many lines
+* 
+* Or not that many?
+*/"#
+          .to_string()
+      )
+    );
+    assert_eq!(
+      entry.snippet,
+      "export function foo(a: string, b: number): void"
+    );
+  }
+
+  #[test]
+  fn export_const() {
+    let source_code =
+            "/** Something about fizzBuzz */\nexport const fizzBuzz = \"fizzBuzz\";\n";
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.kind, doc::DocNodeKind::Variable);
+    assert_eq!(
+      entry.js_doc,
+      Some("/** Something about fizzBuzz */".to_string())
+    );
+    assert_eq!(entry.snippet, "export const fizzBuzz = \"fizzBuzz\";");
+  }
+
+  #[test]
+  fn export_class() {
+    let source_code = r#"
+/** Class doc */
+export class Foobar extends Fizz implements Buzz {
+    private private1: boolean;
+    protected protected1: number;
+    public public1: boolean;
+    public2: number;
+
+    /** Constructor js doc */
+    constructor(name: string, private private2: number, protected protected2: number) {}
+
+    /** Async foo method */
+    async foo(): Promise<void> {
+        //
+    }
+
+    /** Sync bar method */
+    bar(): void {
+        //
+    }
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.kind, doc::DocNodeKind::Class);
+    assert_eq!(entry.js_doc, Some("/** Class doc */".to_string()));
+    assert_eq!(
+      entry.snippet,
+      r#"export class Foobar extends Fizz implements Buzz"#
+    );
+  }
+
+  #[test]
+  fn export_interface() {
+    let source_code = r#"
+/**
+ * Interface js doc
+ */
+export interface Reader {
+    /** Read n bytes */
+    read(buf: Uint8Array, something: unknown): Promise<number>
+}
+    "#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.kind, doc::DocNodeKind::Interface);
+    assert_eq!(
+      entry.js_doc,
+      Some("/**\n * Interface js doc\n */".to_string())
+    );
+    assert_eq!(
+      entry.snippet,
+      r#"export interface Reader {
+    /** Read n bytes */
+    read(buf: Uint8Array, something: unknown): Promise<number>
+}"#
+    );
+  }
+
+  #[test]
+  fn export_type_alias() {
+    let source_code = r#"
+/** Array holding numbers */
+export type NumberArray = Array<number>;
+    "#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.kind, doc::DocNodeKind::TypeAlias);
+    assert_eq!(
+      entry.js_doc,
+      Some("/** Array holding numbers */".to_string())
+    );
+    assert_eq!(entry.snippet, "export type NumberArray = Array<number>;");
+  }
+
+  #[test]
+  fn export_enum() {
+    let source_code = r#"
+/**
+ * Some enum for good measure
+ */
+export enum Hello {
+    World = "world",
+    Fizz = "fizz",
+    Buzz = "buzz",
+}
+    "#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.kind, doc::DocNodeKind::Enum);
+    assert_eq!(
+      entry.js_doc,
+      Some("/**\n * Some enum for good measure\n */".to_string())
+    );
+    assert_eq!(
+      entry.snippet,
+      r#"export enum Hello {
+    World = "world",
+    Fizz = "fizz",
+    Buzz = "buzz",
+}"#
+    );
+  }
+
+  #[test]
+  fn lint_require_returns() {
+    let source_code = r#"
+/**
+ * Documented
+ * @returns the answer
+ */
+export function documented(): number {
+  return 42;
+}
+
+/** Not documented */
+export function undocumented(): Promise<string> {
+  return Promise.resolve("");
+}
+
+/**
+ * Empty tag
+ * @returns
+ */
+export function emptyTag(): string {
+  return "";
+}
+
+export function noReturn(): void {}
+
+export namespace Nested {
+  export function inner(): boolean {
+    return true;
+  }
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let diagnostics = doc::lint::lint(&entries);
+    let messages: Vec<&str> =
+      diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+      messages,
+      vec![
+        "Function `undocumented` returns `Promise<string>` but has no @returns documentation",
+        "Function `emptyTag` returns `string` but has no @returns documentation",
+        "Function `Nested.inner` returns `boolean` but has no @returns documentation",
+      ]
+    );
+    assert!(diagnostics.iter().all(|d| d.rule == "require-returns"));
+    assert_eq!(diagnostics[0].location.line, 11);
+  }
+
+  #[test]
+  fn collect_examples() {
+    let source_code = r#"
+/**
+ * Adds numbers.
+ *
+ * @example
+ * ```ts
+ * add(1, 2);
+ * ```
+ *
+ * ```ts
+ * add(3, 4);
+ * ```
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+export class Foo {
+  /**
+   * @example new Foo().bar();
+   */
+  bar(): void {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let examples = doc::examples::collect_examples(&entries);
+    let examples: Vec<(&str, &str)> = examples
+      .iter()
+      .map(|e| (e.symbol.as_str(), e.code.as_str()))
+      .collect();
+    assert_eq!(
+      examples,
+      vec![
+        ("add", "add(1, 2);\n"),
+        ("add", "add(3, 4);\n"),
+        ("Foo.bar", "new Foo().bar();\n"),
+      ]
+    );
+  }
+
+  #[test]
+  fn lint_param_names() {
+    let source_code = r#"
+/**
+ * @param {string} a first
+ * @param [b=2] second
+ * @param opts.verbose nested
+ * @param c stale
+ */
+export function foo(a: string, b?: number, opts: object): void {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let diagnostics = doc::lint::lint(&entries);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "param-names");
+    assert_eq!(
+      diagnostics[0].message,
+      "@param `c` does not match any parameter of `foo`"
+    );
+  }
+
+  #[test]
+  fn example_test_source() {
+    let source_code = r#"
+/**
+ * @example
+ * ```ts
+ * import { assertEquals } from "https://deno.land/std/testing/asserts.ts";
+ * import { add } from "./mod.ts";
+ * assertEquals(add(1, 2), 3);
+ * ```
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+/** @example sub(2, 1); */
+export function sub(a: number, b: number): number {
+  return a - b;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let examples = doc::examples::collect_examples(&entries);
+    let sources: Vec<String> = examples
+      .iter()
+      .map(|example| {
+        doc::examples::example_test_source(example, "file:///mod.ts", &entries)
+      })
+      .collect();
+    assert_eq!(
+      sources,
+      vec![
+        r#"import { assertEquals } from "https://deno.land/std/testing/asserts.ts";
+import { add } from "./mod.ts";
+
+Deno.test("add example", async () => {
+  assertEquals(add(1, 2), 3);
+});
+"#,
+        r#"import { sub } from "file:///mod.ts";
+
+Deno.test("sub example", async () => {
+  sub(2, 1);
+});
+"#,
+      ]
+    );
+  }
+
+  #[test]
+  fn example_imports() {
+    let source_code = r#"
+/**
+ * @example
+ * ```ts
+ * const server = new Server(helper());
+ * const options: Options = { port: 80 };
+ * connect(options, connect.retries);
+ * server.close();
+ * ```
+ */
+export function connect(options: Options, retries: number): void;
+export namespace connect {
+  const retries: number;
+}
+export interface Options {
+  port: number;
+}
+export default class Server {
+  constructor(options: Options);
+  close(): void;
+}
+declare function helper(): Options;
+declare global {
+  const server: Server;
+}
+"#;
+    let entries =
+      get_docs("server.d.ts".to_string(), source_code.to_string()).unwrap();
+    let examples = doc::examples::collect_examples(&entries);
+    assert_eq!(
+      doc::examples::example_module_source(
+        &examples[0],
+        "file:///server.d.ts",
+        &entries
+      ),
+      r#"import Server, { connect } from "file:///server.d.ts";
+import type { Options } from "file:///server.d.ts";
+
+const server = new Server(helper());
+const options: Options = { port: 80 };
+connect(options, connect.retries);
+server.close();
+"#
+    );
+  }
+
+  #[test]
+  fn export_class_property_init() {
+    let source_code = r#"
+export class Config {
+  retries = 3;
+  name: string = "config";
+  timeout?: number;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    let inits: Vec<Option<&str>> = class_def
+      .properties
+      .iter()
+      .map(|prop| prop.init.as_deref())
+      .collect();
+    assert_eq!(inits, vec![Some("3"), Some("\"config\""), None]);
+  }
+
+  #[test]
+  fn export_const_literal_value() {
+    let source_code = r#"
+export const VERSION = "1.0.3";
+export const offset: number = -1;
+export const opts = { a: 1 };
+export let count = 0;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let literals: Vec<_> = entries
+      .iter()
+      .map(|entry| entry.variable_def.as_ref().unwrap())
+      .map(|variable_def| {
+        (variable_def.init.as_deref(), variable_def.is_literal)
+      })
+      .collect();
+    assert_eq!(
+      literals,
+      vec![
+        (Some("\"1.0.3\""), true),
+        (Some("-1"), true),
+        (Some("{ a: 1 }"), false),
+        (Some("0"), true),
+      ]
+    );
+    let offset_def = entries[1].variable_def.as_ref().unwrap();
+    assert_eq!(offset_def.ts_type.as_ref().unwrap().repr, "number");
+  }
+
+  #[test]
+  fn export_enum_member_values() {
+    let source_code = r#"
+export enum Flags {
+  None,
+  Read = 1 << 1,
+  Write = 1 << 2,
+  ReadWrite = Read | Write,
+  Next,
+  Negative = -(1 + 1),
+  All = ~Flags.None,
+  Name = "na" + `me`,
+  Unknown = "abc".length,
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let enum_def = entries[0].enum_def.as_ref().unwrap();
+    let values: Vec<String> = enum_def
+      .members
+      .iter()
+      .map(|member| match &member.value {
+        Some(value) => value.to_string(),
+        None => "?".to_string(),
+      })
+      .collect();
+    assert_eq!(
+      values,
+      vec!["0", "2", "4", "6", "7", "-2", "-1", "\"name\"", "?"]
+    );
+    assert_eq!(enum_def.members[0].init, None);
+    assert_eq!(enum_def.members[3].init.as_deref(), Some("Read | Write"));
+  }
+
+  #[test]
+  fn render_ts_type_precedence() {
+    let source_code = r#"
+export function f(
+  a: (A | B)[],
+  b: (() => void) | null,
+  c: ((A & B) | C),
+  d: keyof (A | B),
+  e: (keyof T)[],
+  g: A | (B | C),
+  h: ((A)),
+  i: (A extends B ? C : D)[],
+  j: () => (A | B),
+  k: (new (x: number) => Foo)[],
+  l: T["a" | "b"][],
+  m: (A | B) & C,
+): ((string | number)[]) {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let function_def = entries[0].function_def.as_ref().unwrap();
+    let rendered: Vec<String> = function_def
+      .params
+      .iter()
+      .map(|param| {
+        doc::printer::render_ts_type(param.ts_type.as_ref().unwrap())
+      })
+      .collect();
+    assert_eq!(
+      rendered,
+      vec![
+        "(A | B)[]",
+        "(() => void) | null",
+        "A & B | C",
+        "keyof (A | B)",
+        "(keyof T)[]",
+        "A | B | C",
+        "A",
+        "(A extends B ? C : D)[]",
+        "() => A | B",
+        "(new (x: number) => Foo)[]",
+        "T[\"a\" | \"b\"][]",
+        "(A | B) & C",
+      ]
+    );
+    assert_eq!(
+      doc::printer::render_ts_type(function_def.return_type.as_ref().unwrap()),
+      "(string | number)[]"
+    );
+  }
+
+  #[test]
+  fn print_text() {
+    let source_code = r#"
+/** Namespace doc */
+export namespace Ns {
+  export const inner = 1;
+}
+
+export type Alias = string;
+
+/**
+ * Adds numbers.
+ *
+ * @returns sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+/** The version. */
+export const VERSION: string = "1.0.0";
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      r#"function add(a: number, b: number): number
+  Adds numbers.
+
+const VERSION: string = "1.0.0"
+  The version.
+
+type Alias = string
+
+namespace Ns
+  Namespace doc
+
+  const inner = 1
+
+"#
+    );
+  }
+
+  #[test]
+  fn optional_types_and_members() {
+    let source_code = r#"
+export class Foo {
+  a?: [string, number?, (A | B)?];
+  b: string;
+  c?(): void;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    let prop = &class_def.properties[0];
+    assert!(prop.optional);
+    assert!(!class_def.properties[1].optional);
+    assert!(class_def.methods[0].optional);
+    assert_eq!(
+      doc::printer::render_ts_type(prop.ts_type.as_ref().unwrap()),
+      "[string, number?, (A | B)?]"
+    );
+  }
+
+  #[test]
+  fn qualified_type_ref_names() {
+    let source_code = r#"
+export function read(
+  r: Deno.Reader,
+  s: ns.Sub.Type<ns.Item>,
+  t: typeof ns.Sub.value,
+): void {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let params = &entries[0].function_def.as_ref().unwrap().params;
+    let type_ref = params[1].ts_type.as_ref().unwrap().type_ref.as_ref();
+    assert_eq!(type_ref.unwrap().type_name, "ns.Sub.Type");
+    let rendered: Vec<String> = params
+      .iter()
+      .map(|param| {
+        doc::printer::render_ts_type(param.ts_type.as_ref().unwrap())
+      })
+      .collect();
+    assert_eq!(
+      rendered,
+      vec!["Deno.Reader", "ns.Sub.Type<ns.Item>", "typeof ns.Sub.value"]
+    );
+  }
+
+  #[test]
+  fn class_extends_type_args() {
+    let source_code = r#"
+export class Cache extends Map<string, Entry> {}
+export class Plain extends Base {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert_eq!(class_def.extends.as_deref(), Some("Map"));
+    assert_eq!(class_def.super_type_params.len(), 2);
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "class Cache extends Map<string, Entry>\n\nclass Plain extends Base\n\n"
+    );
+  }
+
+  #[test]
+  fn type_alias_type_params() {
+    let source_code = r#"
+export type Pair<A, B = A> = [A, B];
+export type Keys<T extends object> = keyof T;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let type_alias_def = entries[0].type_alias_def.as_ref().unwrap();
+    assert_eq!(type_alias_def.type_params.len(), 2);
+    assert_eq!(type_alias_def.type_params[1].name, "B");
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "type Keys<T extends object> = keyof T\n\ntype Pair<A, B = A> = [A, B]\n\n"
+    );
+  }
+
+  #[test]
+  fn print_wrapped_types() {
+    let source_code = r#"
+export type Shape = "circle" | "square" | { sides: number };
+export type Both = A & (B | C);
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let options = doc::printer::PrinterOptions {
+      wrap_types: true,
+      ..Default::default()
+    };
+    assert_eq!(
+      doc::printer::print(&entries, &options),
+      r#"type Both =
+  & A
+  & (B | C)
+
+type Shape =
+  | "circle"
+  | "square"
+  | { sides: number }
+
+"#
+    );
+  }
+
+  #[test]
+  fn print_columns() {
+    let source_code = r#"
+/** The version.
+ *
+ * Follows semver. */
+export const VERSION: string = "1.0.0";
+export let counter: number;
+export const flag = true;
+export enum Color { Red, Green = "g", Blue = Red + 2 }
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let options = doc::printer::PrinterOptions {
+      columns: true,
+      ..Default::default()
+    };
+    assert_eq!(
+      doc::printer::print(&entries, &options),
+      r#"const  VERSION  string  The version.
+let    counter  number
+const  flag     true
+
+enum Color
+  Red    = 0
+  Green  = "g"
+  Blue   = 2
+
+"#
+    );
+  }
+
+  #[test]
+  fn import_statements() {
+    let source_code = r#"
+export function foo() {}
+export namespace Ns {
+  export const bar = 1;
+}
+"#;
+    let mut entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    doc::import::set_import_statements(
+      &mut entries,
+      "https://deno.land/x/mod/test.ts",
+    );
+    assert_eq!(
+      entries[0].import_statement.as_deref(),
+      Some(r#"import { foo } from "https://deno.land/x/mod/test.ts";"#)
+    );
+    let ns_elements = &entries[1].namespace_def.as_ref().unwrap().elements;
+    assert_eq!(ns_elements[0].import_statement, None);
+
+    doc::import::set_import_statements(&mut entries, "src/test.ts");
+    assert_eq!(
+      entries[0].import_statement.as_deref(),
+      Some(r#"import { foo } from "./src/test.ts";"#)
+    );
+    doc::import::set_import_statements(&mut entries, "/src/test.ts");
+    assert_eq!(
+      entries[0].import_statement.as_deref(),
+      Some(r#"import { foo } from "file:///src/test.ts";"#)
+    );
+  }
+
+  #[test]
+  fn print_details() {
+    let source_code = r#"
+/**
+ * Adds numbers.
+ *
+ * @param a First.
+ * @returns The sum.
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+export namespace Ns {
+  export class Point {
+    /** Horizontal. */
+    x = 0;
+    length(): number {
+      return 0;
+    }
+  }
+}
+"#;
+    let mut entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    doc::import::set_import_statements(&mut entries, "./mod.ts");
+    let options = Default::default();
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "add",
+        &options
+      )
+      .unwrap(),
+      r#"import { add } from "./mod.ts";
+
+function add(a: number, b: number): number
+  Adds numbers.
+
+  @param a First.
+
+  @returns The sum.
+"#
+    );
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Ns.Point",
+        &options
+      )
+      .unwrap(),
+      r#"import { Ns } from "./mod.ts";
+
+class Point
+
+  x = 0;
+    Horizontal.
+
+  length(): number
+"#
+    );
+    assert!(doc::printer::print_details(
+      &entries,
+      &Default::default(),
+      "Ns.Nope",
+      &options
+    )
+    .is_none());
+  }
+
+  #[test]
+  fn print_member_details() {
+    let source_code = r#"
+export class Point {
+  /** Horizontal. */
+  x = 0;
+  /** Distance to the origin. */
+  length(): number;
+  length(unit: string): number;
+  length(unit?: string): number {
+    return 0;
+  }
+}
+export interface Shape {
+  /** Area of the shape. */
+  area(): number;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let options = Default::default();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert_eq!(class_def.properties[0].location.line, 4);
+    assert_eq!(class_def.methods[0].location.line, 6);
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Point#x",
+        &options
+      )
+      .unwrap(),
+      "x = 0;\n  Horizontal.\n\n  Defined at test.ts:4:3\n"
+    );
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Shape#area",
+        &options
+      )
+      .unwrap(),
+      "area(): number\n  Area of the shape.\n\n  Defined at test.ts:14:3\n"
+    );
+    let length = doc::printer::print_details(
+      &entries,
+      &Default::default(),
+      "Point#length",
+      &options,
+    )
+    .unwrap();
+    assert!(length.contains("Defined at test.ts:7:3\n"));
+    assert!(doc::printer::print_details(
+      &entries,
+      &Default::default(),
+      "Point#y",
+      &options
+    )
+    .is_none());
+  }
+
+  #[test]
+  fn usage_stubs() {
+    let source_code = r#"
+export function add(a: number, b: number | string): number {
+  return 0;
+}
+export async function load(url: URL, opts: { cache: boolean }, tags: string[]): Promise<void> {}
+export function log(level: "info" | "warn", point: [number, number?], cb: (x: number) => void) {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let stubs: Vec<String> =
+      entries.iter().map(doc::usage::usage_stub).collect();
+    assert_eq!(
+      stubs,
+      vec![
+        "const result = add(0, 0);",
+        "await load({} as URL, {} as { cache: boolean }, []);",
+        "log(\"info\", [0], () => {});",
+      ]
+    );
+  }
+
+  #[test]
+  fn json_schema() {
+    let source_code = r#"
+/** Server configuration. */
+export interface Config {
+  /** Port to listen on. */
+  port: number;
+  readonly host?: string;
+  mode: "dev" | "prod";
+  tags: string[];
+  tls: Tls | null;
+  limits: [number, number?];
+}
+export type Tls = Record<string, boolean>;
+export function notAType() {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let schema = doc::json_schema::json_schema(&entries);
+    let expected = serde_json::json!({
+      "$schema": "http://json-schema.org/draft-07/schema#",
+      "definitions": {
+        "Config": {
+          "type": "object",
+          "description": "Server configuration.",
+          "properties": {
+            "port": { "type": "number", "description": "Port to listen on." },
+            "host": { "type": "string", "readOnly": true },
+            "mode": { "enum": ["dev", "prod"] },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "tls": {
+              "anyOf": [{ "$ref": "#/definitions/Tls" }, { "type": "null" }]
+            },
+            "limits": {
+              "type": "array",
+              "items": [{ "type": "number" }, { "type": "number" }],
+              "minItems": 1,
+              "maxItems": 2
+            }
+          },
+          "required": ["port", "mode", "tags", "tls", "limits"]
+        },
+        "Tls": {
+          "type": "object",
+          "additionalProperties": { "type": "boolean" }
+        }
+      }
+    });
+    assert_eq!(schema, expected);
+  }
+
+  #[test]
+  fn json_schema_extends() {
+    let source_code = r#"
+export interface Base {
+  id: string;
+}
+/** A user. */
+export interface User extends Base, Partial<Other> {
+  name: string;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let schema = doc::json_schema::json_schema(&entries);
+    assert_eq!(
+      schema["definitions"]["User"],
+      serde_json::json!({
+        "allOf": [
+          { "$ref": "#/definitions/Base" },
+          {
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+          }
+        ],
+        "description": "A user."
+      })
+    );
+  }
+
+  #[test]
+  fn print_dts() {
+    let source_code = r#"
+/**
+ * Adds numbers.
+ * @param a First.
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+export const VERSION = "1.0";
+export class Point extends Base<number> {
+  private secret: string;
+  static readonly origin?: Point;
+  x = 0;
+  constructor(x: number) {}
+  async load(): Promise<void> {}
+}
+export enum Color { Red, Green = "g" }
+export interface Named {
+  name: string;
+}
+export namespace Ns {
+  export type Id<T = string> = T | number;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(
+      doc::dts::print_dts(&entries),
+      r#"/**
+ * Adds numbers.
+ * @param a First.
+ */
+export declare function add(a: number, b: number): number;
+
+export declare const VERSION = "1.0";
+
+export declare class Point extends Base<number> {
+  constructor(x: number);
+  private secret: string;
+  static readonly origin?: Point;
+  x: number;
+  load(): Promise<void>;
+}
+
+export declare enum Color {
+  Red = 0,
+  Green = "g",
+}
+
+export interface Named {
+  name: string;
+}
+
+export declare namespace Ns {
+  export type Id<T = string> = T | number;
+}
+"#
+    );
+  }
+
+  #[test]
+  fn print_dts_multi_line_tags() {
+    let source_code = r#"
+/**
+ * Greets.
+ * @example
+ * ```ts
+ * greet();
+ * ```
+ * @deprecated
+ */
+export function greet() {}
+export class Config {
+  static y = "s";
+  static readonly z = "s";
+}
+export default function () {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(
+      doc::dts::print_dts(&entries),
+      r#"/**
+ * Greets.
+ * @example
+ * ```ts
+ * greet();
+ * ```
+ * @deprecated
+ */
+export declare function greet(): void;
+
+export declare class Config {
+  static y: string;
+  static readonly z: "s";
+}
+
+declare function _default(): void;
+export default _default;
+"#
+    );
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "greet",
+        &Default::default()
+      )
+      .unwrap(),
+      r#"function greet()
+  Greets.
+
+  @example
+  ```ts
+  greet();
+  ```
+
+  @deprecated
+"#
+    );
+  }
+
+  #[test]
+  fn print_dts_methods() {
+    let source_code = r#"
+export class Queue<T> {
+  *[Symbol.iterator](): Iterator<T> {}
+  static of<T>(items: T[], limit: number = 10): Queue<T> {
+    return new Queue();
+  }
+  async drain(timeout = 0): Promise<void> {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(
+      doc::dts::print_dts(&entries),
+      r#"export declare class Queue<T> {
+  [Symbol.iterator](): Iterator<T>;
+  static of<T>(items: T[], limit?: number): Queue<T>;
+  drain(timeout?): Promise<void>;
+}
+"#
+    );
+  }
+
+  #[test]
+  fn print_dts_default_exports() {
+    let dts = |source_code: &str| {
+      let entries =
+        get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+      doc::dts::print_dts(&entries)
+    };
+
+    assert_eq!(
+      dts("/** Start a server. */\nexport default function serve(port: number) {}"),
+      "/**\n * Start a server.\n */\n\
+       declare function _default(port: number): void;\n\
+       export default _default;\n"
+    );
+    assert_eq!(
+      dts("export default class Server {}"),
+      "declare class _default {\n}\nexport default _default;\n"
+    );
+    assert_eq!(
+      dts("export default 80;"),
+      "declare const _default = 80;\nexport default _default;\n"
+    );
+  }
+
+  #[test]
+  fn print_markdown() {
+    let source_code = r#"
+export interface ServeOptions {
+  /** Port to listen on. */
+  port?: number;
+  host: string;
+}
+
+/**
+ * Start a server.
+ *
+ * @param handler - Handles requests.
+ * @param [options.port=8000] The port.
+ * @param {boolean} options.tls Use TLS.
+ */
+export function serve(handler: (req: Request) => Response, options: ServeOptions) {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let markdown = doc::markdown::print_markdown(&entries);
+    let section = &markdown[markdown.find("## serve").unwrap()..];
+    assert_eq!(
+      section,
+      r#"## serve
+
+```ts
+function serve(handler: (req: Request) => Response, options: ServeOptions)
+```
+
+Start a server.
+
+| Name | Type | Default | Description |
+| --- | --- | --- | --- |
+| `handler` | `(req: Request) => Response` |  | Handles requests. |
+| `options` | `ServeOptions` |  |  |
+| `options.port?` | `number` | `8000` | The port. |
+| `options.host` | `string` |  |  |
+| `options.tls` | `boolean` |  | Use TLS. |
+
+"#
+    );
+  }
+
+  #[test]
+  fn diff_changes() {
+    let old_source = r#"
+export function foo(a: string): void {}
+export const removed = 1;
+export class Point {
+  x: number;
+}
+"#;
+    let new_source = r#"
+export function foo(a: number): void {}
+export class Point {
+  x: number;
+  y: number;
+}
+"#;
+    let old_entries =
+      get_docs("old.ts".to_string(), old_source.to_string()).unwrap();
+    let new_entries =
+      get_docs("new.ts".to_string(), new_source.to_string()).unwrap();
+    let changes = doc::diff::diff(&old_entries, &new_entries);
+    assert_eq!(
+      serde_json::to_value(&changes).unwrap(),
+      serde_json::json!([
+        {
+          "kind": "changed",
+          "path": "foo",
+          "symbolKind": "function",
+          "before": "function foo(a: string): void",
+          "after": "function foo(a: number): void"
+        },
+        {
+          "kind": "removed",
+          "path": "removed",
+          "symbolKind": "variable",
+          "before": "const removed = 1",
+          "after": null
+        },
+        {
+          "kind": "added",
+          "path": "Point.y",
+          "symbolKind": "property",
+          "before": null,
+          "after": "y: number;"
+        }
+      ])
+    );
+  }
+
+  #[test]
+  fn lint_github_annotation() {
+    let diagnostic = doc::lint::LintDiagnostic {
+      rule: "check-examples",
+      message: "Example does not type check:\n100% wrong".to_string(),
+      location: doc::Location {
+        filename: "src/a,b.ts".to_string(),
+        line: 3,
+        col: 0,
+        ..Default::default()
+      },
+    };
+    assert_eq!(
+      diagnostic.to_github_annotation(),
+      "::error file=src/a%2Cb.ts,line=3,col=1,title=check-examples::Example does not type check:%0A100%25 wrong"
+    );
+  }
+
+  #[test]
+  fn lint_sarif() {
+    let source_code = r#"
+export function foo(): number {
+  return 1;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let sarif = doc::lint::to_sarif(&doc::lint::lint(&entries));
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "deno_doc");
+    assert_eq!(run["results"].as_array().unwrap().len(), 1);
+    assert_eq!(
+      run["results"][0],
+      serde_json::json!({
+        "ruleId": "require-returns",
+        "level": "error",
+        "message": {
+          "text": "Function `foo` returns `number` but has no @returns documentation"
+        },
+        "locations": [{
+          "physicalLocation": {
+            "artifactLocation": { "uri": "test.ts" },
+            "region": { "startLine": 2, "startColumn": 1 }
+          }
+        }]
+      })
+    );
+  }
+
+  #[test]
+  fn stats() {
+    let source_code = r#"
+/** Adds numbers. */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+/** A point. */
+export interface Point {
+  x: number;
+  y: number;
+}
+export type Id = string;
+export namespace Ns {
+  export const inner = 1;
+}
+function helper() {}
+const a = 1, b = 2;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let internal = count_internal_declarations(
+      "test.ts".to_string(),
+      source_code.to_string(),
+    )
+    .unwrap();
+    let stats = doc::stats::Stats::new(&entries, internal);
+    assert_eq!(
+      stats.print(),
+      r#"Symbols: 5 exported, 3 internal
+  function    1
+  variable    1
+  interface   1
+  type alias  1
+  namespace   1
+
+Documented: 2 of 5, 10 characters on average
+
+Largest types:
+  Point (interface, 4 lines)
+  Id (type alias, 1 lines)
+"#
+    );
+  }
+
+  #[test]
+  fn workspace_index() {
+    use doc::workspace::Package;
+
+    let packages: Vec<Package> = vec![
+      ("http", "export function serve() {}"),
+      ("log", "export namespace log { export function info() {} }"),
+    ]
+    .into_iter()
+    .map(|(name, source_code)| Package {
+      name: name.to_string(),
+      entrypoint: format!("{}/mod.ts", name),
+      doc_nodes: get_docs(format!("{}/mod.ts", name), source_code.to_string())
+        .unwrap(),
+    })
+    .collect();
+
+    let index = doc::workspace::WorkspaceIndex::new(&packages);
+    assert_eq!(
+      serde_json::to_value(&index).unwrap(),
+      serde_json::json!({
+        "packages": [
+          { "name": "http", "entrypoint": "http/mod.ts", "docs": "http.json" },
+          { "name": "log", "entrypoint": "log/mod.ts", "docs": "log.json" }
+        ],
+        "symbols": [
+          { "name": "log", "kind": "namespace", "package": "log" },
+          { "name": "log.info", "kind": "function", "package": "log" },
+          { "name": "serve", "kind": "function", "package": "http" }
+        ]
+      })
+    );
+    assert_eq!(
+      doc::workspace::package_name("std=./mod.ts"),
+      ("std".to_string(), "./mod.ts".to_string())
+    );
+  }
+
+  #[test]
+  fn workspace_watch() {
+    use doc::workspace::FileWatcher;
+
+    let dir = std::env::temp_dir()
+      .join(format!("deno_doc_watch_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+    std::fs::write(
+      path("mod.ts"),
+      "export * from \"./server.ts\";\nexport function connect() {}",
+    )
+    .unwrap();
+    std::fs::write(
+      path("server.ts"),
+      "export * from \"./types.ts\";\nexport * from \"./http.ts\";\n\
+       export function serve() {}",
+    )
+    .unwrap();
+    std::fs::write(path("types.ts"), "export interface Options {}").unwrap();
+    std::fs::write(path("http.ts"), "export const STATUS = 200;").unwrap();
+
+    let (doc_nodes, files) =
+      document_package(&path("mod.ts"), &[], &[]).unwrap();
+    assert_eq!(doc_nodes.len(), 4);
+    // Modules re-exported through other modules are watched as well.
+    let canonical: Vec<std::path::PathBuf> = files
+      .iter()
+      .map(|file| std::fs::canonicalize(file).unwrap())
+      .collect();
+    let canonical_dir = std::fs::canonicalize(&dir).unwrap();
+    assert_eq!(
+      canonical,
+      vec![
+        canonical_dir.join("mod.ts"),
+        canonical_dir.join("server.ts"),
+        canonical_dir.join("types.ts"),
+        canonical_dir.join("http.ts")
+      ]
+    );
+
+    let mut watcher = FileWatcher::new(&files);
+    assert!(watcher.changed().is_empty());
+    std::fs::remove_file(path("types.ts")).unwrap();
+    let changed = watcher.changed();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(std::path::Path::new(&changed[0]), dir.join("types.ts"));
+    assert!(watcher.changed().is_empty());
+    std::fs::write(path("types.ts"), "export interface Settings {}").unwrap();
+    let changed = watcher.changed();
+    assert_eq!(changed.len(), 1);
+
+    // `http.ts` is unrelated to the change, so its cached documentation is
+    // used even though the file now says otherwise.
+    std::fs::write(path("http.ts"), "export const CODE = 200;").unwrap();
+    invalidate_modules(&changed);
+    let (doc_nodes, _) = document_package(&path("mod.ts"), &[], &[]).unwrap();
+    let names: Vec<&str> =
+      doc_nodes.iter().map(|node| node.name.as_str()).collect();
+    assert!(names.contains(&"Settings"));
+    assert!(!names.contains(&"Options"));
+    assert!(names.contains(&"STATUS"));
+    assert!(!names.contains(&"CODE"));
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn group_by_category() {
+    let source_code = r#"
+/** @category Networking */
+export function serve() {}
+export function version() {}
+/**
+ * Connect.
+ * @group Networking
+ */
+export function connect() {}
+/** @category Encoding */
+export const encoding = "utf-8";
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].category.as_deref(), Some("Networking"));
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      r#"function version()
+
+Encoding
+  const encoding = "utf-8"
+
+Networking
+  function connect()
+    Connect.
+
+  function serve()
+
+"#
+    );
+    let markdown = doc::markdown::print_markdown(&entries);
+    let headings: Vec<&str> = markdown
+      .lines()
+      .filter(|line| line.starts_with('#'))
+      .collect();
+    assert_eq!(
+      headings,
+      vec![
+        "## version",
+        "## Encoding",
+        "### encoding",
+        "## Networking",
+        "### serve",
+        "### connect",
+      ]
+    );
+  }
+
+  #[test]
+  fn custom_tags() {
+    let source_code = r#"
+/**
+ * Start a server.
+ * @since 1.2
+ * @permissions net, read
+ * @unknown ignored
+ */
+export function serve() {}
+"#;
+    let mut entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let custom_tags = vec![
+      doc::tags::CustomTag::parse("permissions=Required permissions"),
+      doc::tags::CustomTag::parse("since"),
+    ];
+    doc::tags::set_custom_tags(&mut entries, &custom_tags);
+    assert_eq!(
+      serde_json::to_value(&entries[0].custom_tags).unwrap(),
+      serde_json::json!([
+        { "name": "since", "label": "since", "value": "1.2" },
+        {
+          "name": "permissions",
+          "label": "Required permissions",
+          "value": "net, read"
+        }
+      ])
+    );
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function serve()\n  Start a server.\n  since: 1.2\n  Required permissions: net, read\n\n"
+    );
+  }
+
+  #[test]
+  fn stability_tags() {
+    let source_code = r#"
+/**
+ * Start a server.
+ * @beta
+ */
+export function serve() {}
+/** @experimental */
+export namespace unstable {
+  /** @internal */
+  export const secret = 1;
+}
+/** @public */
+export const stable = 1;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].stability, Some(doc::Stability::Beta));
+    assert_eq!(entries[2].stability, Some(doc::Stability::Public));
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      r#"function serve() [beta]
+  Start a server.
+
+const stable = 1
+
+namespace unstable [experimental]
+
+  const secret = 1 [internal]
+
+"#
+    );
+  }
+
+  #[test]
+  fn filter_by_stability() {
+    use doc::Stability;
+
+    let source_code = r#"
+export function stable() {}
+/** @alpha */
+export function early() {}
+/** @beta */
+export namespace preview {
+  export const inherited = 1;
+  /** @public */
+  export const published = 1;
+  /** @alpha */
+  export const earlier = 1;
+}
+"#;
+    let names = |doc_nodes: &[doc::DocNode]| -> Vec<String> {
+      let mut names = vec![];
+      for node in doc_nodes {
+        names.push(node.name.to_string());
+        if let Some(namespace_def) = &node.namespace_def {
+          for element in &namespace_def.elements {
+            names.push(format!("{}.{}", node.name, element.name));
+          }
+        }
+      }
+      names
+    };
+    let entries =
+      || get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+
+    let excluded =
+      doc::tags::filter_by_stability(entries(), &[], &[Stability::Alpha]);
+    assert_eq!(
+      names(&excluded),
+      vec![
+        "stable",
+        "preview",
+        "preview.inherited",
+        "preview.published"
+      ]
+    );
+
+    let only = doc::tags::filter_by_stability(
+      entries(),
+      &[Stability::Public, Stability::Beta],
+      &[],
+    );
+    assert_eq!(
+      names(&only),
+      vec![
+        "stable",
+        "preview",
+        "preview.inherited",
+        "preview.published"
+      ]
+    );
+
+    let public =
+      doc::tags::filter_by_stability(entries(), &[Stability::Public], &[]);
+    assert_eq!(names(&public), vec!["stable"]);
+  }
+
+  #[test]
+  fn symbol_graph() {
+    let source_code = r#"
+export interface Options {
+  level: Level;
+}
+export type Level = "info" | "warn";
+export namespace log {
+  export class Logger extends Base {
+    options: Options;
+  }
+  export function create(options?: Partial<Options>): Logger {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let graph = doc::graph::SymbolGraph::new(&entries);
+    let ids: Vec<&str> =
+      graph.nodes.iter().map(|node| node.id.as_str()).collect();
+    assert_eq!(
+      ids,
+      vec![
+        "Options",
+        "Options.level",
+        "Level",
+        "log",
+        "log.Logger",
+        "log.Logger.options",
+        "log.create"
+      ]
+    );
+    let relationships: Vec<String> = graph
+      .relationships
+      .iter()
+      .map(|r| {
+        format!(
+          "{} {:?} {}{}",
+          r.source,
+          r.kind,
+          r.target,
+          if r.external { " (external)" } else { "" }
+        )
+      })
+      .collect();
+    assert_eq!(
+      relationships,
+      vec![
+        "Options.level MemberOf Options",
+        "log.Logger MemberOf log",
+        "log.Logger.options MemberOf log.Logger",
+        "log.create MemberOf log",
+        "log.Logger Extends Base (external)",
+        "Options.level References Level",
+        "log.Logger.options References Options",
+        "log.create References Partial (external)",
+        "log.create References Options",
+        "log.create References log.Logger",
+      ]
+    );
+  }
+
+  #[test]
+  fn print_tags_files() {
+    let source_code = r#"
+export function serve() {}
+export namespace log {
+  export const level = 1;
+}
+"#;
+    let entries =
+      get_docs("mod.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(
+      doc::ctags::print_ctags(&entries),
+      "!_TAG_FILE_FORMAT\t2\t/extended format/\n\
+       !_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/\n\
+       !_TAG_PROGRAM_NAME\tdeno_doc\t//\n\
+       level\tmod.ts\t4;\"\tv\tnamespace:log\n\
+       log\tmod.ts\t3;\"\tn\n\
+       serve\tmod.ts\t2;\"\tf\n"
+    );
+    let section = "export function serve\u{7f}serve\u{1}2,\n\
+                   export namespace log\u{7f}log\u{1}3,\n\
+                   export const level\u{7f}level\u{1}4,\n";
+    assert_eq!(
+      doc::ctags::print_etags(&entries),
+      format!("\u{c}\nmod.ts,{}\n{}", section.len(), section)
+    );
+  }
+
+  #[test]
+  fn print_lsif() {
+    let source_code = r#"
+/** Start a server. */
+export function serve() {}
+export namespace log {
+  export const level = 1;
+}
+"#;
+    let entries =
+      get_docs("mod.ts".to_string(), source_code.to_string()).unwrap();
+    let lsif = doc::lsif::print_lsif(&entries, "https://deno.land/x/mod.ts");
+    let elements: Vec<serde_json::Value> = lsif
+      .lines()
+      .map(|line| serde_json::from_str(line).unwrap())
+      .collect();
+    let with_label = |label: &str| -> Vec<&serde_json::Value> {
+      elements
+        .iter()
+        .filter(|e| e["type"] == "vertex" && e["label"] == label)
+        .collect()
+    };
+
+    assert_eq!(with_label("metaData").len(), 1);
+    assert_eq!(with_label("document").len(), 1);
+    let ranges = with_label("range");
+    assert_eq!(ranges.len(), 3);
+    assert_eq!(
+      ranges[2]["start"],
+      serde_json::json!({ "line": 4, "character": 15 })
+    );
+    assert_eq!(
+      ranges[2]["end"],
+      serde_json::json!({ "line": 4, "character": 20 })
+    );
+    assert_eq!(
+      with_label("hoverResult")[0]["result"]["contents"],
+      serde_json::json!([
+        { "language": "typescript", "value": "function serve()" },
+        "Start a server."
+      ])
+    );
+    assert_eq!(
+      with_label("moniker")[2]["identifier"],
+      "https://deno.land/x/mod.ts#log.level"
+    );
+    for (i, element) in elements.iter().enumerate() {
+      assert_eq!(element["id"], i + 1);
+    }
+  }
+
+  #[test]
+  fn print_dot() {
+    let source_code = r#"
+export interface Options {
+  level: Level;
+}
+export type Level = "info" | "warn";
+export namespace log {
+  export class Logger extends Base {
+    options: Options;
+  }
+  export class FileLogger extends Logger {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let graph = doc::graph::SymbolGraph::new(&entries);
+    let expected = r#"digraph {
+  rankdir=LR;
+  "Options" [label="interface Options", shape=box];
+  "Level" [label="typeAlias Level", shape=ellipse];
+  subgraph "cluster_log" {
+    label="namespace log";
+    "log.Logger" [label="class Logger", shape=box];
+    "log.FileLogger" [label="class FileLogger", shape=box];
+  }
+  "log.FileLogger" -> "log.Logger" [arrowhead=empty];
+  "Options" -> "Level" [style=dashed];
+  "log.Logger" -> "Options" [style=dashed];
+}
+"#;
+    assert_eq!(doc::dot::print_dot(&graph), expected);
+  }
+
+  #[test]
+  fn print_mermaid() {
+    let source_code = r#"
+export interface Writer {
+  write(data: Uint8Array): number;
+  path?: string;
+}
+export interface Closer {}
+export interface File extends Writer, Closer {}
+export namespace log {
+  export abstract class Logger<T> implements Writer {
+    private level: number;
+    static handlers: Array<string>;
+    abstract flush(): void;
+    get name(): string {}
+  }
+  export class FileLogger extends Logger implements Writer, Closer {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let expected = r#"classDiagram
+  class Writer {
+    <<interface>>
+    +path : string
+    +write() number
+  }
+  class Closer {
+    <<interface>>
+  }
+  class File {
+    <<interface>>
+  }
+  class log_Logger["log.Logger"] {
+    <<abstract>>
+    -level : number
+    +handlers : Array~string~$
+    +name : string
+    +flush()*
+  }
+  class log_FileLogger["log.FileLogger"] {
+  }
+  Writer <|-- File
+  Closer <|-- File
+  Writer <|.. log_Logger
+  log_Logger <|-- log_FileLogger
+  Writer <|.. log_FileLogger
+  Closer <|.. log_FileLogger
+"#;
+    assert_eq!(doc::mermaid::print_mermaid(&entries), expected);
+    assert_eq!(
+      doc::printer::print(&entries[..3], &Default::default()),
+      "interface Closer\n\ninterface File extends Writer, Closer\n\n\
+       interface Writer\n\n"
+    );
+  }
+
+  #[test]
+  fn print_tree() {
+    let source_code = r#"
+export namespace http {
+  export class Server {
+    constructor() {}
+    port: number;
+    close() {}
+  }
+  export enum Status { Ok, NotFound }
+}
+export interface Options {
+  port: number;
+}
+export function serve() {}
+"#;
+    let entries =
+      get_docs("mod.ts".to_string(), source_code.to_string()).unwrap();
+    let expected = "mod.ts
+├── http
+│   ├── Server
+│   │   ├── constructor
+│   │   ├── port
+│   │   └── close
+│   └── Status
+│       ├── Ok
+│       └── NotFound
+├── Options
+│   └── port
+└── serve
+";
+    assert_eq!(doc::tree::print_tree("mod.ts", &entries), expected);
+  }
+
+  #[test]
+  fn print_pages() {
+    let source_code = r#"
+export function a() {}
+export function b() {}
+/** @category Util */
+export function c() {}
+export function d() {}
+export function e() {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let page = |page| {
+      let options = doc::printer::PrinterOptions {
+        limit: Some(2),
+        page,
+        ..Default::default()
+      };
+      doc::printer::print(&entries, &options)
+    };
+    assert_eq!(
+      page(0),
+      "function a()\n\nfunction b()\n\n\
+       3 more not shown, pass --page 2 to see the next 2\n"
+    );
+    assert_eq!(
+      page(1),
+      "function d()\n\nfunction e()\n\n\
+       1 more not shown, pass --page 3 to see the next 1\n"
+    );
+    assert_eq!(page(2), "Util\n  function c()\n\n");
+    assert_eq!(page(3), "Page 4 is empty, there are 3 pages\n");
+  }
+
+  #[test]
+  fn glob_matches() {
+    assert!(glob::is_match("**/_*.ts", "_util.ts"));
+    assert!(glob::is_match("**/_*.ts", "./http/_util.ts"));
+    assert!(glob::is_match("**/_*.ts", "http/internal/_util.ts"));
+    assert!(!glob::is_match("**/_*.ts", "http/util.ts"));
+    assert!(!glob::is_match("*.ts", "http/mod.ts"));
+    assert!(glob::is_match("http/**", "http/internal/mod.ts"));
+    assert!(glob::is_match("internal*", "internalState"));
+    assert!(glob::is_match("?ay", "day"));
+    assert!(!glob::is_match("internal*", "isInternal"));
+  }
+
+  #[test]
+  fn exclude_symbols() {
+    let source_code = r#"
+export function internalReset() {}
+export function serve() {}
+export namespace log {
+  export const internalLevel = 1;
+  export const level = 1;
+}
+"#;
+    let entries =
+      get_docs("http/_util.ts".to_string(), source_code.to_string()).unwrap();
+    let entries = doc::exclude::exclude_symbols(entries, &["internal*"]);
+    assert_eq!(
+      doc::tree::print_tree("http/_util.ts", &entries),
+      "http/_util.ts\n├── serve\n└── log\n    └── level\n"
+    );
+    let entries = doc::exclude::exclude_files(entries, &["**/_*.ts"]);
+    assert!(entries.is_empty());
+  }
+
+  #[test]
+  fn transform_doc_nodes() {
+    let source_code = r#"
+export function serve() {}
+export const port = 80;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let transformers: Vec<Box<dyn Transformer>> = vec![
+      Box::new(|mut doc_nodes: Vec<doc::DocNode>| {
+        for node in &mut doc_nodes {
+          node.name = node.name.to_uppercase();
+        }
+        doc_nodes
+      }),
+      // Doc nodes make it through JSON unchanged.
+      Box::new(doc::transform::CommandTransformer {
+        command: "cat".to_string(),
+      }),
+    ];
+    let entries = doc::transform::apply(entries, &transformers).unwrap();
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function SERVE()\n\nconst PORT = 80\n\n"
+    );
+
+    let failing: Vec<Box<dyn Transformer>> =
+      vec![Box::new(doc::transform::CommandTransformer {
+        command: "false".to_string(),
+      })];
+    assert_eq!(
+      doc::transform::apply(entries, &failing).unwrap_err(),
+      "false failed: "
+    );
+  }
+
+  #[test]
+  fn transform_large_input() {
+    let source_code: String = (0..2000)
+      .map(|i| {
+        format!(
+          "/** Serve number {}. */\nexport function serve{}() {{}}\n",
+          i, i
+        )
+      })
+      .collect();
+    let entries = get_docs("test.ts".to_string(), source_code).unwrap();
+    let json = serde_json::to_value(&entries).unwrap();
+    // Far more than fits in a pipe buffer.
+    assert!(json.to_string().len() > 64 * 1024);
+    let transformers: Vec<Box<dyn Transformer>> =
+      vec![Box::new(doc::transform::CommandTransformer {
+        command: "cat".to_string(),
+      })];
+    let transformed = doc::transform::apply(entries, &transformers).unwrap();
+    assert_eq!(serde_json::to_value(&transformed).unwrap(), json);
+  }
+
+  #[test]
+  fn interface_members() {
+    let source_code = r#"
+export interface Handler {
+  /** Handle a request. */
+  (request: Request): Response;
+  new (options?: Options): Handler;
+  readonly [header: string]: string;
+  name: string;
+  /** Close all connections. */
+  close?(force: boolean, ...reasons: string[]): Promise<void>;
+  listen({ port }: Options): void;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let interface_def = entries[0].interface_def.as_ref().unwrap();
+    assert_eq!(interface_def.methods.len(), 2);
+    assert_eq!(interface_def.methods[1].params[0].name, "{ port }");
+    let json = serde_json::to_value(interface_def).unwrap();
+    assert_eq!(
+      json["indexSignatures"][0]["params"][0]["name"],
+      serde_json::json!("header")
+    );
+    assert_eq!(
+      json["constructSignatures"][0]["tsType"]["repr"],
+      serde_json::json!("Handler")
+    );
+    let expected = "\
+interface Handler
+
+  (request: Request): Response
+    Handle a request.
+
+  new (options?: Options): Handler
+
+  readonly [header: string]: string
+
+  name: string
+
+  close?(force: boolean, ...reasons: string[]): Promise<void>
+    Close all connections.
+
+  listen({ port }: Options): void
+";
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Handler",
+        &Default::default()
+      )
+      .unwrap(),
+      expected
+    );
+  }
+
+  #[test]
+  fn declaration_type_params() {
+    let source_code = r#"
+export function first<T extends unknown[] = string[]>(items: T) {}
+export class Cache<K, V = unknown> extends Map<K, V> {}
+export interface Box<T extends object> extends Container<T> {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let function_def = entries[0].function_def.as_ref().unwrap();
+    assert_eq!(function_def.type_params[0].name, "T");
+    assert_eq!(
+      function_def.type_params[0].default.as_ref().unwrap().repr,
+      "string[]"
+    );
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function first<T extends unknown[] = string[]>(items: T)\n\n\
+       class Cache<K, V = unknown> extends Map<K, V>\n\n\
+       interface Box<T extends object> extends Container<T>\n\n"
+    );
+    assert!(doc::dts::print_dts(&entries[..2]).contains(
+      "export declare function first<T extends unknown[] = string[]>(items: T): void;"
+    ));
+  }
+
+  #[test]
+  fn class_heritage() {
+    let source_code = r#"
+export class Foo<T> extends Bar<T> implements Baz, Iterable<T> {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert_eq!(class_def.extends.as_deref(), Some("Bar"));
+    assert_eq!(class_def.implements, vec!["Baz", "Iterable<T>"]);
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "class Foo<T> extends Bar<T> implements Baz, Iterable<T>\n\n"
+    );
+    assert_eq!(
+      doc::dts::print_dts(&entries),
+      "export declare class Foo<T> extends Bar<T> implements Baz, Iterable<T> {\n}\n"
+    );
+  }
+
+  #[test]
+  fn print_inherited_interface_members() {
+    let source_code = r#"
+export interface Reader {
+  read(): string;
+}
+export namespace io {
+  export interface Closer {
+    close(): void;
+  }
+  export interface ReadCloser extends Reader, Closer, Iterable<string> {
+    name: string;
+  }
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let expected = "\
+interface ReadCloser extends Reader, Closer, Iterable<string>
+
+  name: string
+
+  Inherited from Reader:
+
+    read(): string
+
+  Inherited from io.Closer:
+
+    close(): void
+";
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "io.ReadCloser",
+        &Default::default()
+      )
+      .unwrap(),
+      expected
+    );
+  }
+
+  #[test]
+  fn function_overloads() {
+    let source_code = r#"
+/** Parse a number. */
+export function parse(input: string): number;
+/** Parse numbers. */
+export function parse(input: string[]): number[];
+export function parse(input: any): any {}
+export function other() {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 2);
+    let function_def = entries[0].function_def.as_ref().unwrap();
+    assert_eq!(function_def.overloads.len(), 1);
+    assert_eq!(
+      function_def.overloads[0].js_doc.as_deref(),
+      Some("/** Parse numbers. */")
+    );
+    let json = serde_json::to_value(&entries[0]).unwrap();
+    assert_eq!(
+      json["functionDef"]["overloads"][0]["params"][0]["name"],
+      serde_json::json!("input")
+    );
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function other()\n\n\
+       function parse(input: string): number\n\
+       function parse(input: string[]): number[]\n  Parse a number.\n\n"
+    );
+    assert_eq!(
+      doc::dts::print_dts(&entries[..1]),
+      "/**\n * Parse a number.\n */\n\
+       export declare function parse(input: string): number;\n\
+       /**\n * Parse numbers.\n */\n\
+       export declare function parse(input: string[]): number[];\n"
+    );
+  }
+
+  #[test]
+  fn optional_params() {
+    let source_code = r#"
+export function greet(name?: string, loud?) {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let params = &entries[0].function_def.as_ref().unwrap().params;
+    assert!(params[0].optional);
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function greet(name?: string, loud?)\n\n"
+    );
+    assert!(doc::markdown::print_markdown(&entries).contains("| `name?` |"));
+  }
+
+  #[test]
+  fn default_params() {
+    let source_code = r#"
+export function listen(port = 8080, host: string = "localhost") {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let params = &entries[0].function_def.as_ref().unwrap().params;
+    assert_eq!(params[0].default.as_deref(), Some("8080"));
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function listen(port = 8080, host: string = \"localhost\")\n\n"
+    );
+    assert_eq!(
+      doc::dts::print_dts(&entries),
+      "export declare function listen(port?, host?: string): void;\n"
+    );
+    assert!(doc::markdown::print_markdown(&entries)
+      .contains("| `host` | `string` | `\"localhost\"` |  |"));
+  }
+
+  #[test]
+  fn rest_params() {
+    let source_code = r#"
+export function log(level: number, ...args: string[]) {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let params = &entries[0].function_def.as_ref().unwrap().params;
+    assert_eq!(params[1].kind, doc::ParamKind::Rest);
+    assert_eq!(params[1].name, "args");
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function log(level: number, ...args: string[])\n\n"
+    );
+  }
+
+  #[test]
+  fn destructured_params() {
+    let source_code = r#"
+export function connect(
+  { host, port = 80 }: Options,
+  [x, y]: [number, number] = [0, 0],
+  ...[first]: string[]
+) {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let params = &entries[0].function_def.as_ref().unwrap().params;
+    assert_eq!(params[0].kind, doc::ParamKind::Object);
+    assert_eq!(params[1].kind, doc::ParamKind::Array);
+    assert_eq!(params[1].default.as_deref(), Some("[0, 0]"));
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function connect({ host, port = 80 }: Options, \
+       [x, y]: [number, number] = [0, 0], ...[first]: string[])\n\n"
+    );
+  }
+
+  #[test]
+  fn exclude_non_public_members() {
+    let source_code = r#"
+export class Server {
+  private constructor() {}
+  protected port: number;
+  public host: string;
+  private start() {}
+  close() {}
+}
+"#;
+    let mut entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert_eq!(
+      class_def.properties[0].accessibility,
+      Some(swc_ecma_ast::Accessibility::Protected)
+    );
+    assert_eq!(
+      class_def.properties[1].accessibility,
+      Some(swc_ecma_ast::Accessibility::Public)
+    );
+
+    doc::exclude::exclude_non_public_members(&mut entries);
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert!(class_def.constructors.is_empty());
+    let properties: Vec<&str> = class_def
+      .properties
+      .iter()
+      .map(|p| p.name.as_str())
+      .collect();
+    assert_eq!(properties, vec!["host"]);
+    let methods: Vec<&str> =
+      class_def.methods.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(methods, vec!["close"]);
+  }
+
+  #[test]
+  fn class_modifiers() {
+    let source_code = r#"
+export abstract class Foo {
+  static readonly VERSION: string = "1";
+  protected abstract run(): void;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert!(class_def.is_abstract);
+    let prop = &class_def.properties[0];
+    assert!(prop.is_static && prop.readonly && !prop.is_abstract);
+    let method = &class_def.methods[0];
+    assert!(method.is_abstract && !method.is_static);
+
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Foo",
+        &Default::default()
+      )
+      .unwrap(),
+      r#"abstract class Foo
+
+  static readonly VERSION: string = "1";
+
+  protected abstract run(): void;
+"#
+    );
+  }
+
+  #[test]
+  fn class_accessors() {
+    let source_code = r#"
+export class Config {
+  /** Name of the config. */
+  get name(): string { return ""; }
+  set name(value: string) {}
+  get size(): number { return 0; }
+  set path(value: string) {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert!(class_def.methods.is_empty());
+    let properties: Vec<(&str, Option<doc::AccessorKind>, bool)> = class_def
+      .properties
+      .iter()
+      .map(|prop| (prop.name.as_str(), prop.accessor, prop.readonly))
+      .collect();
+    assert_eq!(
+      properties,
+      vec![
+        ("name", Some(doc::AccessorKind::GetterSetter), false),
+        ("size", Some(doc::AccessorKind::Getter), true),
+        ("path", Some(doc::AccessorKind::Setter), false),
+      ]
+    );
+    let name = &class_def.properties[0];
+    assert_eq!(name.js_doc.as_deref(), Some("/** Name of the config. */"));
+    assert_eq!(name.snippet, "get name(): string\nset name(value: string)");
+    let path = &class_def.properties[2];
+    assert_eq!(path.ts_type.as_ref().unwrap().repr, "string");
+  }
+
+  #[test]
+  fn class_decorators() {
+    let source_code = r#"
+/** A service. */
+@Injectable()
+@sealed
+export class Service {
+  @Inject("db", { lazy: true }) db: Db;
+
+  @log
+  run(): void {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    assert_eq!(class_def.decorators[0].name, "Injectable");
+    assert_eq!(class_def.decorators[0].args, Some(vec![]));
+    assert_eq!(class_def.decorators[1].args, None);
+    assert_eq!(
+      class_def.properties[0].decorators[0].args,
+      Some(vec![r#""db""#.to_string(), "{ lazy: true }".to_string()])
+    );
+
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Service",
+        &Default::default()
+      )
+      .unwrap(),
+      r#"@Injectable()
+@sealed
+class Service
+  A service.
+
+  @Inject("db", { lazy: true })
+  db: Db;
+
+  @log
+  run(): void
+"#
+    );
+  }
+
+  #[test]
+  fn export_default() {
+    let source_code = r#"
+export const port = 80;
+
+/** Starts the server. */
+export default function serve(port: number): void {}
+"#;
+    let mut entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[1].name, "default");
+    assert!(entries[1].is_default);
+    assert_eq!(entries[1].kind, doc::DocNodeKind::Function);
+    assert_eq!(
+      entries[1].js_doc.as_deref(),
+      Some("/** Starts the server. */")
+    );
+    assert_eq!(
+      entries[1].snippet,
+      "export default function serve(port: number): void"
+    );
+
+    doc::import::set_import_statements(&mut entries, "./server.ts");
+    assert_eq!(
+      entries[1].import_statement.as_deref(),
+      Some(r#"import server from "./server.ts";"#)
+    );
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function default(port: number): void\n  Starts the server.\n\n\
+       const port = 80\n\n"
+    );
+
+    let source_code = r#"
+export default class {}
+export const version = "1.0";
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].name, "default");
+    assert_eq!(entries[0].kind, doc::DocNodeKind::Class);
+
+    let source_code = "export default { port: 80 };";
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].name, "default");
+    assert_eq!(entries[0].kind, doc::DocNodeKind::Variable);
+  }
+
+  #[test]
+  fn named_reexports() {
+    let dir = std::env::temp_dir()
+      .join(format!("deno_doc_reexports_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    std::fs::write(
+      dir.join("lib/server.ts"),
+      r#"
+/** Starts the server. */
+export function serve() {}
+export const port = 80;
+export default class Server {}
+"#,
+    )
+    .unwrap();
+    let mod_path = dir.join("mod.ts").to_string_lossy().to_string();
+    let source_code = r#"
+export { serve, port as defaultPort, default as Server } from "./lib/server.ts";
+"#;
+    let entries = get_docs(mod_path, source_code.to_string()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["serve", "defaultPort", "Server"]);
+    assert!(entries
+      .iter()
+      .all(|e| e.reexported_from.as_deref() == Some("./lib/server.ts")));
+    assert!(!entries[2].is_default);
+    assert_eq!(
+      entries[0].js_doc.as_deref(),
+      Some("/** Starts the server. */")
+    );
+    assert!(entries[0].location.filename.ends_with("server.ts"));
+  }
+
+  #[test]
+  fn star_reexports() {
+    let dir = std::env::temp_dir()
+      .join(format!("deno_doc_star_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+      dir.join("a.ts"),
+      r#"
+export * from "./b.ts";
+export function serve() {}
+export const port = 80;
+export default class Server {}
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+      dir.join("b.ts"),
+      r#"
+export * from "./a.ts";
+export const port = 8080;
+export const host = "localhost";
+"#,
+    )
+    .unwrap();
+    let mod_path = dir.join("mod.ts").to_string_lossy().to_string();
+    let source_code = r#"
+export * from "./a.ts";
+export * from "./b.ts";
+export const version = "1.0";
+"#;
+    let entries = get_docs(mod_path, source_code.to_string()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["version", "serve", "port", "host"]);
+    let port = &entries[2];
+    assert_eq!(port.reexported_from.as_deref(), Some("./a.ts"));
+    assert!(port.location.filename.ends_with("a.ts"));
+  }
+
+  #[test]
+  fn imported_references() {
+    let dir = std::env::temp_dir()
+      .join(format!("deno_doc_imports_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+      dir.join("types.ts"),
+      r#"
+/** Options of the server. */
+export interface Options {
+  port: number;
+}
+export type Handler = (req: Request) => Response;
+"#,
+    )
+    .unwrap();
+    let mod_path = dir.join("mod.ts").to_string_lossy().to_string();
+    let source_code = r#"
+import { Options } from "./types.ts";
+import type { Handler as RequestHandler } from "./types.ts";
+import { serve } from "https://deno.land/std/http/server.ts";
+
+export function listen(options: Options, handler: RequestHandler): void {}
+"#;
+    let entries = get_docs(mod_path.clone(), source_code.to_string()).unwrap();
+    let module_graph =
+      build_module_graph(&mod_path, source_code.to_string()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(module_graph.imports.len(), 1);
+    assert_eq!(module_graph.type_imports.len(), 1);
+    assert_eq!(module_graph.type_imports[0].local_name, "RequestHandler");
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &module_graph,
+        "listen",
+        &Default::default()
+      )
+      .unwrap(),
+      r#"function listen(options: Options, handler: RequestHandler): void
+
+  Imported from ./types.ts:
+    interface Options
+      Options of the server.
+
+  Imported from ./types.ts:
+    type Handler = (req: Request) => Response
+"#
+    );
+  }
+
+  #[test]
+  fn remote_modules() {
+    assert_eq!(
+      resolve_specifier(
+        "https://deno.land/std/log/mod.ts",
+        "../fmt/./colors.ts"
+      )
+      .as_deref(),
+      Some("https://deno.land/std/fmt/colors.ts")
+    );
+    assert_eq!(
+      resolve_specifier("mod.ts", "https://deno.land/std/log/mod.ts")
+        .as_deref(),
+      Some("https://deno.land/std/log/mod.ts")
+    );
+    assert_eq!(resolve_specifier("mod.ts", "react"), None);
+
+    let cache_dir = std::env::temp_dir()
+      .join(format!("deno_doc_cache_{}", std::process::id()));
+    let url = "https://deno.land/std/log/mod.ts?v=1";
+    let path = doc::fetch::cache_path(&cache_dir, url);
+    assert!(path.ends_with("https/deno.land/std/log/mod.ts_v=1"));
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "export const level = 1;").unwrap();
+    let source_code = doc::fetch::fetch(url, &cache_dir).unwrap();
+    std::fs::remove_dir_all(&cache_dir).unwrap();
+    assert_eq!(source_code, "export const level = 1;");
+  }
+
+  #[test]
+  fn declare_global() {
+    let source_code = r#"
+export const version = "1.0";
+
+/** Globals set by the library. */
+declare global {
+  /** Browser window. */
+  interface Window {
+    app: string;
+  }
+  const appVersion: string;
+}
+"#;
+    let mut entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    doc::import::set_import_statements(&mut entries, "test.ts");
+    let global = &entries[1];
+    assert_eq!(global.name, "global");
+    assert!(global.import_statement.is_none());
+    let namespace_def = global.namespace_def.as_ref().unwrap();
+    assert_eq!(namespace_def.augmentation, Some(doc::Augmentation::Global));
+    let names: Vec<&str> = namespace_def
+      .elements
+      .iter()
+      .map(|e| e.name.as_str())
+      .collect();
+    assert_eq!(names, vec!["Window", "appVersion"]);
+
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "const version = \"1.0\"\n\n\
+       declare global\n  Globals set by the library.\n\n\
+       \x20 const appVersion: string\n\n\
+       \x20 interface Window\n    Browser window.\n\n"
+    );
+    assert_eq!(
+      doc::dts::print_dts(&entries[1..]),
+      r#"/**
+ * Globals set by the library.
+ */
+declare global {
+  /**
+   * Browser window.
+   */
+  export interface Window {
+    app: string;
+  }
+
+  export const appVersion: string;
+}
+"#
+    );
+  }
+
+  #[test]
+  fn declare_module() {
+    let source_code = r#"
+/** Adds `serveStatic` to the router. */
+declare module "https://deno.land/x/oak/mod.ts" {
+  interface Router {
+    serveStatic(path: string): void;
+  }
+}
+
+declare module "https://deno.land/x/oak/mod.ts" {
+  function staticRouter(): Router;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "https://deno.land/x/oak/mod.ts");
+    let namespace_def = entries[0].namespace_def.as_ref().unwrap();
+    assert_eq!(namespace_def.augmentation, Some(doc::Augmentation::Module));
+    let names: Vec<&str> = namespace_def
+      .elements
+      .iter()
+      .map(|e| e.name.as_str())
+      .collect();
+    assert_eq!(names, vec!["Router", "staticRouter"]);
+
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "declare module \"https://deno.land/x/oak/mod.ts\"\n\
+       \x20 Adds `serveStatic` to the router.\n\n\
+       \x20 function staticRouter(): Router\n\n\
+       \x20 interface Router\n\n"
+    );
+  }
+
+  #[test]
+  fn reference_directives() {
+    let dir = std::env::temp_dir()
+      .join(format!("deno_doc_references_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("types")).unwrap();
+    std::fs::write(
+      dir.join("types/globals.d.ts"),
+      "export interface Config {}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("lib.d.ts"), "export const version: string;\n")
+      .unwrap();
+    let mod_path = dir.join("mod.d.ts").to_string_lossy().to_string();
+    let source_code = r#"// Copyright the authors.
+/// <reference path="types/globals.d.ts" />
+/// <reference types="./lib.d.ts" />
+/// <reference lib="dom" />
+
+export function start(config: Config): void;
+"#;
+    let entries = get_docs(mod_path, source_code.to_string()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["start", "Config", "version"]);
+    assert!(entries[1].location.filename.ends_with("globals.d.ts"));
+  }
+
+  #[test]
+  fn declaration_files() {
+    let source_code = r#"
+/** Parses the input. */
+declare function parse(input: string): parse.Result;
+declare function parse(input: Uint8Array): parse.Result;
+declare namespace parse {
+  /** Result of parse. */
+  interface Result {
+    ok: boolean;
+  }
+  const version: string;
+}
+/** Default options. */
+declare var options: { strict: boolean };
+declare class Parser {
+  constructor(input: string);
+}
+export = parse;
+"#;
+    let entries =
+      get_docs("index.d.ts".to_string(), source_code.to_string()).unwrap();
+    let names: Vec<(&str, bool)> = entries
+      .iter()
+      .map(|e| (e.name.as_str(), e.is_default))
+      .collect();
+    assert_eq!(
+      names,
+      vec![("default", true), ("options", false), ("Parser", false)]
+    );
+    let function_def = entries[0].function_def.as_ref().unwrap();
+    assert_eq!(function_def.overloads.len(), 1);
+    assert_eq!(
+      entries[0].js_doc.as_deref(),
+      Some("/** Parses the input. */")
+    );
+    assert_eq!(
+      entries[1].js_doc.as_deref(),
+      Some("/** Default options. */")
+    );
+    let elements = &entries[0].namespace_def.as_ref().unwrap().elements;
+    let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Result", "version"]);
+
+    // Outside of declaration files only exports are documented.
+    let entries =
+      get_docs("index.ts".to_string(), source_code.to_string()).unwrap();
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["default"]);
+  }
+
+  #[test]
+  fn tsx_modules() {
+    let source_code = r#"
+/** A button. */
+export function Button(props: { label: string }) {
+  return <button class="button">{props.label}</button>;
+}
+"#;
+    let entries =
+      get_docs("button.tsx".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].name, "Button");
+    assert_eq!(entries[0].js_doc.as_deref(), Some("/** A button. */"));
+
+    // Type assertions like `<T>value` are only allowed outside of TSX.
+    let source_code = "export const value = <number>input;";
+    assert!(get_docs("mod.ts".to_string(), source_code.to_string()).is_ok());
+  }
+
+  #[test]
+  fn async_generator_functions() {
+    let source_code = r#"
+export async function load(): Promise<string> {}
+export function* ids(): Generator<number> {}
+export async function* lines(): AsyncGenerator<string> {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let function_def = entries[2].function_def.as_ref().unwrap();
+    assert!(function_def.is_async);
+    assert!(function_def.is_generator);
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function* ids(): Generator<number>\n\n\
+       async function* lines(): AsyncGenerator<string>\n\n\
+       async function load(): Promise<string>\n\n"
+    );
+  }
+
+  #[test]
+  fn function_expressions() {
+    let source_code = r#"
+/** Handle a request. */
+export const handler = async (req: Request): Promise<Response> => {
+  return new Response();
+};
+export const double = <T>(value: T) => [value, value];
+export const parse = function (text: string): number {
+  return 0;
+};
+export const typed: (a: number) => void = (a) => {};
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].kind, doc::DocNodeKind::Function);
+    assert_eq!(
+      entries[0].snippet,
+      "export const handler = async (req: Request): Promise<Response> =>"
+    );
+    assert_eq!(
+      entries[0].js_doc,
+      Some("/** Handle a request. */".to_string())
+    );
+    assert_eq!(entries[3].kind, doc::DocNodeKind::Variable);
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function double<T>(value: T)\n\n\
+       async function handler(req: Request): Promise<Response>\n  \
+       Handle a request.\n\n\
+       function parse(text: string): number\n\n\
+       const typed: (a: number) => void\n\n"
+    );
+  }
+
+  #[test]
+  fn inferred_variable_types() {
+    let source_code = r#"
+export const VERSION = "1.0.0";
+export let retries = 3;
+export const ports = [80, 443];
+export const mixed = [1, "a"];
+export const defaults = { host: "localhost", port: 80 };
+export const cache = new Map<string, number>();
+export const debug = false;
+export const now = new Date();
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let types: Vec<String> = entries[..7]
+      .iter()
+      .map(|node| {
+        let ts_type = node.variable_def.as_ref().unwrap().ts_type.as_ref();
+        doc::printer::render_ts_type(ts_type.unwrap())
+      })
+      .collect();
+    assert_eq!(
+      types,
+      vec![
+        "\"1.0.0\"",
+        "number",
+        "number[]",
+        "(number | string)[]",
+        "{ host: string; port: number }",
+        "Map<string, number>",
+        "false",
+      ]
+    );
+    let ts_type = entries[7].variable_def.as_ref().unwrap().ts_type.as_ref();
+    let type_ref = ts_type.unwrap().type_ref.as_ref().unwrap();
+    assert_eq!(type_ref.type_name, "Date");
+    assert!(type_ref.type_params.is_none());
+    assert_eq!(
+      doc::printer::print(&entries[..2], &Default::default()),
+      "const VERSION = \"1.0.0\"\n\nlet retries: number = 3\n\n"
+    );
+  }
+
+  #[test]
+  fn export_assignment() {
+    let source_code = r#"
+/** A connection pool. */
+class Pool {
+  size: number;
+}
+function helper() {}
+export = Pool;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].kind, doc::DocNodeKind::Class);
+    assert_eq!(entries[0].name, "default");
+    assert!(entries[0].is_default);
+    assert_eq!(
+      entries[0].js_doc.as_deref(),
+      Some("/** A connection pool. */")
+    );
+
+    let source_code = r#"
+/** Settings. */
+export = { debug: false };
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].kind, doc::DocNodeKind::Variable);
+    assert_eq!(entries[0].name, "default");
+    assert_eq!(entries[0].js_doc.as_deref(), Some("/** Settings. */"));
+  }
+
+  #[test]
+  fn merged_declarations() {
+    let source_code = r#"
+/** Options of a server. */
+export interface Options {
+  port: number;
+}
+export namespace Options {
+  export const DEFAULT_PORT = 80;
+}
+export function serve(options: Options) {}
+/** Helpers for serve. */
+export namespace serve {
+  export function close() {}
+}
+export namespace serve {
+  export function restart() {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let kinds: Vec<(&str, doc::DocNodeKind)> = entries
+      .iter()
+      .map(|node| (node.name.as_str(), node.kind.clone()))
+      .collect();
+    assert_eq!(
+      kinds,
+      vec![
+        ("Options", doc::DocNodeKind::Interface),
+        ("serve", doc::DocNodeKind::Function)
+      ]
+    );
+    assert_eq!(
+      entries[1].js_doc.as_deref(),
+      Some("/** Helpers for serve. */")
+    );
+    let elements = &entries[1].namespace_def.as_ref().unwrap().elements;
+    let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["close", "restart"]);
+
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function serve(options: Options)\n  Helpers for serve.\n\n  \
+       function close()\n\n  \
+       function restart()\n\n\
+       interface Options\n  Options of a server.\n\n  \
+       const DEFAULT_PORT = 80\n\n"
+    );
+    assert_eq!(
+      doc::dts::print_dts(&entries[..1]),
+      "/**\n * Options of a server.\n */\n\
+       export interface Options {\n  port: number;\n}\n\
+       export declare namespace Options {\n  \
+       export const DEFAULT_PORT = 80;\n}\n"
+    );
+  }
+
+  #[test]
+  fn multiple_declarators() {
+    let source_code = r#"
+/** Bounds. */
+export const min = 1,
+  /** Upper bound. */
+  max: number = 10;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].name, "min");
+    assert_eq!(entries[0].snippet, "export const min = 1");
+    assert_eq!(entries[0].js_doc.as_deref(), Some("/** Bounds. */"));
+    assert_eq!(entries[1].name, "max");
+    assert_eq!(entries[1].snippet, "export const max: number = 10");
+    assert_eq!(entries[1].js_doc.as_deref(), Some("/** Upper bound. */"));
+    assert_eq!(entries[1].location.line, 5);
+    let ts_type = entries[1].variable_def.as_ref().unwrap().ts_type.as_ref();
+    assert_eq!(ts_type.unwrap().repr, "number");
+  }
+
+  #[test]
+  fn ambient_declarations() {
+    let source_code = r#"
+export declare function setTimeout(ms: number): number;
+export declare const Deno: { pid: number };
+export function now(): number {
+  return 0;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let ambient: Vec<(&str, bool)> = entries
+      .iter()
+      .map(|node| (node.name.as_str(), node.is_ambient))
+      .collect();
+    assert_eq!(
+      ambient,
+      vec![("setTimeout", true), ("Deno", true), ("now", false)]
+    );
+    assert_eq!(
+      doc::printer::print(&entries, &Default::default()),
+      "function now(): number\n\n\
+       declare function setTimeout(ms: number): number\n\n\
+       declare const Deno: { pid: number }\n\n"
+    );
+
+    let source_code = r#"
+interface Options {}
+declare global {
+  interface Window {}
+}
+"#;
+    let entries =
+      get_docs("lib.d.ts".to_string(), source_code.to_string()).unwrap();
+    assert!(entries[0].is_ambient);
+    assert!(!entries[1].is_ambient);
+  }
+
+  #[test]
+  fn const_enums() {
+    let source_code = r#"
+/** Access flags. */
+export const enum Flags {
+  None = 0,
+  /** Readable. */
+  Read = 1,
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries[0].js_doc, Some("/** Access flags. */".to_string()));
+    assert!(entries[0].snippet.starts_with("export const enum Flags {"));
+    assert_eq!(entries[0].location.line, 3);
+    assert_eq!(entries[0].location.col, 0);
+    let enum_def = entries[0].enum_def.as_ref().unwrap();
+    assert!(enum_def.is_const);
+    assert_eq!(enum_def.members.len(), 2);
+    assert_eq!(
+      doc::printer::format_signature(&entries[0], &Default::default()),
+      "const enum Flags"
+    );
+    assert_eq!(
+      doc::dts::print_dts(&entries),
+      "/**\n * Access flags.\n */\n\
+       export declare const enum Flags {\n  None = 0,\n  Read = 1,\n}\n"
+    );
+  }
+
+  #[test]
+  fn location_spans() {
+    let source_code = r#"
+export function add(a: number, b: number) {
+  return a + b;
+}
+export const one = 1, two = 2;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let location = &entries[0].location;
+    assert_eq!((location.line, location.col), (2, 0));
+    assert_eq!((location.end_line, location.end_col), (4, 1));
+    assert_eq!(
+      &source_code[location.byte_start..location.byte_end],
+      "export function add(a: number, b: number) {\n  return a + b;\n}"
+    );
+    let location = &entries[1].location;
+    assert_eq!(
+      &source_code[location.byte_start..location.byte_end],
+      "export const one = 1"
+    );
+    let location = &entries[2].location;
+    assert_eq!(
+      &source_code[location.byte_start..location.byte_end],
+      "two = 2"
+    );
+  }
+
+  #[test]
+  fn computed_member_names() {
+    let source_code = r#"
+const KEY = "key";
+export class Store {
+  [KEY] = 1;
+  ["literal"]: string;
+  ["foo" + "bar"](): void {}
+}
+export interface Shape {
+  [KEY]: number;
+  [KEY + "s"](): number[];
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    let names: Vec<&str> = class_def
+      .properties
+      .iter()
+      .map(|prop| prop.name.as_str())
+      .chain(class_def.methods.iter().map(|method| method.name.as_str()))
+      .collect();
+    assert_eq!(names, vec!["[KEY]", "literal", "[\"foo\" + \"bar\"]"]);
+    let interface_def = entries[1].interface_def.as_ref().unwrap();
+    assert_eq!(interface_def.properties[0].name, "[KEY]");
+    assert_eq!(interface_def.methods[0].name, "[KEY + \"s\"]");
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Shape",
+        &Default::default()
+      )
+      .unwrap(),
+      "interface Shape\n\n  [KEY]: number\n\n  [KEY + \"s\"](): number[]\n"
+    );
+  }
+
+  #[test]
+  fn symbol_members() {
+    let source_code = r#"
+/** Lines of a file. */
+export class Lines {
+  /** Each line. */
+  *[Symbol.iterator](): Iterator<string> {}
+  async *[Symbol.asyncIterator](): AsyncIterator<string> {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    let names: Vec<&str> = class_def
+      .methods
+      .iter()
+      .map(|method| method.name.as_str())
+      .collect();
+    assert_eq!(names, vec!["[Symbol.iterator]", "[Symbol.asyncIterator]"]);
+    assert!(class_def.is_iterable);
+    assert!(class_def.is_async_iterable);
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Lines",
+        &Default::default()
+      )
+      .unwrap(),
+      "class Lines\n  Lines of a file.\n\n  \
+       Iterable with for...of.\n\n  \
+       Async iterable with for await...of.\n\n  \
+       *[Symbol.iterator](): Iterator<string>\n    Each line.\n\n  \
+       async *[Symbol.asyncIterator](): AsyncIterator<string>\n"
+    );
+    assert_eq!(
+      doc::printer::print_details(
+        &entries,
+        &Default::default(),
+        "Lines#[Symbol.iterator]",
+        &Default::default()
+      )
+      .unwrap(),
+      "*[Symbol.iterator](): Iterator<string>\n  Each line.\n\n  \
+       Defined at test.ts:5:3\n"
+    );
+  }
+
+  #[test]
+  fn this_params() {
+    let source_code = r#"
+/** Handle a click. */
+export function onClick(this: HTMLElement, event: Event): void {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let params = &entries[0].function_def.as_ref().unwrap().params;
+    assert_eq!(params[0].kind, doc::ParamKind::This);
+    assert_eq!(params[1].kind, doc::ParamKind::Identifier);
+
+    let signature =
+      doc::printer::format_signature(&entries[0], &Default::default());
+    assert_eq!(
+      signature,
+      "function onClick(this: HTMLElement, event: Event): void"
+    );
+    assert_eq!(doc::usage::usage_stub(&entries[0]), "onClick({} as Event);");
+
+    let markdown = doc::markdown::print_markdown(&entries);
+    assert!(markdown.contains("`event`"));
+    assert!(!markdown.contains("| `this` |"));
+  }
+
+  #[test]
+  fn type_predicates() {
+    let source_code = r#"
+export function isString(value: unknown): value is string {
+  return typeof value === "string";
+}
+
+export interface Node {
+  isLeaf(): this is Leaf;
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let function = entries.iter().find(|e| e.name == "isString").unwrap();
+    let return_type = function
+      .function_def
+      .as_ref()
+      .unwrap()
+      .return_type
+      .as_ref()
+      .unwrap();
+    assert_eq!(
+      return_type.kind,
+      Some(doc::ts_type::TsTypeDefKind::TypePredicate)
+    );
+    let type_predicate = return_type.type_predicate.as_ref().unwrap();
+    assert_eq!(type_predicate.param_name, "value");
+    assert_eq!(
+      type_predicate.ts_type.as_ref().unwrap().keyword,
+      Some("string".to_string())
+    );
+    assert_eq!(
+      doc::printer::format_signature(function, &Default::default()),
+      "function isString(value: unknown): value is string"
+    );
+
+    let interface = entries.iter().find(|e| e.name == "Node").unwrap();
+    let method = &interface.interface_def.as_ref().unwrap().methods[0];
+    let return_type = method.return_type.as_ref().unwrap();
+    assert_eq!(doc::printer::render_ts_type(return_type), "this is Leaf");
+    assert_eq!(return_type.type_ref_names(), vec!["Leaf"]);
+  }
+
+  #[test]
+  fn assertion_signatures() {
+    let source_code = r#"
+export function assert(condition: unknown, msg?: string): asserts condition {}
+
+export function assertIsString(value: unknown): asserts value is string {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let return_type = entries[0]
+      .function_def
+      .as_ref()
+      .unwrap()
+      .return_type
+      .as_ref()
+      .unwrap();
+    let type_predicate = return_type.type_predicate.as_ref().unwrap();
+    assert!(type_predicate.asserts);
+    assert_eq!(type_predicate.param_name, "condition");
+    assert_eq!(type_predicate.ts_type, None);
+    assert_eq!(
+      doc::printer::format_signature(&entries[0], &Default::default()),
+      "function assert(condition: unknown, msg?: string): asserts condition"
+    );
+    assert_eq!(
+      doc::usage::usage_stub(&entries[0]),
+      "assert(undefined, \"\");"
+    );
+
+    assert_eq!(
+      doc::printer::format_signature(&entries[1], &Default::default()),
+      "function assertIsString(value: unknown): asserts value is string"
+    );
+  }
+
+  #[test]
+  fn big_int_literals() {
+    let source_code = r#"
+export const MAX = 9007199254740993n;
+export let counter = 0n;
+export type Flag = 1n | -2n;
+export const MIN = -9007199254740993n;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let ts_type = entries[0]
+      .variable_def
+      .as_ref()
+      .unwrap()
+      .ts_type
+      .as_ref()
+      .unwrap();
+    let literal = ts_type.literal.as_ref().unwrap();
+    assert_eq!(literal.kind, doc::ts_type::LiteralDefKind::BigInt);
+    assert_eq!(literal.big_int, Some("9007199254740993".to_string()));
+    assert_eq!(doc::printer::render_ts_type(ts_type), "9007199254740993n");
+
+    let ts_type = entries[1]
+      .variable_def
+      .as_ref()
+      .unwrap()
+      .ts_type
+      .as_ref()
+      .unwrap();
+    assert_eq!(doc::printer::render_ts_type(ts_type), "bigint");
+
+    let ts_type = &entries[2].type_alias_def.as_ref().unwrap().ts_type;
+    let union = ts_type.union.as_ref().unwrap();
+    let literal = union[0].literal.as_ref().unwrap();
+    assert_eq!(literal.kind, doc::ts_type::LiteralDefKind::BigInt);
+    assert_eq!(literal.big_int, Some("1".to_string()));
+    assert_eq!(doc::printer::render_ts_type(ts_type), "1n | -2n");
+    assert_eq!(entries[2].snippet, "export type Flag = 1n | -2n;");
+
+    let ts_type = entries[3]
+      .variable_def
+      .as_ref()
+      .unwrap()
+      .ts_type
+      .as_ref()
+      .unwrap();
+    assert_eq!(doc::printer::render_ts_type(ts_type), "-9007199254740993n");
+
+    // Literals with a prefix are kept as decimal digits as well.
+    let source_code = r#"
+export const HEX = 0x1Fn;
+export const OCTAL = 0o17n;
+export const BINARY = 0b101n;
+export type Large = 0xFFFFFFFFFFFFFFFFFn;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let big_int = |ts_type: &doc::ts_type::TsTypeDef| {
+      ts_type.literal.as_ref().unwrap().big_int.clone().unwrap()
+    };
+    let digits: Vec<String> = entries[..3]
+      .iter()
+      .map(|node| {
+        big_int(
+          node
+            .variable_def
+            .as_ref()
+            .unwrap()
+            .ts_type
+            .as_ref()
+            .unwrap(),
+        )
+      })
+      .collect();
+    assert_eq!(digits, vec!["31", "15", "5"]);
+    let ts_type = &entries[3].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(big_int(ts_type), "295147905179352825855");
+    assert_eq!(entries[0].snippet, "export const HEX = 0x1Fn;");
+  }
+
+  #[test]
+  fn generic_fn_types() {
+    let source_code = r#"
+export type Pick = <T extends Base>(items: T[], ...rest: string[]) => T;
+export type Make = new <T>({ a, b }: Options, ...args: T[]) => T;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let ts_type = &entries[0].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(
+      ts_type.kind,
+      Some(doc::ts_type::TsTypeDefKind::FnOrConstructor)
+    );
+    let fn_type = ts_type.fn_or_constructor.as_ref().unwrap();
+    assert_eq!(fn_type.type_params[0].name, "T");
+    assert_eq!(fn_type.params[1].kind, doc::ParamKind::Rest);
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "<T extends Base>(items: T[], ...rest: string[]) => T"
+    );
+    assert_eq!(ts_type.type_ref_names(), vec!["Base", "T", "T"]);
+
+    let ts_type = &entries[1].type_alias_def.as_ref().unwrap().ts_type;
+    let ctor_type = ts_type.fn_or_constructor.as_ref().unwrap();
+    let kinds: Vec<&doc::ParamKind> =
+      ctor_type.params.iter().map(|param| &param.kind).collect();
+    assert_eq!(kinds, vec![&doc::ParamKind::Object, &doc::ParamKind::Rest]);
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "new <T>({ a, b }: Options, ...args: T[]) => T"
+    );
+  }
+
+  #[test]
+  fn mapped_types() {
+    let source_code = r#"
+export type Partial<T> = { [K in keyof T]?: T[K] };
+export type Mutable<T> = { -readonly [K in keyof T]-?: T[K] };
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let ts_type = &entries[0].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(ts_type.kind, Some(doc::ts_type::TsTypeDefKind::Mapped));
+    let mapped_type = ts_type.mapped_type.as_ref().unwrap();
+    assert_eq!(mapped_type.type_param, "K");
+    assert_eq!(mapped_type.readonly, None);
+    assert_eq!(
+      mapped_type.optional,
+      Some(doc::ts_type::MappedModifier::True)
+    );
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "{ [K in keyof T]?: T[K] }"
+    );
+
+    let ts_type = &entries[1].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "{ -readonly [K in keyof T]-?: T[K] }"
+    );
+  }
+
+  #[test]
+  fn infer_types() {
+    let source_code = r#"
+export type Awaited<T> = T extends Promise<infer R> ? R : never;
+export type ElementOf<T> = T extends (infer E)[] ? E : never;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let ts_type = &entries[0].type_alias_def.as_ref().unwrap().ts_type;
+    let conditional = ts_type.conditional_type.as_ref().unwrap();
+    let type_params = conditional
+      .extends_type
+      .type_ref
+      .as_ref()
+      .unwrap()
+      .type_params
+      .as_ref()
+      .unwrap();
+    assert_eq!(
+      type_params[0].kind,
+      Some(doc::ts_type::TsTypeDefKind::Infer)
+    );
+    assert_eq!(type_params[0].infer, Some("R".to_string()));
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "T extends Promise<infer R> ? R : never"
+    );
+
+    let ts_type = &entries[1].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "T extends (infer E)[] ? E : never"
+    );
+  }
+
+  #[test]
+  fn import_types() {
+    let source_code = r#"
+export declare const server: import("./server.ts").Server<Options>;
+"#;
+    let entries =
+      get_docs("test.d.ts".to_string(), source_code.to_string()).unwrap();
+    let ts_type = entries[0]
+      .variable_def
+      .as_ref()
+      .unwrap()
+      .ts_type
+      .as_ref()
+      .unwrap();
+    assert_eq!(ts_type.kind, Some(doc::ts_type::TsTypeDefKind::Import));
+    let import_type = ts_type.import_type.as_ref().unwrap();
+    assert_eq!(import_type.specifier, "./server.ts");
+    assert_eq!(import_type.qualifier, Some("Server".to_string()));
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "import(\"./server.ts\").Server<Options>"
+    );
+    assert_eq!(ts_type.type_ref_names(), vec!["Options"]);
+  }
+
+  #[test]
+  fn qualified_param_types_in_markdown() {
+    let source_code = r#"
+export namespace http {
+  export interface Options {
+    /** Port to listen on. */
+    port: number;
+  }
+}
+
+/**
+ * Start a server.
+ * @param options - Server options.
+ */
+export function serve(options: http.Options): void {}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let markdown = doc::markdown::print_markdown(&entries);
+    assert!(markdown.contains("| `options` | `http.Options` |"));
+    assert!(markdown.contains("| `options.port` | `number` |"));
+    assert!(markdown.contains("Port to listen on."));
+  }
+
+  #[test]
+  fn type_literals() {
+    let source_code = r#"
+export type Handler = {
+  (event: Event): void;
+  readonly name: string;
+  options?: Options;
+  [key: string]: unknown;
+  close(): Promise<void>;
+};
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let ts_type = &entries[0].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(ts_type.kind, Some(doc::ts_type::TsTypeDefKind::TypeLiteral));
+    let type_literal = ts_type.type_literal.as_ref().unwrap();
+    assert_eq!(type_literal.properties.len(), 2);
+    assert_eq!(type_literal.methods[0].name, "close");
+    assert_eq!(type_literal.call_signatures.len(), 1);
+    assert_eq!(type_literal.index_signatures.len(), 1);
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "{ (event: Event): void; [key: string]: unknown; readonly name: string; \
+       options?: Options; close(): Promise<void> }"
+    );
+    assert_eq!(
+      ts_type.type_ref_names(),
+      vec!["Event", "Options", "Promise"]
+    );
+
+    let schema = doc::json_schema::json_schema(&entries);
+    assert_eq!(
+      schema["definitions"]["Handler"]["required"],
+      serde_json::json!(["name"])
+    );
+  }
+
+  #[test]
+  fn parse_errors() {
+    let source_code = "export function (a: string) {}\n";
+    let diagnostics =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap_err();
+    assert!(!diagnostics.is_empty());
+    assert!(!diagnostics[0].message().is_empty());
+  }
+
+  #[test]
+  fn labeled_tuples() {
+    let source_code = r#"
+export type Person = [name: string, age?: number, ...rest: boolean[]];
+export type Pair = [string, /* count */ number];
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(
+      entries[0].snippet,
+      "export type Person = [name: string, age?: number, ...rest: boolean[]];"
+    );
+    let ts_type = &entries[0].type_alias_def.as_ref().unwrap().ts_type;
+    let elements = ts_type.tuple.as_ref().unwrap();
+    let labels: Vec<_> =
+      elements.iter().map(|elem| elem.label.as_deref()).collect();
+    assert_eq!(labels, vec![Some("name"), Some("age"), Some("rest")]);
+    assert_eq!(
+      elements[1].kind,
+      Some(doc::ts_type::TsTypeDefKind::Optional)
+    );
+    assert_eq!(elements[2].kind, Some(doc::ts_type::TsTypeDefKind::Rest));
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "[name: string, age?: number, ...rest: boolean[]]"
+    );
 
-    let doc_entries = get_doc_nodes_for_module_body(&doc_parser, module.body);
-    Ok(doc_entries)
-  })
-}
+    let ts_type = &entries[1].type_alias_def.as_ref().unwrap().ts_type;
+    assert!(ts_type.tuple.as_ref().unwrap()[1].label.is_none());
+    assert_eq!(doc::printer::render_ts_type(ts_type), "[string, number]");
 
-fn main() {
-  let args: Vec<String> = std::env::args().collect();
+    // A labeled tuple followed by `:` isn't an index signature.
+    let source_code = r#"
+export type Params<T> = T extends string ? [name: string] : [id: number];
+export interface Headers {
+  host: string
+  [name: string]: string
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let ts_type = &entries[0].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "T extends string ? [name: string] : [id: number]"
+    );
+    let interface_def = entries[1].interface_def.as_ref().unwrap();
+    assert_eq!(interface_def.index_signatures[0].params[0].name, "name");
 
-  if args.len() < 2 {
-    eprintln!("Missing file name");
-    std::process::exit(1);
+    // Brackets in regular expressions and JSX text don't open tuples.
+    let source_code = r#"
+export const open = /[[]/;
+export const close = /]/;
+export const text = <p>[</p>;
+export type Pair = [key: string, value: number];
+"#;
+    let entries =
+      get_docs("test.tsx".to_string(), source_code.to_string()).unwrap();
+    let ts_type = &entries[3].type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(
+      doc::printer::render_ts_type(ts_type),
+      "[key: string, value: number]"
+    );
   }
 
-  let file_name = args[1].to_string();
-  let source_code =
-    std::fs::read_to_string(&file_name).expect("Failed to read file");
-  let doc_nodes =
-    get_docs(file_name, source_code).expect("Failed to print docs");
-
-  let docs_json = serde_json::to_string_pretty(&doc_nodes).unwrap();
-
-  println!("{}", docs_json);
+  #[test]
+  fn index_signatures() {
+    let source_code = r#"
+export interface Headers {
+  [name: string]: Header;
+  host: string;
 }
+export type Header = string | string[];
+export type Counts = { [key: string]: number };
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let index = &entries[0].interface_def.as_ref().unwrap().index_signatures[0];
+    assert_eq!(index.params[0].name, "name");
+    assert_eq!(
+      doc::printer::format_index_signature(index),
+      "[name: string]: Header"
+    );
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+    let schema = doc::json_schema::json_schema(&entries);
+    assert_eq!(
+      schema["definitions"]["Headers"]["additionalProperties"],
+      serde_json::json!({ "$ref": "#/definitions/Header" })
+    );
+    assert_eq!(
+      schema["definitions"]["Counts"]["additionalProperties"],
+      serde_json::json!({ "type": "number" })
+    );
+
+    let graph = doc::graph::SymbolGraph::new(&entries);
+    assert!(graph.relationships.iter().any(|relationship| {
+      relationship.source == "Headers" && relationship.target == "Header"
+    }));
+  }
 
   #[test]
-  fn export_fn() {
-    let source_code = r#"/**
-* Hello there, this is a multiline JSdoc.
-* 
-* It has many lines
-* 
-* Or not that many?
-*/
-export function foo(a: string, b: number): void {
-    console.log("Hello world");
+  fn construct_signatures() {
+    let source_code = r#"
+export interface ServerConstructor {
+  new (port: number): Server;
+  readonly prototype: Server;
 }
+export interface Server {}
 "#;
     let entries =
       get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
-    assert_eq!(entries.len(), 1);
-    let entry = &entries[0];
-    assert_eq!(entry.kind, doc::DocNodeKind::Function);
+    let interface_def = entries[0].interface_def.as_ref().unwrap();
+    assert_eq!(interface_def.construct_signatures.len(), 1);
     assert_eq!(
-      entry.js_doc,
-      Some(
-        r#"/**
-* Hello there, this is a multiline JSdoc.
-* 
-* It has many lines
-* 
-* Or not that many?
-*/"#
-          .to_string()
-      )
+      doc::printer::format_call_signature(
+        &interface_def.construct_signatures[0],
+        true
+      ),
+      "new (port: number): Server"
     );
+
+    let mermaid = doc::mermaid::print_mermaid(&entries);
+    assert!(mermaid.contains("    +new() Server\n    +prototype : Server\n"));
+
+    let graph = doc::graph::SymbolGraph::new(&entries);
+    assert!(graph.relationships.iter().any(|relationship| {
+      relationship.source == "ServerConstructor"
+        && relationship.target == "Server"
+    }));
+  }
+
+  #[test]
+  fn call_signatures() {
+    let source_code = r#"
+export interface Parser {
+  (input: string): Ast;
+  new (): Parser;
+  strict: boolean;
+}
+export interface Ast {}
+"#;
+    let entries =
+      get_docs("mod.ts".to_string(), source_code.to_string()).unwrap();
+    let interface_def = entries[0].interface_def.as_ref().unwrap();
     assert_eq!(
-      entry.snippet,
-      "export function foo(a: string, b: number): void"
+      doc::printer::format_call_signature(
+        &interface_def.call_signatures[0],
+        false
+      ),
+      "(input: string): Ast"
     );
+
+    let expected = "mod.ts
+├── Parser
+│   ├── (input: string): Ast
+│   ├── new (): Parser
+│   └── strict
+└── Ast
+";
+    assert_eq!(doc::tree::print_tree("mod.ts", &entries), expected);
+
+    let graph = doc::graph::SymbolGraph::new(&entries);
+    assert!(graph.relationships.iter().any(|relationship| {
+      relationship.source == "Parser" && relationship.target == "Ast"
+    }));
   }
 
   #[test]
-  fn export_const() {
-    let source_code =
-            "/** Something about fizzBuzz */\nexport const fizzBuzz = \"fizzBuzz\";\n";
+  fn type_only_exports() {
+    let source_code = r#"
+export interface Options {}
+export type Level = "info" | "warn";
+export class Logger {}
+export interface Handler {}
+export namespace Handler {
+  export const DEFAULT = 0;
+}
+"#;
     let entries =
       get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
-    assert_eq!(entries.len(), 1);
-    let entry = &entries[0];
-    assert_eq!(entry.kind, doc::DocNodeKind::Variable);
+    let type_only: Vec<(&str, bool)> = entries
+      .iter()
+      .map(|node| (node.name.as_str(), node.is_type_only))
+      .collect();
     assert_eq!(
-      entry.js_doc,
-      Some("/** Something about fizzBuzz */".to_string())
+      type_only,
+      vec![
+        ("Options", true),
+        ("Level", true),
+        ("Logger", false),
+        ("Handler", false)
+      ]
     );
-    assert_eq!(entry.snippet, "export const fizzBuzz = \"fizzBuzz\";");
   }
 
   #[test]
-  fn export_class() {
+  fn type_only_reexports() {
+    let dir = std::env::temp_dir()
+      .join(format!("deno_doc_type_only_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+      dir.join("logger.ts"),
+      "export class Logger {}\nexport function log() {}\n",
+    )
+    .unwrap();
+    let mod_path = dir.join("mod.ts").to_string_lossy().to_string();
     let source_code = r#"
-/** Class doc */
-export class Foobar extends Fizz implements Buzz {
-    private private1: boolean;
-    protected protected1: number;
-    public public1: boolean;
-    public2: number;
+export type { Logger } from "./logger.ts";
+export { log } from "./logger.ts";
+"#;
+    let entries = get_docs(mod_path, source_code.to_string()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
 
-    /** Constructor js doc */
-    constructor(name: string, private private2: number, protected protected2: number) {}
+    let type_only: Vec<(&str, bool)> = entries
+      .iter()
+      .map(|node| (node.name.as_str(), node.is_type_only))
+      .collect();
+    assert_eq!(type_only, vec![("Logger", true), ("log", false)]);
+  }
 
-    /** Async foo method */
-    async foo(): Promise<void> {
-        //
-    }
+  #[test]
+  fn object_literal_members() {
+    let source_code = r#"
+/** HTTP client. */
+export const api = {
+  /** Fetch a resource. */
+  get(url: string): Promise<string> {
+    return fetch(url).then((r) => r.text());
+  },
+  post: async (url: string, body: string) => {},
+  /** Timeout in milliseconds. */
+  timeout: 5000,
+  ...defaults,
+};
 
-    /** Sync bar method */
-    bar(): void {
-        //
-    }
-}
+export let mutable = { value: 1 };
 "#;
     let entries =
       get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
-    assert_eq!(entries.len(), 1);
-    let entry = &entries[0];
-    assert_eq!(entry.kind, doc::DocNodeKind::Class);
-    assert_eq!(entry.js_doc, Some("/** Class doc */".to_string()));
+    assert_eq!(entries.len(), 2);
+    assert!(entries[1].interface_def.is_none());
+
+    let interface_def = entries[0].interface_def.as_ref().unwrap();
+    let methods: Vec<&str> = interface_def
+      .methods
+      .iter()
+      .map(|m| m.name.as_str())
+      .collect();
+    assert_eq!(methods, vec!["get", "post"]);
     assert_eq!(
-      entry.snippet,
-      r#"export class Foobar extends Fizz implements Buzz"#
+      interface_def.methods[0].js_doc,
+      Some("/** Fetch a resource. */".to_string())
+    );
+    let timeout = &interface_def.properties[0];
+    assert_eq!(timeout.name, "timeout");
+    assert_eq!(timeout.ts_type.as_ref().unwrap().repr, "number");
+
+    let output = doc::printer::print(
+      &entries[..1],
+      &doc::printer::PrinterOptions::default(),
+    );
+    assert!(output.starts_with(
+      "const api: { timeout: number; get(url: string): Promise<string>; \
+       post(url: string, body: string) }\n"
+    ));
+    let details = doc::printer::print_details(
+      &entries,
+      &Default::default(),
+      "api#get",
+      &Default::default(),
+    )
+    .unwrap();
+    assert!(details.contains("get(url: string): Promise<string>"));
+    assert!(details.contains("Fetch a resource."));
+
+    assert_eq!(
+      doc::dts::print_dts(&entries[..1]),
+      "/**\n * HTTP client.\n */\n\
+       export declare const api: { timeout: number; \
+       get(url: string): Promise<string>; \
+       post(url: string, body: string) };\n"
     );
   }
 
   #[test]
-  fn export_interface() {
+  fn js_doc_types() {
     let source_code = r#"
 /**
- * Interface js doc
+ * Fetch a resource.
+ * @param {string} url
+ * @param {Object.<string, string>=} headers
+ * @param {number} [retries]
+ * @returns {Promise<Response>}
  */
-export interface Reader {
-    /** Read n bytes */
-    read(buf: Uint8Array, something: unknown): Promise<number>
-}
-    "#;
+export function get(url, headers, retries = 3) {}
+
+/** @type {?string} */
+export let token = null;
+
+/** Not typed. */
+export function untyped(value) {}
+"#;
+    let entries =
+      get_docs("test.js".to_string(), source_code.to_string()).unwrap();
+    let function_def = entries[0].function_def.as_ref().unwrap();
+    let params = &function_def.params;
+    assert_eq!(params[0].ts_type.as_ref().unwrap().repr, "string");
+    assert!(!params[0].optional);
+    let headers = params[1].ts_type.as_ref().unwrap();
+    assert_eq!(headers.repr, "Object<string, string>");
+    assert_eq!(headers.kind, Some(doc::ts_type::TsTypeDefKind::TypeRef));
+    assert!(params[1].optional);
+    assert_eq!(params[2].ts_type.as_ref().unwrap().repr, "number");
+    assert!(!params[2].optional);
+    let return_type = function_def.return_type.as_ref().unwrap();
+    assert_eq!(return_type.repr, "Promise<Response>");
+
+    let variable_def = entries[1].variable_def.as_ref().unwrap();
+    let ts_type = variable_def.ts_type.as_ref().unwrap();
+    assert_eq!(ts_type.repr, "string | null");
+    assert_eq!(ts_type.kind, Some(doc::ts_type::TsTypeDefKind::Union));
+
+    let params = &entries[2].function_def.as_ref().unwrap().params;
+    assert!(params[0].ts_type.is_none());
+
+    // TypeScript files keep their own types only.
     let entries =
       get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
-    assert_eq!(entries.len(), 1);
-    let entry = &entries[0];
-    assert_eq!(entry.kind, doc::DocNodeKind::Interface);
+    let function_def = entries[0].function_def.as_ref().unwrap();
+    assert!(function_def.params[0].ts_type.is_none());
+  }
+
+  #[test]
+  fn js_doc_typedefs() {
+    let source_code = r#"
+/**
+ * Options of the server.
+ * @typedef {Object} Options
+ * @property {number} port - Port to listen on.
+ * @property {string=} hostname
+ * @property {Object} [tls]
+ * @property {string} tls.cert
+ */
+
+/** @typedef {string | number} Id */
+
+/**
+ * Handles a request.
+ * @callback Handler
+ * @param {Request} request - The request.
+ * @param {Id} [id]
+ * @returns {Promise<Response>}
+ */
+
+/** @param {Options} options */
+export function serve(options) {}
+"#;
+    let entries =
+      get_docs("test.js".to_string(), source_code.to_string()).unwrap();
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["serve", "Options", "Id", "Handler"]);
+    // Only the closest JSDoc block documents `serve`.
+    let params = &entries[0].function_def.as_ref().unwrap().params;
+    assert_eq!(params[0].ts_type.as_ref().unwrap().repr, "Options");
+
+    let options = &entries[1];
+    assert_eq!(options.kind, doc::DocNodeKind::Interface);
+    assert!(options.is_type_only);
     assert_eq!(
-      entry.js_doc,
-      Some("/**\n * Interface js doc\n */".to_string())
+      options.js_doc,
+      Some("/**\n * Options of the server.\n */".to_string())
     );
+    assert_eq!(options.location.line, 2);
+    let properties = &options.interface_def.as_ref().unwrap().properties;
+    let props: Vec<(&str, bool)> = properties
+      .iter()
+      .map(|prop| (prop.name.as_str(), prop.optional))
+      .collect();
     assert_eq!(
-      entry.snippet,
-      r#"export interface Reader {
-    /** Read n bytes */
-    read(buf: Uint8Array, something: unknown): Promise<number>
-}"#
+      props,
+      vec![("port", false), ("hostname", true), ("tls", true)]
+    );
+    assert_eq!(
+      properties[0].js_doc,
+      Some("/** Port to listen on. */".to_string())
+    );
+    assert_eq!(
+      options.snippet,
+      "interface Options {\n  port: number;\n  hostname?: string;\n  tls?: Object;\n}"
+    );
+
+    let id = entries[2].type_alias_def.as_ref().unwrap();
+    assert_eq!(id.ts_type.kind, Some(doc::ts_type::TsTypeDefKind::Union));
+
+    let handler = &entries[3];
+    assert_eq!(handler.kind, doc::DocNodeKind::TypeAlias);
+    let ts_type = &handler.type_alias_def.as_ref().unwrap().ts_type;
+    assert_eq!(
+      ts_type.repr,
+      "(request: Request, id?: Id) => Promise<Response>"
+    );
+    assert_eq!(
+      ts_type.kind,
+      Some(doc::ts_type::TsTypeDefKind::FnOrConstructor)
     );
+    assert!(handler
+      .js_doc
+      .as_ref()
+      .unwrap()
+      .contains("@param {Request} request - The request."));
+
+    // TypeScript files have type declarations instead.
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    assert_eq!(entries.len(), 1);
   }
 
   #[test]
-  fn export_type_alias() {
+  fn variable_initializers() {
     let source_code = r#"
-/** Array holding numbers */
-export type NumberArray = Array<number>;
-    "#;
+export let retries = 3;
+export const cache = new Map<string, number>();
+export const handler = (req: Request) => {};
+export const typed: () => void = () => {};
+export const config = {
+  timeout: 5000,
+};
+"#;
     let entries =
       get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
-    assert_eq!(entries.len(), 1);
-    let entry = &entries[0];
-    assert_eq!(entry.kind, doc::DocNodeKind::TypeAlias);
+    let inits: Vec<Option<&str>> = entries
+      .iter()
+      .filter_map(|entry| entry.variable_def.as_ref())
+      .map(|variable_def| variable_def.init.as_deref())
+      .collect();
     assert_eq!(
-      entry.js_doc,
-      Some("/** Array holding numbers */".to_string())
+      inits,
+      vec![
+        Some("3"),
+        Some("new Map<string, number>()"),
+        None,
+        Some("{\n  timeout: 5000,\n}"),
+      ]
     );
-    assert_eq!(entry.snippet, "export type NumberArray = Array<number>;");
+    let timeout = &entries[4].interface_def.as_ref().unwrap().properties[0];
+    assert_eq!(timeout.ts_type.as_ref().unwrap().repr, "number");
+
+    assert_eq!(
+      doc::printer::print(&entries[1..2], &Default::default()),
+      "const cache: Map<string, number> = new Map<string, number>()\n\n"
+    );
+    let config = doc::printer::print(&entries[4..], &Default::default());
+    assert!(config.starts_with("const config: { timeout: number }\n"));
   }
 
   #[test]
-  fn export_enum() {
+  fn constructor_param_properties() {
     let source_code = r#"
+export class Person {
+  /**
+   * Create a person.
+   * @param name - Full name.
+   */
+  constructor(
+    public name: string,
+    private age: number,
+    /** Whether the person is active. */
+    readonly active = true,
+    nickname?: string,
+  ) {}
+}
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let class_def = entries[0].class_def.as_ref().unwrap();
+    let props: Vec<(&str, Option<swc_ecma_ast::Accessibility>, bool)> =
+      class_def
+        .properties
+        .iter()
+        .map(|prop| (prop.name.as_str(), prop.accessibility, prop.readonly))
+        .collect();
+    assert_eq!(
+      props,
+      vec![
+        ("name", Some(swc_ecma_ast::Accessibility::Public), false),
+        ("age", Some(swc_ecma_ast::Accessibility::Private), false),
+        ("active", None, true),
+      ]
+    );
+    let name = &class_def.properties[0];
+    assert_eq!(name.snippet, "public name: string");
+    assert_eq!(name.ts_type.as_ref().unwrap().repr, "string");
+    assert_eq!(name.js_doc, Some("/** Full name. */".to_string()));
+    let active = &class_def.properties[2];
+    assert_eq!(active.init.as_deref(), Some("true"));
+    assert_eq!(
+      active.js_doc,
+      Some("/** Whether the person is active. */".to_string())
+    );
+    assert_eq!(class_def.constructors.len(), 1);
+    // The properties are declared on their own, the constructor only takes
+    // their values.
+    assert_eq!(
+      doc::dts::print_dts(&entries),
+      r#"export declare class Person {
+  /**
+   * Create a person.
+   * @param name - Full name.
+   */
+  constructor(name: string, age: number, active?, nickname?: string);
+  /**
+   * Full name.
+   */
+  name: string;
+  private age: number;
+  /**
+   * Whether the person is active.
+   */
+  readonly active;
+}
+"#
+    );
+  }
+
+  #[test]
+  fn module_docs() {
+    let module_docs = |source_code: &str| {
+      get_module_docs("test.ts".to_string(), source_code.to_string()).unwrap()
+    };
+
+    let (module_doc, entries) = module_docs(
+      r#"
 /**
- * Some enum for good measure
+ * Utilities for servers.
+ * @module
  */
-export enum Hello {
-    World = "world",
-    Fizz = "fizz",
-    Buzz = "buzz",
+export function serve() {}
+"#,
+    );
+    let module_doc = module_doc.unwrap();
+    assert!(module_doc.contains("Utilities for servers."));
+    let json =
+      serde_json::to_value(module_json(Some(&module_doc), &entries)).unwrap();
+    assert_eq!(json[0]["kind"], "moduleDoc");
+    assert_eq!(json[0]["jsDoc"], module_doc.as_str());
+    assert_eq!(json[1]["name"], entries[0].name.as_str());
+    assert_eq!(entries[0].js_doc, None);
+    assert_eq!(
+      doc::printer::print_module_doc(&module_doc),
+      "Utilities for servers.\n\n"
+    );
+
+    let (module_doc, entries) = module_docs(
+      r#"
+/** Utilities for servers. */
+
+/** Start a server. */
+export function serve() {}
+"#,
+    );
+    assert_eq!(
+      module_doc,
+      Some("/** Utilities for servers. */".to_string())
+    );
+    assert_eq!(
+      entries[0].js_doc,
+      Some("/** Start a server. */".to_string())
+    );
+
+    let (module_doc, _) = module_docs(
+      r#"
+/** Utilities for servers. */
+import { Handler } from "./handler.ts";
+export function serve(handler: Handler) {}
+"#,
+    );
+    assert_eq!(
+      module_doc,
+      Some("/** Utilities for servers. */".to_string())
+    );
+
+    let (module_doc, _) = module_docs("/** Nothing to see. */\n");
+    assert_eq!(module_doc, Some("/** Nothing to see. */".to_string()));
+
+    // A single block right before a declaration documents the declaration.
+    let (module_doc, entries) = module_docs(
+      r#"
+/** Start a server. */
+export function serve() {}
+"#,
+    );
+    assert_eq!(module_doc, None);
+    assert_eq!(
+      entries[0].js_doc,
+      Some("/** Start a server. */".to_string())
+    );
+  }
+
+  #[test]
+  fn source_order() {
+    let source_code = r#"
+export namespace Shapes {
+  export const unit = 1;
+  export function area() {}
 }
-    "#;
+export interface Options {}
+export function serve() {}
+export const VERSION = "1.0.0";
+"#;
     let entries =
       get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
-    assert_eq!(entries.len(), 1);
-    let entry = &entries[0];
-    assert_eq!(entry.kind, doc::DocNodeKind::Enum);
+    let indexes: Vec<usize> = entries.iter().map(|node| node.index).collect();
+    assert_eq!(indexes, vec![0, 1, 2, 3]);
+    let elements = &entries[0].namespace_def.as_ref().unwrap().elements;
+    assert_eq!(elements[1].index, 1);
+
+    // A reordered list, like the output of a transform.
+    let mut entries = entries;
+    entries.reverse();
+    let options = doc::printer::PrinterOptions {
+      source_order: true,
+      ..Default::default()
+    };
     assert_eq!(
-      entry.js_doc,
-      Some("/**\n * Some enum for good measure\n */".to_string())
+      doc::printer::print(&entries, &options),
+      "namespace Shapes\n\n  const unit = 1\n\n  function area()\n\n\
+       interface Options\n\n\
+       function serve()\n\n\
+       const VERSION = \"1.0.0\"\n\n"
     );
+
+    // Variables apart from each other get a table each.
+    let source_code = r#"
+export const a = 1;
+export function f() {}
+export const b = 2;
+"#;
+    let entries =
+      get_docs("test.ts".to_string(), source_code.to_string()).unwrap();
+    let options = doc::printer::PrinterOptions {
+      source_order: true,
+      columns: true,
+      ..Default::default()
+    };
     assert_eq!(
-      entry.snippet,
-      r#"export enum Hello {
-    World = "world",
-    Fizz = "fizz",
-    Buzz = "buzz",
-}"#
+      doc::printer::print(&entries, &options),
+      "const  a  1\n\nfunction f()\n\nconst  b  2\n\n"
     );
   }
 }